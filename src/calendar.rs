@@ -1,5 +1,15 @@
 use std::collections::HashMap;
-use time::Month;
+
+use serde::{Deserialize, Serialize};
+use time::{Date, Month};
+use tracing::{debug, warn};
+
+use crate::{
+    error::{Error, Result},
+    model::{self, OverrideField},
+    parse_date,
+    support::fuzzy,
+};
 
 /// Represents a collection of Releases organized by month.
 pub type CalendarData = HashMap<Month, Releases>;
@@ -10,15 +20,27 @@ pub type Releases = HashMap<Day, Vec<Release>>;
 type Day = u8;
 
 /// Represents a heavy metal release.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Release {
     pub artist: String,
     pub album: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metallum_info: Option<MetallumInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub musicbrainz_info: Option<MusicBrainzInfo>,
+    /// The record label releasing the album, e.g. `Nuclear Blast`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The type of release set by a manual override, which wins over the sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub override_type: Option<String>,
+    /// The genre of the artist set by a manual override, which wins over the sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub override_genre: Option<String>,
 }
 
 /// Represents information about a release obtained from Metal Archives (Metallum).
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MetallumInfo {
     pub artist_link: String,
     pub album_link: String,
@@ -26,6 +48,17 @@ pub struct MetallumInfo {
     pub genre: String,
 }
 
+/// Represents information about a release obtained from [MusicBrainz](https://musicbrainz.org).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MusicBrainzInfo {
+    /// The MBID of the artist, if the release is credited to a single artist.
+    pub artist_id: Option<String>,
+    /// The MBID of the release group, i.e. the album regardless of its editions.
+    pub release_group_id: String,
+    pub release_type: String,
+    pub genre: String,
+}
+
 impl Release {
     /// Creates a new `Release` instance with the given artist and album names.
     pub fn new(artist: impl Into<String>, album: impl Into<String>) -> Self {
@@ -40,6 +73,10 @@ impl Release {
             artist: artist.into(),
             album,
             metallum_info: None,
+            musicbrainz_info: None,
+            label: None,
+            override_type: None,
+            override_genre: None,
         }
     }
 
@@ -59,6 +96,108 @@ impl Release {
         });
         self
     }
+
+    /// Adds MusicBrainz information to the `Release`.
+    pub fn with_musicbrainz(
+        mut self,
+        artist_id: Option<String>,
+        release_group_id: impl Into<String>,
+        release_type: impl Into<String>,
+        genre: impl Into<String>,
+    ) -> Self {
+        self.musicbrainz_info = Some(MusicBrainzInfo {
+            artist_id,
+            release_group_id: release_group_id.into(),
+            release_type: release_type.into(),
+            genre: genre.into(),
+        });
+        self
+    }
+
+    /// Sets the record label of the `Release`, unless it is empty.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        let label: String = label.into();
+        let label = label.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.label = (!label.is_empty()).then_some(label);
+        self
+    }
+
+    /// Recreates the scraped release from a release stored in the database and its artist.
+    ///
+    /// The Metallum and MusicBrainz information is only restored when the release has a
+    /// link to the source, which is how `create_or_update` tells that it came from there.
+    pub fn from_stored(release: &model::Release, artist: &model::Artist) -> Self {
+        let release_type = release.release_type.clone().unwrap_or_default();
+        let genre = artist.genre.clone().unwrap_or_default();
+
+        let mut stored = Self::new(&artist.name, &release.album);
+        if let Some(album_link) = &release.url_metallum {
+            stored = stored.with_metallum(
+                artist.url_metallum.clone().unwrap_or_default(),
+                album_link,
+                &release_type,
+                &genre,
+            );
+        }
+        if let Some(release_group_id) = &release.musicbrainz_id {
+            stored = stored.with_musicbrainz(
+                artist.musicbrainz_id.clone(),
+                release_group_id,
+                release_type,
+                genre,
+            );
+        }
+        match &release.label {
+            Some(label) => stored.with_label(label),
+            None => stored,
+        }
+    }
+
+    /// Returns the type of release, e.g. `Full-length`, from its override or from the
+    /// first source that knows it.
+    pub fn release_type(&self) -> Option<&str> {
+        self.override_type
+            .as_deref()
+            .or(self
+                .metallum_info
+                .as_ref()
+                .map(|info| info.release_type.as_str()))
+            .or(self
+                .musicbrainz_info
+                .as_ref()
+                .map(|info| info.release_type.as_str()))
+            .filter(|release_type| !release_type.is_empty())
+    }
+
+    /// Returns the genre of the artist from its override or from the first source that knows it.
+    pub fn genre(&self) -> Option<&str> {
+        self.override_genre
+            .as_deref()
+            .or(self.metallum_info.as_ref().map(|info| info.genre.as_str()))
+            .or(self
+                .musicbrainz_info
+                .as_ref()
+                .map(|info| info.genre.as_str()))
+            .filter(|genre| !genre.is_empty())
+    }
+}
+
+/// A release along with its day of the year, as written in a calendar file.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct DatedRelease {
+    pub month: u8,
+    pub day: u8,
+    #[serde(flatten)]
+    pub release: Release,
+}
+
+/// The JSON representation of a calendar written by the `export` command and read
+/// by the `import` command, e.g.
+/// `{"year":2024,"releases":[{"month":8,"day":30,"artist":"Wintersun","album":"Time II"}]}`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CalendarFile {
+    pub year: i32,
+    pub releases: Vec<DatedRelease>,
 }
 
 /// Represents a calendar of heavy metal releases for a specific year.
@@ -108,33 +247,254 @@ impl Calendar {
         self.data.get(&month).and_then(|map| map.get(&day))
     }
 
+    /// Returns the number of releases in the calendar.
+    pub fn len(&self) -> usize {
+        self.data
+            .values()
+            .flat_map(|releases| releases.values())
+            .map(Vec::len)
+            .sum()
+    }
+
+    /// Whether the calendar has no release at all.
+    pub fn is_empty(&self) -> bool {
+        self.data
+            .values()
+            .all(|releases| releases.values().all(Vec::is_empty))
+    }
+
+    /// Converts the calendar to its file representation, the releases ordered by date.
+    pub fn to_file(&self) -> CalendarFile {
+        CalendarFile {
+            year: self.year,
+            releases: self
+                .sorted_releases()
+                .map(|(month, day, release)| DatedRelease {
+                    month: month as u8,
+                    day,
+                    release: release.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Builds the calendar from its file representation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a release is dated on a day that does not exist
+    /// in the year of the calendar.
+    pub fn from_file(file: CalendarFile) -> Result<Self> {
+        let mut calendar = Self::new(file.year);
+
+        for dated in file.releases {
+            let month = Month::try_from(dated.month)
+                .ok()
+                .filter(|&month| Date::from_calendar_date(file.year, month, dated.day).is_ok())
+                .ok_or(Error::InvalidDate {
+                    year: file.year,
+                    month: dated.month,
+                    day: dated.day,
+                })?;
+            calendar.add_release(month, dated.day, dated.release);
+        }
+
+        Ok(calendar)
+    }
+
     /// Merges the current calendar with another calendar by combining their releases.
+    ///
+    /// The releases that are the same album of the same artist, despite differences
+    /// of case, diacritics, punctuation or spelling, are merged into one. The one with
+    /// the most metadata is kept along with its date. On a tie, the release of the
+    /// current calendar wins.
     pub fn merge(&self, other: &Self) -> Self {
-        let mut calendar = Calendar::new(self.year);
+        let mut merged: Vec<(Month, Day, Release, ReleaseKey)> = Vec::new();
+        let mut by_artist: HashMap<String, Vec<usize>> = HashMap::new();
 
-        for (&month, month_releases) in &self.data {
-            for (&day, day_releases) in month_releases {
-                for release in day_releases {
-                    calendar.add_release(month, day, release.clone());
+        for (month, day, release) in self.sorted_releases().chain(other.sorted_releases()) {
+            let key = ReleaseKey::new(release);
+
+            match find_duplicate(&merged, &by_artist, &key) {
+                Some(index) if release.richness() > merged[index].2.richness() => {
+                    let kept = &merged[index].2;
+                    debug!(
+                        "Merged `{} - {}` into `{} - {}`",
+                        kept.artist, kept.album, release.artist, release.album
+                    );
+                    let mut release = release.clone();
+                    release.fill_missing(kept);
+                    merged[index] = (month, day, release, key);
+                }
+                Some(index) => {
+                    let kept = &mut merged[index].2;
+                    debug!(
+                        "Merged `{} - {}` into `{} - {}`",
+                        release.artist, release.album, kept.artist, kept.album
+                    );
+                    kept.fill_missing(release);
+                }
+                None => {
+                    by_artist
+                        .entry(key.artist.clone())
+                        .or_default()
+                        .push(merged.len());
+                    merged.push((month, day, release.clone(), key));
                 }
             }
         }
 
-        for (&month, month_releases) in &other.data {
-            for (&day, day_releases) in month_releases {
-                for release in day_releases {
-                    calendar.add_release(month, day, release.clone());
+        let mut calendar = Calendar::new(self.year);
+        for (month, day, release, _) in merged {
+            calendar.add_release(month, day, release);
+        }
+        calendar
+    }
+
+    /// Applies the manual overrides to the releases, which are matched by their normalized
+    /// artist and album as scraped.
+    ///
+    /// The suppressed releases are left out, and the overrides of an album win over those
+    /// of every album of its artist. A date override to another year is ignored because
+    /// the release would vanish from both calendars.
+    pub fn apply_overrides(&self, overrides: &[model::Override]) -> Self {
+        let mut calendar = Calendar::new(self.year);
+
+        for (month, day, release) in self.sorted_releases() {
+            let key = ReleaseKey::new(release);
+            let mut matching = overrides
+                .iter()
+                .filter(|o| o.matches(&key.artist, &key.album))
+                .collect::<Vec<_>>();
+            matching.sort_by_key(|o| !o.album_key.is_empty());
+
+            let (mut month, mut day) = (month, day);
+            let mut corrected = release.clone();
+            let mut is_suppressed = false;
+
+            for o in matching {
+                let value = o.value.clone().unwrap_or_default();
+                match o.field.parse::<OverrideField>() {
+                    Ok(OverrideField::Suppress) => is_suppressed = true,
+                    Ok(OverrideField::Artist) => corrected.artist = value,
+                    Ok(OverrideField::Album) => corrected.album = value,
+                    Ok(OverrideField::Date) => match parse_date(&value) {
+                        Some(date) if date.year() == self.year => {
+                            month = date.month();
+                            day = date.day();
+                        }
+                        _ => warn!(
+                            "Ignoring the date override {value} of `{} - {}` outside of {}",
+                            release.artist, release.album, self.year
+                        ),
+                    },
+                    Ok(OverrideField::ReleaseType) => corrected.override_type = Some(value),
+                    Ok(OverrideField::Genre) => corrected.override_genre = Some(value),
+                    Ok(OverrideField::Label) => corrected.label = Some(value),
+                    Err(_) => warn!("Ignoring the override {} of field `{}`", o.id, o.field),
                 }
             }
+
+            if is_suppressed {
+                debug!("Suppressed `{} - {}`", release.artist, release.album);
+                continue;
+            }
+            calendar.add_release(month, day, corrected);
         }
 
         calendar
     }
+
+    /// Iterates over the releases ordered by date, and in their order within a day.
+    fn sorted_releases(&self) -> impl Iterator<Item = (Month, Day, &Release)> {
+        let mut releases = self
+            .data
+            .iter()
+            .flat_map(|(&month, releases)| {
+                releases.iter().flat_map(move |(&day, releases)| {
+                    releases.iter().map(move |release| (month, day, release))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        releases.sort_by_key(|(month, day, _)| (*month as u8, *day));
+        releases.into_iter()
+    }
+}
+
+impl Release {
+    /// Returns how much metadata the release has, to pick the best of duplicates.
+    fn richness(&self) -> u8 {
+        let metallum = match &self.metallum_info {
+            Some(info) => {
+                1 + u8::from(!info.genre.is_empty()) + u8::from(!info.release_type.is_empty())
+            }
+            None => 0,
+        };
+        metallum + u8::from(self.musicbrainz_info.is_some()) + u8::from(self.label.is_some())
+    }
+
+    /// Copies the metadata of a duplicate that the release lacks.
+    fn fill_missing(&mut self, duplicate: &Release) {
+        if self.metallum_info.is_none() {
+            self.metallum_info = duplicate.metallum_info.clone();
+        }
+        if self.musicbrainz_info.is_none() {
+            self.musicbrainz_info = duplicate.musicbrainz_info.clone();
+        }
+        if self.label.is_none() {
+            self.label = duplicate.label.clone();
+        }
+    }
+}
+
+/// The normalized artist and album of a release used to find its duplicates.
+struct ReleaseKey {
+    artist: String,
+    album: String,
+}
+
+impl ReleaseKey {
+    fn new(release: &Release) -> Self {
+        Self {
+            artist: fuzzy::artist_key(&release.artist),
+            album: fuzzy::normalize(&release.album),
+        }
+    }
+}
+
+/// Finds the index of the merged release that is the same album as the key.
+///
+/// The artists spelled the same way are looked at first. Otherwise, the artists
+/// spelled similarly are, provided they start with the same letter so that the
+/// whole calendar is not compared for every release.
+fn find_duplicate(
+    merged: &[(Month, Day, Release, ReleaseKey)],
+    by_artist: &HashMap<String, Vec<usize>>,
+    key: &ReleaseKey,
+) -> Option<usize> {
+    let first_letter = key.artist.chars().next();
+
+    let same_artist = by_artist.get(&key.artist).into_iter().flatten();
+    let similar_artists = by_artist
+        .iter()
+        .filter(|(artist, _)| {
+            **artist != key.artist
+                && artist.chars().next() == first_letter
+                && fuzzy::is_similar(artist, &key.artist)
+        })
+        .flat_map(|(_, indices)| indices);
+
+    same_artist
+        .chain(similar_artists)
+        .copied()
+        .find(|&index| fuzzy::is_similar(&merged[index].3.album, &key.album))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::fixtures;
 
     use crate::scraper::test_utils::compare_calendars;
 
@@ -153,6 +513,10 @@ mod tests {
                 artist: artist.to_string(),
                 album: album.to_string(),
                 metallum_info: None,
+                musicbrainz_info: None,
+                label: None,
+                override_type: None,
+                override_genre: None,
             }
         );
         Ok(())
@@ -171,6 +535,10 @@ mod tests {
                 artist: artist.to_string(),
                 album: String::from("Concrete Jungle"),
                 metallum_info: None,
+                musicbrainz_info: None,
+                label: None,
+                override_type: None,
+                override_genre: None,
             }
         );
         Ok(())
@@ -202,11 +570,24 @@ mod tests {
                 artist: artist.to_string(),
                 album: album.to_string(),
                 metallum_info: Some(metallum),
+                musicbrainz_info: None,
+                label: None,
+                override_type: None,
+                override_genre: None,
             }
         );
         Ok(())
     }
 
+    #[test]
+    fn test_release_with_label_ok() {
+        let got = Release::new("Wintersun", "Time II").with_label("  Nuclear   Blast ");
+        let got_empty = Release::new("Wintersun", "Time II").with_label(" ");
+
+        pretty_assertions::assert_eq!(got.label, Some(String::from("Nuclear Blast")));
+        pretty_assertions::assert_eq!(got_empty.label, None);
+    }
+
     #[test]
     fn test_default_calendar_empty_ok() -> Result<()> {
         let got = Calendar::new(2024);
@@ -262,6 +643,17 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_calendar_is_empty_ok() -> Result<()> {
+        let mut calendar = Calendar::new(2024);
+        assert!(calendar.is_empty());
+
+        calendar.add_release(Month::August, 30, Release::new("Wintersun", "Time II"));
+
+        assert!(!calendar.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn test_calendar_merge_ok() -> Result<()> {
         let calendar1 = a_calendar();
@@ -314,6 +706,301 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_calendar_merge_prefers_metallum_ok() -> Result<()> {
+        let wiki = a_calendar();
+        let mut metallum = Calendar::new(2025);
+        let arch_enemy = Release::new("Arch Enemy", "Blood Dynasty").with_metallum(
+            "https://www.metal-archives.com/bands/Arch_Enemy/10",
+            "https://www.metal-archives.com/albums/Arch_Enemy/Blood_Dynasty/1",
+            "Full-length",
+            "Melodic Death Metal",
+        );
+        metallum.add_release(Month::March, 28, arch_enemy.clone());
+
+        let got1 = wiki.merge(&metallum);
+        let got2 = metallum.merge(&wiki);
+
+        for got in [got1, got2] {
+            pretty_assertions::assert_eq!(
+                got.get_releases(Month::March, 28),
+                Some(&vec![arch_enemy.clone()])
+            );
+            pretty_assertions::assert_eq!(
+                got.sorted_releases().count(),
+                a_calendar().sorted_releases().count()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_calendar_merge_fuzzy_names_ok() -> Result<()> {
+        let wiki = a_calendar();
+        let mut metallum = Calendar::new(2025);
+        metallum.add_release(
+            Month::January,
+            10,
+            Release::new("Halo Effect", "March Of The Unheard"),
+        );
+        metallum.add_release(
+            Month::January,
+            24,
+            Release::new("Harakiri For The Sky", "Scorched Erth"),
+        );
+        metallum.add_release(
+            Month::January,
+            24,
+            Release::new("Avatarium", "Between You God the Devil & the Dead"),
+        );
+        metallum.add_release(Month::January, 3, Release::new("Faídra", "Dies Iræ"));
+
+        let got = wiki.merge(&metallum);
+
+        compare_calendars(got, a_calendar());
+        Ok(())
+    }
+
+    #[test]
+    fn test_calendar_merge_keeps_different_albums_ok() -> Result<()> {
+        let mut calendar1 = Calendar::new(2025);
+        calendar1.add_release(Month::August, 30, Release::new("Wintersun", "Time I"));
+        let mut calendar2 = Calendar::new(2025);
+        calendar2.add_release(Month::August, 30, Release::new("Wintersun", "Time II"));
+        calendar2.add_release(Month::August, 30, Release::new("Winterfylleth", "Time I"));
+
+        let got = calendar1.merge(&calendar2);
+
+        pretty_assertions::assert_eq!(
+            got.get_releases(Month::August, 30),
+            Some(&vec![
+                Release::new("Wintersun", "Time I"),
+                Release::new("Wintersun", "Time II"),
+                Release::new("Winterfylleth", "Time I"),
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_calendar_merge_split_release_ok() -> Result<()> {
+        let mut calendar1 = Calendar::new(2025);
+        calendar1.add_release(
+            Month::May,
+            2,
+            Release::new("The Halo Effect / Amorphis", "Northern Lights"),
+        );
+        let mut calendar2 = Calendar::new(2025);
+        let split = Release::new("Amorphis / Halo Effect", "Northern Lights").with_metallum(
+            "https://www.metal-archives.com/bands/Amorphis/12",
+            "https://www.metal-archives.com/albums/Amorphis/Northern_Lights/2",
+            "Split",
+            "Melodic Death Metal",
+        );
+        calendar2.add_release(Month::May, 9, split.clone());
+
+        let got = calendar1.merge(&calendar2);
+
+        pretty_assertions::assert_eq!(got.get_releases(Month::May, 2), None);
+        pretty_assertions::assert_eq!(got.get_releases(Month::May, 9), Some(&vec![split]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_calendar_merge_keeps_musicbrainz_ids_ok() -> Result<()> {
+        let metallum = Release::new("Arch Enemy", "Blood Dynasty").with_metallum(
+            "https://www.metal-archives.com/bands/Arch_Enemy/10",
+            "https://www.metal-archives.com/albums/Arch_Enemy/Blood_Dynasty/1",
+            "Full-length",
+            "Melodic Death Metal",
+        );
+        let musicbrainz = Release::new("Arch Enemy", "Blood Dynasty").with_musicbrainz(
+            Some(String::from("c2e7a7c0-5d46-4a66-b9a2-ea2f1e5ba8b5")),
+            "0b0d8c5b-5d1a-4e7e-b8f1-2a0a3ab1e4c4",
+            "Full-length",
+            "Melodic Death Metal",
+        );
+        let mut calendar1 = Calendar::new(2025);
+        calendar1.add_release(Month::March, 28, metallum.clone());
+        let mut calendar2 = Calendar::new(2025);
+        calendar2.add_release(Month::March, 28, musicbrainz.clone());
+
+        let got1 = calendar1.merge(&calendar2);
+        let got2 = calendar2.merge(&calendar1);
+
+        let want = Release {
+            musicbrainz_info: musicbrainz.musicbrainz_info,
+            ..metallum
+        };
+        for got in [got1, got2] {
+            pretty_assertions::assert_eq!(
+                got.get_releases(Month::March, 28),
+                Some(&vec![want.clone()])
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_calendar_file_round_trip_ok() -> Result<()> {
+        let calendar = a_calendar();
+
+        let json = serde_json::to_string(&calendar.to_file())?;
+        let got = Calendar::from_file(serde_json::from_str(&json)?)?;
+
+        pretty_assertions::assert_eq!(got.to_file(), calendar.to_file());
+        pretty_assertions::assert_eq!(got.len(), calendar.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_calendar_from_file_ok() -> Result<()> {
+        let json = r#"{"year":2024,"releases":[{"month":8,"day":30,"artist":"Wintersun","album":"Time II","label":"Nuclear Blast"}]}"#;
+
+        let got = Calendar::from_file(serde_json::from_str(json)?)?;
+
+        let mut want = Calendar::new(2024);
+        want.add_release(
+            Month::August,
+            30,
+            Release::new("Wintersun", "Time II").with_label("Nuclear Blast"),
+        );
+        pretty_assertions::assert_eq!(got, want);
+        Ok(())
+    }
+
+    #[test]
+    fn test_calendar_from_file_invalid_date_fails() {
+        for (month, day) in [(2, 30), (13, 1), (1, 0)] {
+            let file = CalendarFile {
+                year: 2025,
+                releases: vec![DatedRelease {
+                    month,
+                    day,
+                    release: Release::new("Wintersun", "Time II"),
+                }],
+            };
+
+            assert!(Calendar::from_file(file).is_err());
+        }
+    }
+
+    #[test]
+    fn test_release_from_stored_ok() {
+        let artist = model::Artist {
+            genre: Some(String::from("Symphonic Melodic Death Metal")),
+            url_metallum: Some(String::from(
+                "https://www.metal-archives.com/bands/Wintersun/89948",
+            )),
+            country: Some(String::from("Finland")),
+            ..fixtures::an_artist()
+        };
+        let release = model::Release {
+            release_type: Some(String::from("Full-length")),
+            url_metallum: Some(String::from(
+                "https://www.metal-archives.com/albums/Wintersun/Time_II/1233034",
+            )),
+            label: Some(String::from("Nuclear Blast")),
+            ..fixtures::a_release()
+        };
+
+        let got = Release::from_stored(&release, &artist);
+        let got_wikipedia = Release::from_stored(
+            &model::Release {
+                url_metallum: None,
+                label: None,
+                ..release
+            },
+            &artist,
+        );
+
+        pretty_assertions::assert_eq!(
+            got,
+            Release::new("Wintersun", "Time II")
+                .with_metallum(
+                    "https://www.metal-archives.com/bands/Wintersun/89948",
+                    "https://www.metal-archives.com/albums/Wintersun/Time_II/1233034",
+                    "Full-length",
+                    "Symphonic Melodic Death Metal",
+                )
+                .with_label("Nuclear Blast")
+        );
+        pretty_assertions::assert_eq!(got_wikipedia, Release::new("Wintersun", "Time II"));
+    }
+
+    #[test]
+    fn test_calendar_apply_overrides_ok() {
+        let an_override =
+            |artist: &str, album: &str, field: &str, value: Option<&str>| model::Override {
+                id: 1,
+                artist: artist.to_string(),
+                album: album.to_string(),
+                artist_key: fuzzy::artist_key(artist),
+                album_key: fuzzy::normalize(album),
+                field: field.to_string(),
+                value: value.map(String::from),
+                note: None,
+                created_at: 0,
+            };
+        let mut calendar = Calendar::new(2025);
+        calendar.add_release(
+            Month::January,
+            1,
+            Release::new("Hazzerd", "The 3rd Dimension"),
+        );
+        calendar.add_release(Month::January, 3, Release::new("Faidra", "Dies Irae"));
+        calendar.add_release(
+            Month::January,
+            10,
+            Release::new("Halo Effect", "March of the Unheard"),
+        );
+        calendar.add_release(
+            Month::January,
+            17,
+            Release::new("Grave Digger", "Bone Collector"),
+        );
+        calendar.add_release(
+            Month::January,
+            17,
+            Release::new("Grave Digger", "Symbol of Eternity"),
+        );
+        let overrides = [
+            an_override("Hazzerd", "", "suppress", None),
+            an_override("Faidra", "DIES IRAE!", "date", Some("2025-02-14")),
+            an_override("Faidra", "Dies Irae", "album", Some("Dies Iræ")),
+            an_override("The Halo Effect", "", "genre", Some("Melodic Death Metal")),
+            an_override("The Halo Effect", "", "release_type", Some("EP")),
+            an_override(
+                "The Halo Effect",
+                "March of the Unheard",
+                "release_type",
+                Some("Full-length"),
+            ),
+            an_override("Grave Digger", "Bone Collector", "date", Some("2026-01-17")),
+            an_override("Grave Digger", "", "label", Some("ROAR! Rock of Angels")),
+        ];
+
+        let got = calendar.apply_overrides(&overrides);
+
+        pretty_assertions::assert_eq!(got.get_releases(Month::January, 1), None);
+        pretty_assertions::assert_eq!(
+            got.get_releases(Month::February, 14),
+            Some(&vec![Release::new("Faidra", "Dies Iræ")])
+        );
+        let halo_effect = &got.get_releases(Month::January, 10).unwrap()[0];
+        pretty_assertions::assert_eq!(halo_effect.release_type(), Some("Full-length"));
+        pretty_assertions::assert_eq!(halo_effect.genre(), Some("Melodic Death Metal"));
+        pretty_assertions::assert_eq!(
+            got.get_releases(Month::January, 17),
+            Some(&vec![
+                Release::new("Grave Digger", "Bone Collector").with_label("ROAR! Rock of Angels"),
+                Release::new("Grave Digger", "Symbol of Eternity")
+                    .with_label("ROAR! Rock of Angels"),
+            ])
+        );
+        pretty_assertions::assert_eq!(got.len(), 4);
+    }
+
     fn a_calendar() -> Calendar {
         Calendar {
             year: 2025,
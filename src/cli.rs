@@ -0,0 +1,271 @@
+//! The command-line interface of the application.
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+/// Notifies you whenever there are new heavy metal album releases.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// The configuration file [default: `CONFIG_FILE` or ./config.toml]
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+impl Cli {
+    /// Returns the command to run, which is `serve` when none is given.
+    pub fn command(self) -> Command {
+        self.command.unwrap_or(Command::Serve {
+            no_initial_scrape: false,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Subcommand)]
+pub enum Command {
+    /// Serve the website and run the scheduled jobs (default)
+    Serve {
+        /// Serve without updating the calendar at startup
+        #[arg(long)]
+        no_initial_scrape: bool,
+    },
+    /// Scrape calendars from the sources and save them in the database
+    Scrape {
+        /// The year to scrape, repeatable [default: the current and next years]
+        #[arg(long = "year", value_name = "YEAR")]
+        years: Vec<i32>,
+        /// The source to scrape from, e.g. `wikipedia`, repeatable [default: the enabled sources]
+        #[arg(long = "source", value_name = "NAME")]
+        sources: Vec<String>,
+    },
+    /// Export the calendar of a year stored in the database as JSON
+    Export {
+        /// The year to export [default: the current year]
+        #[arg(long)]
+        year: Option<i32>,
+        /// The file to write to [default: the standard output]
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Import calendars exported as JSON into the database
+    Import {
+        /// The files to import, each holding the calendar of a year
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Apply the pending database migrations
+    Migrate,
+    /// Manage the manual overrides applied to the scraped releases
+    Overrides {
+        #[command(subcommand)]
+        command: OverridesCommand,
+    },
+    /// Manage the cached feeds
+    Feeds {
+        #[command(subcommand)]
+        command: FeedsCommand,
+    },
+    /// Manage the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Debug, PartialEq, Subcommand)]
+pub enum FeedsCommand {
+    /// Generate today's feeds again from the releases in the database
+    Regenerate,
+}
+
+#[derive(Debug, PartialEq, Subcommand)]
+pub enum OverridesCommand {
+    /// List the overrides
+    List,
+    /// Add an override, or replace the one of the same field for the same artist and album
+    Set {
+        /// The artist whose releases are corrected
+        #[arg(long)]
+        artist: String,
+        /// The album corrected [default: every album of the artist]
+        #[arg(long)]
+        album: Option<String>,
+        /// Why the correction is made
+        #[arg(long)]
+        note: Option<String>,
+        /// The corrected field: suppress, artist, album, date, release_type, genre or label
+        field: String,
+        /// The corrected value, e.g. 2024-08-30 for a date, omitted for suppress
+        value: Option<String>,
+    },
+    /// Delete an override by its ID
+    Remove { id: i32 },
+    /// Import overrides files written as JSON
+    Import {
+        /// The files to import
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Export the overrides as JSON
+    Export {
+        /// The file to write to [default: the standard output]
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, PartialEq, Subcommand)]
+pub enum ConfigCommand {
+    /// Check the configuration file and the environment variables for errors
+    Check,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[test]
+    fn test_cli_defaults_to_serve_ok() -> Result<()> {
+        let got = Cli::try_parse_from(["heavy-metal-notifier"])?.command();
+
+        pretty_assertions::assert_eq!(
+            got,
+            Command::Serve {
+                no_initial_scrape: false
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_parses_commands_ok() -> Result<()> {
+        let cases = [
+            (
+                vec!["serve", "--no-initial-scrape"],
+                Command::Serve {
+                    no_initial_scrape: true,
+                },
+            ),
+            (
+                vec![
+                    "scrape",
+                    "--year",
+                    "2024",
+                    "--year",
+                    "2025",
+                    "--source",
+                    "wikipedia",
+                ],
+                Command::Scrape {
+                    years: vec![2024, 2025],
+                    sources: vec![String::from("wikipedia")],
+                },
+            ),
+            (
+                vec!["export", "--year", "2024", "-o", "2024.json"],
+                Command::Export {
+                    year: Some(2024),
+                    output: Some(PathBuf::from("2024.json")),
+                },
+            ),
+            (
+                vec!["import", "2024.json", "2025.json"],
+                Command::Import {
+                    files: vec![PathBuf::from("2024.json"), PathBuf::from("2025.json")],
+                },
+            ),
+            (vec!["migrate"], Command::Migrate),
+            (
+                vec![
+                    "overrides",
+                    "set",
+                    "--artist",
+                    "Wintersun",
+                    "--album",
+                    "Time II",
+                    "date",
+                    "2024-08-30",
+                ],
+                Command::Overrides {
+                    command: OverridesCommand::Set {
+                        artist: String::from("Wintersun"),
+                        album: Some(String::from("Time II")),
+                        note: None,
+                        field: String::from("date"),
+                        value: Some(String::from("2024-08-30")),
+                    },
+                },
+            ),
+            (
+                vec!["overrides", "set", "--artist", "Hazzerd", "suppress"],
+                Command::Overrides {
+                    command: OverridesCommand::Set {
+                        artist: String::from("Hazzerd"),
+                        album: None,
+                        note: None,
+                        field: String::from("suppress"),
+                        value: None,
+                    },
+                },
+            ),
+            (
+                vec!["overrides", "remove", "3"],
+                Command::Overrides {
+                    command: OverridesCommand::Remove { id: 3 },
+                },
+            ),
+            (
+                vec!["overrides", "import", "overrides.json"],
+                Command::Overrides {
+                    command: OverridesCommand::Import {
+                        files: vec![PathBuf::from("overrides.json")],
+                    },
+                },
+            ),
+            (
+                vec!["feeds", "regenerate"],
+                Command::Feeds {
+                    command: FeedsCommand::Regenerate,
+                },
+            ),
+            (
+                vec!["config", "check"],
+                Command::Config {
+                    command: ConfigCommand::Check,
+                },
+            ),
+        ];
+
+        for (args, want) in cases {
+            let got =
+                Cli::try_parse_from(std::iter::once("heavy-metal-notifier").chain(args))?.command();
+
+            pretty_assertions::assert_eq!(got, want);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_config_file_ok() -> Result<()> {
+        let got = Cli::try_parse_from([
+            "heavy-metal-notifier",
+            "config",
+            "check",
+            "--config",
+            "/etc/metal/config.toml",
+        ])?;
+
+        pretty_assertions::assert_eq!(got.config, Some(PathBuf::from("/etc/metal/config.toml")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_import_without_files_fails() {
+        assert!(Cli::try_parse_from(["heavy-metal-notifier", "import"]).is_err());
+    }
+}
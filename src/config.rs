@@ -1,26 +1,100 @@
-use crate::{error::Result, support::env::get_env};
-use std::sync::OnceLock;
+//! The configuration of the application.
+//!
+//! The settings are read from a TOML file, `./config.toml` by default, and each of them
+//! can be overridden by its environment variable, e.g. `HOST_URL` for `server.host_url`.
+//! Every setting has a default, so neither the file nor the variables are required.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use serde::Deserialize;
+use time::Time;
+use tokio_cron_scheduler::Job;
 use tracing::warn;
 
+use crate::{
+    error::{Error, Result},
+    support::env::get_env,
+};
+
+/// The configuration file read when none is given.
+pub const DEFAULT_CONFIG_FILE: &str = "./config.toml";
+
+static INSTANCE: OnceLock<Config> = OnceLock::new();
+
 /// Gets the current Config struct. It will be initialized if not already done.
 pub fn config() -> &'static Config {
-    static INSTANCE: OnceLock<Config> = OnceLock::new();
-
     INSTANCE.get_or_init(|| {
-        Config::load_from_env().unwrap_or_else(|err| {
-            eprintln!("Could not load configuration: {err:?}");
+        Config::load(None).unwrap_or_else(|err| {
+            eprintln!("Could not load configuration: {err}");
             std::process::exit(1);
         })
     })
 }
 
+/// Loads the configuration from the file, or from the default one when `None`, and
+/// makes it the one returned by [`config`].
+///
+/// It must be called before anything reads the configuration for the file to be used.
+///
+/// # Errors
+///
+/// This function returns an [`Error::Config`] listing every problem of the configuration.
+pub fn init(file: Option<&Path>) -> Result<&'static Config> {
+    let config = Config::load(file)?;
+    Ok(INSTANCE.get_or_init(|| config))
+}
+
 /// Configuration struct for the application.
 #[derive(PartialEq, Debug)]
 #[allow(non_snake_case)]
 pub struct Config {
+    /// The password of the administrators, who can correct the data. Administration is disabled if unset.
+    pub ADMIN_PASSWORD: Option<String>,
+    /// The first past year whose releases are backfilled from Wikipedia, if any.
+    pub BACKFILL_FROM_YEAR: Option<i32>,
+    /// The cron expression of the search of the artists' Bandcamp pages.
+    pub BANDCAMP_CRON: String,
+    /// The delay before each request to Bandcamp.
+    pub BANDCAMP_DELAY: Duration,
+    /// The cron expression of the weekly calendar update.
+    pub CALENDAR_CRON: String,
+    /// The folder where the thumbnails of the album covers are stored.
+    pub COVERS_DIR: String,
+    /// The cron expression of the daily email digest.
+    pub DAILY_DIGEST_CRON: String,
+    /// The path of the SQLite database.
+    pub DATABASE_PATH: String,
+    /// The names of the release sources that are not scraped, e.g. `wikipedia`.
+    pub DISABLED_SOURCES: Vec<String>,
+    /// Whether the tracklists of the releases are scraped from their album page on The Metal Archives.
+    pub FETCH_TRACKLISTS: bool,
+    /// The number of days listed by the `mode=items` RSS feed.
+    pub FEED_ITEMS_DAYS: i64,
+    /// The number of daily items listed by the feeds.
+    pub FEED_MAX_ITEMS: i64,
+    /// The cron expression of the generation of the day's feeds.
+    pub FEEDS_CRON: String,
     pub HOST_URL: String,
     pub IS_PROD: bool,
+    /// The cron expression of the maintenance of the database.
+    pub MAINTENANCE_CRON: String,
+    /// The delay before each request to The Metal Archives, which asks for 3 seconds in its robots.txt.
+    pub METALLUM_DELAY: Duration,
+    /// The MusicBrainz JSON files, or the URL of a web service, releases are read from, if any.
+    pub MUSICBRAINZ_LOCATION: Option<String>,
     pub PORT: String,
+    /// The key used to sign the links sent by email, e.g. to confirm a subscription.
+    pub SECRET_KEY: Option<String>,
+    /// The local time of day at which the webhooks are notified of the day's releases.
+    pub WEBHOOK_TIME: Time,
+    /// The cron expression of the weekly email digest.
+    pub WEEKLY_DIGEST_CRON: String,
     pub smtp: Option<SmtpConfig>,
 }
 
@@ -33,40 +107,172 @@ pub struct SmtpConfig {
     pub email_admin: String,
 }
 
-impl Config {
-    /// Populates the Config's fields from the environment variables.
-    pub fn load_from_env() -> Result<Self> {
-        let mut base_url = get_env("HOST_URL").unwrap_or(String::from("http://localhost"));
-        let port = get_env("SERVICE_PORT").unwrap_or(String::from("7125"));
-        if base_url == "http://localhost" {
-            base_url = format!("{}:{}", base_url, port);
+/// A problem found in the configuration.
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    /// The configuration file could not be read.
+    Unreadable { path: String, reason: String },
+    /// The configuration file is not valid TOML, or has an unknown or mistyped setting.
+    Malformed { path: String, reason: String },
+    /// A setting has a value that cannot be used.
+    InvalidValue {
+        key: &'static str,
+        env: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+    /// A setting is missing while another one that needs it is set.
+    MissingValue {
+        key: &'static str,
+        env: &'static str,
+        required_by: &'static str,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreadable { path, reason } => {
+                write!(f, "could not read the configuration file {path}: {reason}")
+            }
+            Self::Malformed { path, reason } => {
+                write!(
+                    f,
+                    "invalid configuration file {path}: {}",
+                    reason.trim_end()
+                )
+            }
+            Self::InvalidValue {
+                key,
+                env,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid `{key}` (`{env}`) {value:?}, expected {expected}"
+            ),
+            Self::MissingValue {
+                key,
+                env,
+                required_by,
+            } => write!(
+                f,
+                "missing `{key}` (`{env}`), which is required when `{required_by}` is set"
+            ),
         }
+    }
+}
 
-        let smtp_username = get_env("SMTP_USERNAME");
-        let smtp_password = get_env("SMTP_PASSWORD");
+/// The configuration file, whose settings are all optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    server: ServerSection,
+    database: DatabaseSection,
+    scheduler: SchedulerSection,
+    scraper: ScraperSection,
+    smtp: SmtpSection,
+    feeds: FeedsSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ServerSection {
+    host_url: Option<String>,
+    port: Option<u16>,
+    is_prod: Option<bool>,
+    admin_password: Option<String>,
+    secret_key: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DatabaseSection {
+    path: Option<String>,
+    covers_dir: Option<String>,
+}
 
-        let smtp = if smtp_username.is_err() || smtp_password.is_err() {
-            warn!("Sending emails is disabled because not all SMTP environment variables are set.");
-            None
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SchedulerSection {
+    calendar_cron: Option<String>,
+    daily_digest_cron: Option<String>,
+    weekly_digest_cron: Option<String>,
+    bandcamp_cron: Option<String>,
+    feeds_cron: Option<String>,
+    maintenance_cron: Option<String>,
+    webhook_time: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ScraperSection {
+    disabled_sources: Option<Vec<String>>,
+    backfill_from_year: Option<i32>,
+    fetch_tracklists: Option<bool>,
+    musicbrainz_location: Option<String>,
+    bandcamp_delay_ms: Option<u64>,
+    metallum_delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SmtpSection {
+    host: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    email_admin: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FeedsSection {
+    max_items: Option<i64>,
+    items_days: Option<i64>,
+}
+
+impl Config {
+    /// Reads the configuration file and overrides its settings with the environment variables.
+    ///
+    /// Without a file, the one of the `CONFIG_FILE` environment variable is read, or else
+    /// `./config.toml` if it exists.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`Error::Config`] when the file cannot be read or parsed,
+    /// or listing every setting that has an invalid value.
+    pub fn load(file: Option<&Path>) -> Result<Self> {
+        let (path, is_required) = Self::file_path(file);
+        let file = read_file(&path, is_required).map_err(|err| Error::Config(vec![err]))?;
+
+        let mut settings = Settings::default();
+        let config = settings.resolve(file);
+
+        if settings.errors.is_empty() {
+            Ok(config)
         } else {
-            let smtp_host = get_env("SMTP_HOST").unwrap_or(String::from("smtp.gmail.com"));
-            let smtp_username = smtp_username?;
-            let smtp_email_admin = get_env("SMTP_EMAIL_ADMIN").unwrap_or(smtp_username.clone());
-
-            Some(SmtpConfig {
-                relay: smtp_host,
-                username: smtp_username,
-                password: smtp_password?,
-                email_admin: smtp_email_admin,
-            })
-        };
+            Err(Error::Config(settings.errors))
+        }
+    }
 
-        Ok(Self {
-            HOST_URL: base_url,
-            IS_PROD: get_env("IS_PROD").unwrap_or(String::from("false")) == "true",
-            PORT: port,
-            smtp,
-        })
+    /// Returns the path of the configuration file to read, and whether it must exist.
+    pub fn file_path(file: Option<&Path>) -> (PathBuf, bool) {
+        match file {
+            Some(path) => (path.to_path_buf(), true),
+            None => match get_env("CONFIG_FILE") {
+                Ok(path) => (PathBuf::from(path), true),
+                Err(_) => (PathBuf::from(DEFAULT_CONFIG_FILE), false),
+            },
+        }
+    }
+
+    /// Returns the cron expression of the daily webhook notifications.
+    pub fn webhook_cron(&self) -> String {
+        format!(
+            "0 {} {} * * *",
+            self.WEBHOOK_TIME.minute(),
+            self.WEBHOOK_TIME.hour()
+        )
     }
 
     /// Returns the base address with the protocol.
@@ -74,11 +280,8 @@ impl Config {
         let base_addr = &self.HOST_URL;
         let base_addr = base_addr
             .strip_prefix("http://")
-            .unwrap_or_else(|| {
-                base_addr
-                    .strip_prefix("https://")
-                    .expect("Expected address to start with http:// or https://")
-            })
+            .or_else(|| base_addr.strip_prefix("https://"))
+            .unwrap_or(base_addr)
             .to_string();
 
         if base_addr.starts_with("localhost") {
@@ -89,6 +292,364 @@ impl Config {
     }
 }
 
+/// Reads and parses the configuration file. A missing file that is not required is empty.
+fn read_file(path: &Path, is_required: bool) -> core::result::Result<ConfigFile, ConfigError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound && !is_required => {
+            return Ok(ConfigFile::default());
+        }
+        Err(err) => {
+            return Err(ConfigError::Unreadable {
+                path: path.display().to_string(),
+                reason: err.to_string(),
+            });
+        }
+    };
+
+    toml::from_str(&text).map_err(|err| ConfigError::Malformed {
+        path: path.display().to_string(),
+        reason: err.to_string(),
+    })
+}
+
+/// Merges the settings of the file with the environment variables and collects the
+/// problems found along the way.
+#[derive(Default)]
+struct Settings {
+    errors: Vec<ConfigError>,
+}
+
+impl Settings {
+    /// Builds the configuration, using the default of every setting that has an invalid value.
+    fn resolve(&mut self, file: ConfigFile) -> Config {
+        let ConfigFile {
+            server,
+            database,
+            scheduler,
+            scraper,
+            smtp,
+            feeds,
+        } = file;
+
+        let port = self
+            .get("server.port", "SERVICE_PORT", server.port, "a port number")
+            .unwrap_or(7125);
+        let mut host_url = self
+            .text("server.host_url", "HOST_URL", server.host_url)
+            .unwrap_or(String::from("http://localhost"));
+        if host_url == "http://localhost" {
+            host_url = format!("{host_url}:{port}");
+        }
+        if !host_url.starts_with("http://") && !host_url.starts_with("https://") {
+            self.invalid(
+                "server.host_url",
+                "HOST_URL",
+                &host_url,
+                "a URL starting with http:// or https://",
+            );
+        }
+
+        let secret_key = self.text("server.secret_key", "SECRET_KEY", server.secret_key);
+        if secret_key.is_none() {
+            warn!(
+                "Email subscriptions and webhooks are disabled because `server.secret_key` (`SECRET_KEY`) is not set."
+            );
+        }
+
+        let database_path = self
+            .text("database.path", "DATABASE_PATH", database.path)
+            .unwrap_or(String::from("./data/metal.db"));
+        let covers_dir = self
+            .text("database.covers_dir", "COVERS_DIR", database.covers_dir)
+            .unwrap_or(String::from("./data/covers"));
+
+        let calendar_cron = self.cron(
+            "scheduler.calendar_cron",
+            "CALENDAR_CRON",
+            scheduler.calendar_cron,
+            "0 0 0 * * 0",
+        );
+        let daily_digest_cron = self.cron(
+            "scheduler.daily_digest_cron",
+            "DAILY_DIGEST_CRON",
+            scheduler.daily_digest_cron,
+            "0 0 8 * * *",
+        );
+        let weekly_digest_cron = self.cron(
+            "scheduler.weekly_digest_cron",
+            "WEEKLY_DIGEST_CRON",
+            scheduler.weekly_digest_cron,
+            "0 0 8 * * Mon",
+        );
+        let bandcamp_cron = self.cron(
+            "scheduler.bandcamp_cron",
+            "BANDCAMP_CRON",
+            scheduler.bandcamp_cron,
+            "0 0 2 * * *",
+        );
+        let feeds_cron = self.cron(
+            "scheduler.feeds_cron",
+            "FEEDS_CRON",
+            scheduler.feeds_cron,
+            "0 1 0 * * *",
+        );
+        let maintenance_cron = self.cron(
+            "scheduler.maintenance_cron",
+            "MAINTENANCE_CRON",
+            scheduler.maintenance_cron,
+            "0 0 4 * * Sun",
+        );
+
+        let webhook_time = self
+            .text(
+                "scheduler.webhook_time",
+                "WEBHOOK_TIME",
+                scheduler.webhook_time,
+            )
+            .and_then(|value| {
+                parse_time(&value).or_else(|| {
+                    self.invalid(
+                        "scheduler.webhook_time",
+                        "WEBHOOK_TIME",
+                        &value,
+                        "a time of day formatted as HH:MM",
+                    );
+                    None
+                })
+            })
+            .unwrap_or(Time::from_hms(9, 0, 0).unwrap_or(Time::MIDNIGHT));
+
+        let disabled_sources = match get_env("DISABLED_SOURCES") {
+            Ok(value) => value.split(',').map(String::from).collect(),
+            Err(_) => scraper.disabled_sources.unwrap_or_default(),
+        }
+        .iter()
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+        let bandcamp_delay = self
+            .get(
+                "scraper.bandcamp_delay_ms",
+                "BANDCAMP_DELAY_MS",
+                scraper.bandcamp_delay_ms,
+                "a number of milliseconds",
+            )
+            .unwrap_or(200);
+        let metallum_delay = self
+            .get(
+                "scraper.metallum_delay_ms",
+                "METALLUM_DELAY_MS",
+                scraper.metallum_delay_ms,
+                "a number of milliseconds",
+            )
+            .unwrap_or(3000);
+
+        let smtp = self.smtp(smtp);
+
+        let feed_max_items = self.positive(
+            "feeds.max_items",
+            "FEED_MAX_ITEMS",
+            feeds.max_items,
+            12,
+            "a number of items from 1",
+        );
+        let feed_items_days = self.positive(
+            "feeds.items_days",
+            "FEED_ITEMS_DAYS",
+            feeds.items_days,
+            7,
+            "a number of days from 1",
+        );
+
+        Config {
+            ADMIN_PASSWORD: self.text(
+                "server.admin_password",
+                "ADMIN_PASSWORD",
+                server.admin_password,
+            ),
+            BACKFILL_FROM_YEAR: self.get(
+                "scraper.backfill_from_year",
+                "BACKFILL_FROM_YEAR",
+                scraper.backfill_from_year,
+                "a year",
+            ),
+            BANDCAMP_CRON: bandcamp_cron,
+            BANDCAMP_DELAY: Duration::from_millis(bandcamp_delay),
+            CALENDAR_CRON: calendar_cron,
+            COVERS_DIR: covers_dir,
+            DAILY_DIGEST_CRON: daily_digest_cron,
+            DATABASE_PATH: database_path,
+            DISABLED_SOURCES: disabled_sources,
+            FETCH_TRACKLISTS: self
+                .get(
+                    "scraper.fetch_tracklists",
+                    "FETCH_TRACKLISTS",
+                    scraper.fetch_tracklists,
+                    "true or false",
+                )
+                .unwrap_or(false),
+            FEED_ITEMS_DAYS: feed_items_days,
+            FEED_MAX_ITEMS: feed_max_items,
+            FEEDS_CRON: feeds_cron,
+            HOST_URL: host_url,
+            IS_PROD: self
+                .get("server.is_prod", "IS_PROD", server.is_prod, "true or false")
+                .unwrap_or(false),
+            MAINTENANCE_CRON: maintenance_cron,
+            METALLUM_DELAY: Duration::from_millis(metallum_delay),
+            MUSICBRAINZ_LOCATION: self.text(
+                "scraper.musicbrainz_location",
+                "MUSICBRAINZ_LOCATION",
+                scraper.musicbrainz_location,
+            ),
+            PORT: port.to_string(),
+            SECRET_KEY: secret_key,
+            WEBHOOK_TIME: webhook_time,
+            WEEKLY_DIGEST_CRON: weekly_digest_cron,
+            smtp,
+        }
+    }
+
+    /// Returns the value of the environment variable if it is set, or else the one of the file.
+    fn get<T: FromStr>(
+        &mut self,
+        key: &'static str,
+        env: &'static str,
+        file: Option<T>,
+        expected: &'static str,
+    ) -> Option<T> {
+        match get_env(env) {
+            Ok(value) => value.trim().parse().ok().or_else(|| {
+                self.invalid(key, env, &value, expected);
+                None
+            }),
+            Err(_) => file,
+        }
+    }
+
+    /// Returns the text of the setting, treating an empty one as unset.
+    fn text(
+        &mut self,
+        key: &'static str,
+        env: &'static str,
+        file: Option<String>,
+    ) -> Option<String> {
+        self.get(key, env, file, "a text")
+            .filter(|value| !value.trim().is_empty())
+    }
+
+    /// Returns the cron expression of the setting, or the default one if it is unset.
+    fn cron(
+        &mut self,
+        key: &'static str,
+        env: &'static str,
+        file: Option<String>,
+        default: &str,
+    ) -> String {
+        match self.text(key, env, file) {
+            Some(cron) if is_valid_cron(&cron) => cron,
+            Some(cron) => {
+                self.invalid(
+                    key,
+                    env,
+                    &cron,
+                    "a cron expression with seconds, e.g. `0 0 0 * * 0`",
+                );
+                String::from(default)
+            }
+            None => String::from(default),
+        }
+    }
+
+    /// Returns the number of the setting, or the default one if it is unset.
+    fn positive(
+        &mut self,
+        key: &'static str,
+        env: &'static str,
+        file: Option<i64>,
+        default: i64,
+        expected: &'static str,
+    ) -> i64 {
+        match self.get(key, env, file, expected) {
+            Some(value) if value >= 1 => value,
+            Some(value) => {
+                self.invalid(key, env, &value.to_string(), expected);
+                default
+            }
+            None => default,
+        }
+    }
+
+    /// Returns the SMTP settings when both the username and the password are set.
+    fn smtp(&mut self, smtp: SmtpSection) -> Option<SmtpConfig> {
+        let username = self.text("smtp.username", "SMTP_USERNAME", smtp.username);
+        let password = self.text("smtp.password", "SMTP_PASSWORD", smtp.password);
+        let relay = self
+            .text("smtp.host", "SMTP_HOST", smtp.host)
+            .unwrap_or(String::from("smtp.gmail.com"));
+        let email_admin = self.text("smtp.email_admin", "SMTP_EMAIL_ADMIN", smtp.email_admin);
+
+        match (username, password) {
+            (Some(username), Some(password)) => Some(SmtpConfig {
+                relay,
+                email_admin: email_admin.unwrap_or(username.clone()),
+                username,
+                password,
+            }),
+            (Some(_), None) => {
+                self.errors.push(ConfigError::MissingValue {
+                    key: "smtp.password",
+                    env: "SMTP_PASSWORD",
+                    required_by: "smtp.username",
+                });
+                None
+            }
+            (None, Some(_)) => {
+                self.errors.push(ConfigError::MissingValue {
+                    key: "smtp.username",
+                    env: "SMTP_USERNAME",
+                    required_by: "smtp.password",
+                });
+                None
+            }
+            (None, None) => {
+                warn!(
+                    "Sending emails is disabled because the SMTP username and password (`SMTP_USERNAME`, `SMTP_PASSWORD`) are not set."
+                );
+                None
+            }
+        }
+    }
+
+    fn invalid(
+        &mut self,
+        key: &'static str,
+        env: &'static str,
+        value: &str,
+        expected: &'static str,
+    ) {
+        self.errors.push(ConfigError::InvalidValue {
+            key,
+            env,
+            value: value.to_string(),
+            expected,
+        });
+    }
+}
+
+/// Parses a time of day formatted as HH:MM.
+fn parse_time(value: &str) -> Option<Time> {
+    let (hour, minute) = value.trim().split_once(':')?;
+    Time::from_hms(hour.parse().ok()?, minute.parse().ok()?, 0).ok()
+}
+
+/// Whether the scheduler accepts the cron expression.
+fn is_valid_cron(cron: &str) -> bool {
+    Job::new(cron, |_uuid, _l| {}).is_ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,14 +660,33 @@ mod tests {
     fn test_load_from_env_base_url_localhost_ok() -> Result<()> {
         let _guard = set_env_localhost();
 
-        let config = Config::load_from_env()?;
+        let config = Config::load(None)?;
 
         pretty_assertions::assert_eq!(
             config,
             Config {
+                ADMIN_PASSWORD: Some(String::from("an admin password")),
+                BACKFILL_FROM_YEAR: Some(2020),
+                BANDCAMP_CRON: String::from("0 0 2 * * *"),
+                BANDCAMP_DELAY: Duration::from_millis(200),
+                CALENDAR_CRON: String::from("0 0 0 * * 0"),
+                COVERS_DIR: String::from("./data/covers"),
+                DAILY_DIGEST_CRON: String::from("0 0 8 * * *"),
+                DATABASE_PATH: String::from("./data/metal.db"),
+                DISABLED_SOURCES: vec![String::from("wikipedia"), String::from("metallum")],
+                FETCH_TRACKLISTS: true,
+                FEED_ITEMS_DAYS: 7,
+                FEED_MAX_ITEMS: 12,
+                FEEDS_CRON: String::from("0 1 0 * * *"),
                 HOST_URL: String::from("http://localhost:7125"),
                 IS_PROD: true,
+                MAINTENANCE_CRON: String::from("0 0 4 * * Sun"),
+                METALLUM_DELAY: Duration::from_secs(3),
+                MUSICBRAINZ_LOCATION: Some(String::from("./data/musicbrainz")),
                 PORT: String::from("7125"),
+                SECRET_KEY: Some(String::from("a secret key")),
+                WEBHOOK_TIME: Time::from_hms(7, 30, 0)?,
+                WEEKLY_DIGEST_CRON: String::from("0 0 8 * * Mon"),
                 smtp: Some(SmtpConfig {
                     relay: String::from("smtp.gmail.com"),
                     username: String::from("my@gmail.com"),
@@ -122,14 +702,33 @@ mod tests {
     fn test_load_from_env_base_url_not_localhost_ok() -> Result<()> {
         let _guard = set_env_hosted();
 
-        let config = Config::load_from_env()?;
+        let config = Config::load(None)?;
 
         pretty_assertions::assert_eq!(
             config,
             Config {
+                ADMIN_PASSWORD: None,
+                BACKFILL_FROM_YEAR: None,
+                BANDCAMP_CRON: String::from("0 0 2 * * *"),
+                BANDCAMP_DELAY: Duration::from_millis(200),
+                CALENDAR_CRON: String::from("0 0 0 * * 0"),
+                COVERS_DIR: String::from("./data/covers"),
+                DAILY_DIGEST_CRON: String::from("0 0 8 * * *"),
+                DATABASE_PATH: String::from("./data/metal.db"),
+                DISABLED_SOURCES: Vec::new(),
+                FETCH_TRACKLISTS: false,
+                FEED_ITEMS_DAYS: 7,
+                FEED_MAX_ITEMS: 12,
+                FEEDS_CRON: String::from("0 1 0 * * *"),
                 HOST_URL: String::from("https://www.metal-releases.com"),
                 IS_PROD: false,
+                MAINTENANCE_CRON: String::from("0 0 4 * * Sun"),
+                METALLUM_DELAY: Duration::from_secs(3),
+                MUSICBRAINZ_LOCATION: None,
                 PORT: String::from("7125"),
+                SECRET_KEY: Some(String::from("a secret key")),
+                WEBHOOK_TIME: Time::from_hms(9, 0, 0)?,
+                WEEKLY_DIGEST_CRON: String::from("0 0 8 * * Mon"),
                 smtp: Some(SmtpConfig {
                     relay: String::from("smtp.gmail.com"),
                     username: String::from("my@gmail.com"),
@@ -141,10 +740,141 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_load_from_file_with_env_overrides_ok() -> Result<()> {
+        let _guard = set_env_file();
+
+        let config = Config::load(Some(Path::new("./tests/testdata/config/config.toml")))?;
+
+        pretty_assertions::assert_eq!(
+            config,
+            Config {
+                ADMIN_PASSWORD: Some(String::from("an admin password")),
+                BACKFILL_FROM_YEAR: Some(2015),
+                BANDCAMP_CRON: String::from("0 0 2 * * *"),
+                BANDCAMP_DELAY: Duration::from_millis(500),
+                CALENDAR_CRON: String::from("0 0 3 * * *"),
+                COVERS_DIR: String::from("/var/lib/metal/covers"),
+                DAILY_DIGEST_CRON: String::from("0 30 7 * * *"),
+                DATABASE_PATH: String::from("/var/lib/metal/metal.db"),
+                DISABLED_SOURCES: vec![String::from("wikipedia")],
+                FETCH_TRACKLISTS: true,
+                FEED_ITEMS_DAYS: 14,
+                FEED_MAX_ITEMS: 30,
+                FEEDS_CRON: String::from("0 1 0 * * *"),
+                HOST_URL: String::from("https://www.metal-releases.com"),
+                IS_PROD: false,
+                MAINTENANCE_CRON: String::from("0 0 4 * * Sun"),
+                METALLUM_DELAY: Duration::from_secs(5),
+                MUSICBRAINZ_LOCATION: None,
+                PORT: String::from("8080"),
+                SECRET_KEY: Some(String::from("a secret key")),
+                WEBHOOK_TIME: Time::from_hms(18, 0, 0)?,
+                WEEKLY_DIGEST_CRON: String::from("0 0 8 * * Mon"),
+                smtp: Some(SmtpConfig {
+                    relay: String::from("smtp.fastmail.com"),
+                    username: String::from("metal@fastmail.com"),
+                    password: String::from("a password"),
+                    email_admin: String::from("metal@fastmail.com"),
+                }),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_invalid_values_fails() {
+        let _guard = set_env_invalid();
+
+        let got = Config::load(Some(Path::new("./tests/testdata/config/invalid.toml")));
+
+        let Err(Error::Config(errors)) = got else {
+            panic!("expected configuration errors, got {got:?}");
+        };
+        pretty_assertions::assert_eq!(
+            errors,
+            vec![
+                ConfigError::InvalidValue {
+                    key: "server.port",
+                    env: "SERVICE_PORT",
+                    value: String::from("http"),
+                    expected: "a port number",
+                },
+                ConfigError::InvalidValue {
+                    key: "server.host_url",
+                    env: "HOST_URL",
+                    value: String::from("www.metal-releases.com"),
+                    expected: "a URL starting with http:// or https://",
+                },
+                ConfigError::InvalidValue {
+                    key: "scheduler.calendar_cron",
+                    env: "CALENDAR_CRON",
+                    value: String::from("every sunday"),
+                    expected: "a cron expression with seconds, e.g. `0 0 0 * * 0`",
+                },
+                ConfigError::InvalidValue {
+                    key: "scheduler.webhook_time",
+                    env: "WEBHOOK_TIME",
+                    value: String::from("25:00"),
+                    expected: "a time of day formatted as HH:MM",
+                },
+                ConfigError::MissingValue {
+                    key: "smtp.password",
+                    env: "SMTP_PASSWORD",
+                    required_by: "smtp.username",
+                },
+                ConfigError::InvalidValue {
+                    key: "feeds.max_items",
+                    env: "FEED_MAX_ITEMS",
+                    value: String::from("0"),
+                    expected: "a number of items from 1",
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_unknown_setting_fails() {
+        let _guard = set_env_invalid();
+
+        let got = Config::load(Some(Path::new("./tests/testdata/config/unknown.toml")));
+
+        let Err(Error::Config(errors)) = got else {
+            panic!("expected configuration errors, got {got:?}");
+        };
+        assert!(matches!(
+            &errors[..],
+            [ConfigError::Malformed { reason, .. }] if reason.contains("unknown field `hots_url`")
+        ));
+    }
+
+    #[test]
+    fn test_load_missing_file_fails() {
+        let _guard = set_env_invalid();
+
+        let got = Config::load(Some(Path::new("./tests/testdata/config/missing.toml")));
+
+        assert!(matches!(
+            got,
+            Err(Error::Config(errors)) if matches!(errors[..], [ConfigError::Unreadable { .. }])
+        ));
+    }
+
+    #[test]
+    fn test_webhook_cron_ok() -> Result<()> {
+        let _guard = set_env_localhost();
+        let config = Config::load(None)?;
+
+        let cron = config.webhook_cron();
+
+        pretty_assertions::assert_eq!(cron, "0 30 7 * * *");
+        Ok(())
+    }
+
     #[test]
     fn test_local_server_addr_localhost_ok() -> Result<()> {
         let _guard = set_env_localhost();
-        let config = Config::load_from_env()?;
+        let config = Config::load(None)?;
 
         let addr = config.local_server_addr();
 
@@ -155,7 +885,7 @@ mod tests {
     #[test]
     fn test_local_server_addr_hosted_ok() -> Result<()> {
         let _guard = set_env_hosted();
-        let config = Config::load_from_env()?;
+        let config = Config::load(None)?;
 
         let addr = config.local_server_addr();
 
@@ -165,6 +895,7 @@ mod tests {
 
     fn set_env_localhost() -> env_lock::EnvGuard<'static> {
         env_lock::lock_env([
+            ("CONFIG_FILE", None),
             ("HOST_URL", Some("http://localhost")),
             ("SERVICE_PORT", Some("7125")),
             ("IS_PROD", Some("true")),
@@ -172,11 +903,31 @@ mod tests {
             ("SMTP_USERNAME", Some("my@gmail.com")),
             ("SMTP_PASSWORD", Some("my app pass word")),
             ("SMTP_EMAIL_ADMIN", Some("admin@email.com")),
+            ("SECRET_KEY", Some("a secret key")),
+            ("WEBHOOK_TIME", Some("07:30")),
+            ("ADMIN_PASSWORD", Some("an admin password")),
+            ("BACKFILL_FROM_YEAR", Some("2020")),
+            ("DISABLED_SOURCES", Some("Wikipedia, metallum,")),
+            ("FETCH_TRACKLISTS", Some("true")),
+            ("MUSICBRAINZ_LOCATION", Some("./data/musicbrainz")),
+            ("DATABASE_PATH", None),
+            ("CALENDAR_CRON", None),
+            ("COVERS_DIR", None),
+            ("DAILY_DIGEST_CRON", None),
+            ("WEEKLY_DIGEST_CRON", None),
+            ("BANDCAMP_CRON", None),
+            ("FEEDS_CRON", None),
+            ("MAINTENANCE_CRON", None),
+            ("BANDCAMP_DELAY_MS", None),
+            ("METALLUM_DELAY_MS", None),
+            ("FEED_MAX_ITEMS", None),
+            ("FEED_ITEMS_DAYS", None),
         ])
     }
 
     fn set_env_hosted() -> env_lock::EnvGuard<'static> {
         env_lock::lock_env([
+            ("CONFIG_FILE", None),
             ("HOST_URL", Some("https://www.metal-releases.com")),
             ("SERVICE_PORT", Some("7125")),
             ("IS_PROD", Some("false")),
@@ -184,6 +935,89 @@ mod tests {
             ("SMTP_USERNAME", Some("my@gmail.com")),
             ("SMTP_PASSWORD", Some("my app pass word")),
             ("SMTP_EMAIL_ADMIN", Some("admin@email.com")),
+            ("SECRET_KEY", Some("a secret key")),
+            ("WEBHOOK_TIME", None),
+            ("ADMIN_PASSWORD", None),
+            ("BACKFILL_FROM_YEAR", None),
+            ("DISABLED_SOURCES", None),
+            ("FETCH_TRACKLISTS", None),
+            ("MUSICBRAINZ_LOCATION", None),
+            ("DATABASE_PATH", None),
+            ("CALENDAR_CRON", None),
+            ("COVERS_DIR", None),
+            ("DAILY_DIGEST_CRON", None),
+            ("WEEKLY_DIGEST_CRON", None),
+            ("BANDCAMP_CRON", None),
+            ("FEEDS_CRON", None),
+            ("MAINTENANCE_CRON", None),
+            ("BANDCAMP_DELAY_MS", None),
+            ("METALLUM_DELAY_MS", None),
+            ("FEED_MAX_ITEMS", None),
+            ("FEED_ITEMS_DAYS", None),
+        ])
+    }
+
+    fn set_env_file() -> env_lock::EnvGuard<'static> {
+        env_lock::lock_env([
+            ("CONFIG_FILE", None),
+            ("HOST_URL", None),
+            ("SERVICE_PORT", None),
+            ("IS_PROD", Some("false")),
+            ("SMTP_HOST", None),
+            ("SMTP_USERNAME", None),
+            ("SMTP_PASSWORD", None),
+            ("SMTP_EMAIL_ADMIN", None),
+            ("SECRET_KEY", Some("a secret key")),
+            ("WEBHOOK_TIME", None),
+            ("ADMIN_PASSWORD", None),
+            ("BACKFILL_FROM_YEAR", None),
+            ("DISABLED_SOURCES", None),
+            ("FETCH_TRACKLISTS", None),
+            ("MUSICBRAINZ_LOCATION", None),
+            ("DATABASE_PATH", None),
+            ("CALENDAR_CRON", None),
+            ("COVERS_DIR", None),
+            ("DAILY_DIGEST_CRON", Some("0 30 7 * * *")),
+            ("WEEKLY_DIGEST_CRON", None),
+            ("BANDCAMP_CRON", None),
+            ("FEEDS_CRON", None),
+            ("MAINTENANCE_CRON", None),
+            ("BANDCAMP_DELAY_MS", None),
+            ("METALLUM_DELAY_MS", None),
+            ("FEED_MAX_ITEMS", None),
+            ("FEED_ITEMS_DAYS", None),
+        ])
+    }
+
+    fn set_env_invalid() -> env_lock::EnvGuard<'static> {
+        env_lock::lock_env([
+            ("CONFIG_FILE", None),
+            ("HOST_URL", None),
+            ("SERVICE_PORT", Some("http")),
+            ("IS_PROD", None),
+            ("SMTP_HOST", None),
+            ("SMTP_USERNAME", None),
+            ("SMTP_PASSWORD", None),
+            ("SMTP_EMAIL_ADMIN", None),
+            ("SECRET_KEY", None),
+            ("WEBHOOK_TIME", None),
+            ("ADMIN_PASSWORD", None),
+            ("BACKFILL_FROM_YEAR", None),
+            ("DISABLED_SOURCES", None),
+            ("FETCH_TRACKLISTS", None),
+            ("MUSICBRAINZ_LOCATION", None),
+            ("DATABASE_PATH", None),
+            ("CALENDAR_CRON", None),
+            ("COVERS_DIR", None),
+            ("DAILY_DIGEST_CRON", None),
+            ("WEEKLY_DIGEST_CRON", None),
+            ("BANDCAMP_CRON", None),
+            ("FEEDS_CRON", None),
+            ("MAINTENANCE_CRON", None),
+            ("BANDCAMP_DELAY_MS", None),
+            ("METALLUM_DELAY_MS", None),
+            ("FEED_MAX_ITEMS", None),
+            ("FEED_ITEMS_DAYS", None),
         ])
     }
 }
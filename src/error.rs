@@ -13,13 +13,30 @@ pub enum Error {
         entity: &'static str,
         id: i64,
     },
+    EntityNotFoundByName {
+        entity: &'static str,
+        name: String,
+    },
+    InvalidDate {
+        year: i32,
+        month: u8,
+        day: u8,
+    },
+    InvalidParam(&'static str),
     MissingEnv(&'static str),
     NoItem,
+    Unauthorized,
+
+    Config(Vec<crate::config::ConfigError>),
 
     CalendarUpdateFail,
+    EmailFail,
+    JobAlreadyRunning(&'static str),
+    MigrationFail(String),
     ParseFail,
     RequestFail,
     ScraperFail,
+    WebhookFail,
 
     // Externals
     #[from]
@@ -29,14 +46,24 @@ pub enum Error {
     #[from]
     Job(tokio_cron_scheduler::JobSchedulerError),
     #[from]
+    Image(image::ImageError),
+    #[from]
     Io(std::io::Error),
     #[from]
+    Json(serde_json::Error),
+    #[from]
     Reqwest(reqwest::Error),
 }
 
 impl core::fmt::Display for Error {
     fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
-        write!(fmt, "{self:?}")
+        match self {
+            Error::Config(errors) => {
+                let errors = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(fmt, "{}", errors.join("\n"))
+            }
+            _ => write!(fmt, "{self:?}"),
+        }
     }
 }
 
@@ -1,23 +1,850 @@
 //! The `jobs` module implements functions that are meant to be run periodically.
+//!
+//! Each task the scheduler runs is a `ScheduledJob` with its own cron expression. Its
+//! runs are recorded in the `job_runs` table by `run_job`, which also prevents a job
+//! from starting while its previous run is still in progress.
 
-use crate::{date_now, error::Result, model::CalendarRepository, scraper::client::MainClient};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
-/// Fetches, scrapes and updates the heavy metal calendar for the current
-/// year and saves it in the database.
-pub async fn update_calendar<R>(calendar_repo: R) -> Result<()>
+use time::{Date, Duration, Month};
+use tracing::{error, info, warn};
+
+use crate::{
+    calendar::{self, Calendar, CalendarFile},
+    config::config,
+    date_now,
+    error::{Error, Result},
+    model::{
+        Artist, CalendarBmc, CalendarRepository, FeedBmc, FeedRepository, Frequency, JobRunBmc,
+        JobRunRepository, OverrideEntry, OverrideRepository, OverridesFile, Release,
+        SourceHealthBmc, SourceHealthRepository, SubscriberBmc, SubscriberRepository, WebhookBmc,
+        WebhookRepository, optimize_database,
+    },
+    notifier::{Notifier, notify_webhooks},
+    scraper::{
+        client::MainClient,
+        source::{ReleaseSource, SourceRegistry},
+    },
+    support::{
+        email::send_html_email,
+        token::{self, Purpose},
+    },
+    web::regenerate_todays_feeds,
+    web::templates::subscriptions::digest_email,
+};
+
+/// The number of days the runs of the jobs are kept.
+const JOB_RUNS_RETENTION_DAYS: i64 = 90;
+
+/// The names of the jobs being run, so that a job never overlaps itself.
+static RUNNING_JOBS: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+/// The tasks run periodically by the scheduler.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScheduledJob {
+    /// Updates the calendar from the sources and notifies the webhooks of the new releases.
+    Calendar,
+    /// Searches the Bandcamp pages of the artists.
+    Bandcamp,
+    /// Generates the day's feeds before they are requested.
+    Feeds,
+    DailyDigest,
+    WeeklyDigest,
+    /// Posts the day's releases to the webhooks.
+    Webhooks,
+    /// Deletes the old job runs and compacts the database.
+    Maintenance,
+}
+
+impl ScheduledJob {
+    /// Every job, in the order they are scheduled.
+    pub const ALL: [ScheduledJob; 7] = [
+        ScheduledJob::Calendar,
+        ScheduledJob::Bandcamp,
+        ScheduledJob::Feeds,
+        ScheduledJob::DailyDigest,
+        ScheduledJob::WeeklyDigest,
+        ScheduledJob::Webhooks,
+        ScheduledJob::Maintenance,
+    ];
+
+    /// Returns the name the runs of the job are recorded under.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScheduledJob::Calendar => "calendar",
+            ScheduledJob::Bandcamp => "bandcamp",
+            ScheduledJob::Feeds => "feeds",
+            ScheduledJob::DailyDigest => "daily-digest",
+            ScheduledJob::WeeklyDigest => "weekly-digest",
+            ScheduledJob::Webhooks => "webhooks",
+            ScheduledJob::Maintenance => "maintenance",
+        }
+    }
+
+    /// Whether an admin may run the job on demand. The digests and the webhooks are
+    /// left to their schedule so that nobody is notified twice.
+    pub fn can_run_on_demand(&self) -> bool {
+        matches!(
+            self,
+            ScheduledJob::Calendar
+                | ScheduledJob::Bandcamp
+                | ScheduledJob::Feeds
+                | ScheduledJob::Maintenance
+        )
+    }
+
+    /// Whether a run of the job is in progress.
+    pub fn is_running(&self) -> bool {
+        RUNNING_JOBS
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .contains(self.name())
+    }
+
+    /// Returns the cron expression of the job from the configuration.
+    pub fn cron(&self) -> String {
+        let config = config();
+        match self {
+            ScheduledJob::Calendar => config.CALENDAR_CRON.clone(),
+            ScheduledJob::Bandcamp => config.BANDCAMP_CRON.clone(),
+            ScheduledJob::Feeds => config.FEEDS_CRON.clone(),
+            ScheduledJob::DailyDigest => config.DAILY_DIGEST_CRON.clone(),
+            ScheduledJob::WeeklyDigest => config.WEEKLY_DIGEST_CRON.clone(),
+            ScheduledJob::Webhooks => config.webhook_cron(),
+            ScheduledJob::Maintenance => config.MAINTENANCE_CRON.clone(),
+        }
+    }
+
+    /// Runs the job and returns the number of items it changed.
+    async fn execute(self) -> Result<usize> {
+        match self {
+            ScheduledJob::Calendar => {
+                let new_releases = update_calendar(
+                    CalendarBmc,
+                    SourceHealthBmc,
+                    &SourceRegistry::with_defaults(),
+                )
+                .await?;
+                let num_new = new_releases.len();
+                if let Err(err) = notify_new_releases(FeedBmc, WebhookBmc, new_releases).await {
+                    error!("Error notifying webhooks: {err}")
+                }
+                Ok(num_new)
+            }
+            ScheduledJob::Bandcamp => update_bandcamp(CalendarBmc).await,
+            ScheduledJob::Feeds => regenerate_feeds(Arc::new(CalendarBmc), Arc::new(FeedBmc)),
+            ScheduledJob::DailyDigest => {
+                send_digests(CalendarBmc, FeedBmc, SubscriberBmc, Frequency::Daily).await
+            }
+            ScheduledJob::WeeklyDigest => {
+                send_digests(CalendarBmc, FeedBmc, SubscriberBmc, Frequency::Weekly).await
+            }
+            ScheduledJob::Webhooks => {
+                notify_todays_releases(CalendarBmc, FeedBmc, WebhookBmc).await
+            }
+            ScheduledJob::Maintenance => maintain_database(JobRunBmc),
+        }
+    }
+}
+
+impl FromStr for ScheduledJob {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        ScheduledJob::ALL
+            .into_iter()
+            .find(|job| job.name() == s)
+            .ok_or(Error::InvalidParam("job"))
+    }
+}
+
+/// Marks a job as running until it is dropped.
+struct RunningGuard(&'static str);
+
+impl RunningGuard {
+    /// Marks the job as running, unless it already is.
+    fn acquire(name: &'static str) -> Option<Self> {
+        let mut running = RUNNING_JOBS.lock().unwrap_or_else(|err| err.into_inner());
+        if running.insert(name) {
+            Some(Self(name))
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING_JOBS
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .remove(self.0);
+    }
+}
+
+/// Runs the job and records its run, along with its outcome.
+///
+/// Returns the number of items the run changed.
+///
+/// # Errors
+///
+/// This function returns `Error::JobAlreadyRunning` when the previous run of the job
+/// is still in progress, or the error the job failed with.
+pub async fn run_job<J>(job: ScheduledJob, runs_repo: &J) -> Result<usize>
+where
+    J: JobRunRepository + ?Sized,
+{
+    let Some(_guard) = RunningGuard::acquire(job.name()) else {
+        warn!(
+            "Skipping the {} job because it is already running.",
+            job.name()
+        );
+        return Err(Error::JobAlreadyRunning(job.name()));
+    };
+
+    info!("Running the {} job", job.name());
+    let run_id = runs_repo.start(job.name(), date_now().unix_timestamp())?;
+    let outcome = job.execute().await;
+
+    let at = date_now().unix_timestamp();
+    match &outcome {
+        Ok(num_items) => {
+            info!("The {} job changed {num_items} items", job.name());
+            runs_repo.record_success(run_id, at, *num_items as i32)?;
+        }
+        Err(err) => runs_repo.record_failure(run_id, at, &err.to_string())?,
+    }
+    outcome
+}
+
+/// Fetches, scrapes and updates the heavy metal calendars of the current and
+/// next years and saves them in the database.
+///
+/// The calendars of the enabled sources of the registry are merged in the order
+/// the sources are registered. The failure of a source is recorded in its health
+/// status, and the years it failed to list are left as they are in the database
+/// rather than saved with the releases of the other sources only.
+///
+/// The next year is included so that the releases announced in December for
+/// January are not lost. When `BACKFILL_FROM_YEAR` is set, the past years since
+/// then that are missing from the database are scraped from the sources that
+/// list past years. The Bandcamp pages of the artists are searched by their own job.
+///
+/// Returns the upcoming releases that were not in the calendar before the update.
+/// Nothing is returned when the calendar was empty, e.g. on the first run.
+pub async fn update_calendar<R, H>(
+    calendar_repo: R,
+    health_repo: H,
+    registry: &SourceRegistry,
+) -> Result<Vec<(Release, Artist)>>
+where
+    R: CalendarRepository,
+    H: SourceHealthRepository,
+{
+    let client = MainClient::new(reqwest::Client::new());
+    let today = date_now().date();
+    let year = today.year();
+    let until = today + Duration::days(365);
+
+    let known = calendar_repo
+        .get_between(today, until)?
+        .iter()
+        .map(|(release, _artist)| release.uid())
+        .collect::<HashSet<_>>();
+
+    let sources = registry
+        .enabled()
+        .into_iter()
+        .filter(|source| source.capabilities().upcoming)
+        .collect::<Vec<_>>();
+
+    let calendars = non_empty(fetch_merged(&sources, &health_repo, &[year, year + 1]).await);
+    if !calendars.is_empty() {
+        calendar_repo.create_or_update(calendars).await?;
+    }
+
+    if let Some(from_year) = config().BACKFILL_FROM_YEAR {
+        backfill(&calendar_repo, &health_repo, registry, from_year, year).await?;
+    }
+
+    if registry
+        .enabled()
+        .iter()
+        .any(|source| source.name() == "metallum")
+    {
+        calendar_repo.update_metallum_details(&client).await?;
+
+        if config().FETCH_TRACKLISTS {
+            calendar_repo.update_tracklists(&client).await?;
+        }
+    }
+
+    calendar_repo.update_covers(&client).await?;
+
+    if known.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(calendar_repo
+        .get_between(today, until)?
+        .into_iter()
+        .filter(|(release, _artist)| !known.contains(&release.uid()))
+        .collect())
+}
+
+/// Fetches the calendars of the years from the sources, merges them per year and saves
+/// them in the database, without the follow-up scrapes of `update_calendar`.
+///
+/// The years no source listed any release for are left as they are in the database.
+///
+/// Returns the number of releases saved.
+pub async fn scrape_calendars<R, H>(
+    calendar_repo: R,
+    health_repo: H,
+    sources: &[Arc<dyn ReleaseSource>],
+    years: &[i32],
+) -> Result<usize>
+where
+    R: CalendarRepository,
+    H: SourceHealthRepository,
+{
+    let calendars = non_empty(fetch_merged(sources, &health_repo, years).await);
+
+    let num_releases = calendars.iter().map(Calendar::len).sum();
+    if !calendars.is_empty() {
+        calendar_repo.create_or_update(calendars).await?;
+    }
+
+    Ok(num_releases)
+}
+
+/// Serializes the releases of the year stored in the database to the JSON of a
+/// [`CalendarFile`], which `import_calendars` reads back.
+pub fn export_calendar<R>(calendar_repo: R, year: i32) -> Result<String>
+where
+    R: CalendarRepository,
+{
+    let (Ok(from), Ok(to)) = (
+        Date::from_calendar_date(year, Month::January, 1),
+        Date::from_calendar_date(year, Month::December, 31),
+    ) else {
+        return Err(Error::InvalidDate {
+            year,
+            month: 1,
+            day: 1,
+        });
+    };
+
+    let mut calendar = Calendar::new(year);
+    for (release, artist) in calendar_repo.get_between(from, to)? {
+        let date = release.date();
+        calendar.add_release(
+            date.month(),
+            date.day(),
+            calendar::Release::from_stored(&release, &artist),
+        );
+    }
+
+    Ok(serde_json::to_string_pretty(&calendar.to_file())?)
+}
+
+/// Saves the calendars of JSON [`CalendarFile`]s in the database at once, as if they
+/// had been scraped.
+///
+/// The releases of each year that are missing from its file are marked as removed.
+///
+/// Returns the year of each calendar and its number of releases.
+pub async fn import_calendars<R>(calendar_repo: R, jsons: &[String]) -> Result<Vec<(i32, usize)>>
+where
+    R: CalendarRepository,
+{
+    let calendars = jsons
+        .iter()
+        .map(|json| Calendar::from_file(serde_json::from_str::<CalendarFile>(json)?))
+        .collect::<Result<Vec<_>>>()?;
+    let imported = calendars
+        .iter()
+        .map(|calendar| (calendar.year, calendar.len()))
+        .collect();
+
+    calendar_repo.create_or_update(calendars).await?;
+    Ok(imported)
+}
+
+/// Saves the overrides of a JSON [`OverridesFile`] in the database, replacing those of
+/// the same field for the same artist and album. They apply from the next time a
+/// calendar is saved, e.g. by the next calendar update.
+///
+/// Returns the number of saved overrides.
+///
+/// # Errors
+///
+/// This function returns an error, and saves none of the overrides, if one is invalid.
+pub fn import_overrides<O>(override_repo: O, json: &str) -> Result<usize>
+where
+    O: OverrideRepository,
+{
+    let file = serde_json::from_str::<OverridesFile>(json)?;
+
+    for (index, entry) in file.overrides.iter().enumerate() {
+        if let Err(err) = entry.validate() {
+            error!(
+                "Invalid override #{} of `{}`: {err}",
+                index + 1,
+                entry.artist
+            );
+            return Err(err);
+        }
+    }
+
+    override_repo.import(&file.overrides)
+}
+
+/// Serializes the overrides stored in the database to the JSON of an [`OverridesFile`],
+/// which `import_overrides` reads back.
+pub fn export_overrides<O>(override_repo: O) -> Result<String>
+where
+    O: OverrideRepository,
+{
+    let file = OverridesFile {
+        overrides: override_repo
+            .all()?
+            .into_iter()
+            .map(OverrideEntry::from)
+            .collect(),
+    };
+
+    Ok(serde_json::to_string_pretty(&file)?)
+}
+
+/// Regenerates today's cached feeds so that they list the releases currently stored.
+///
+/// Returns the number of feeds that were regenerated, the main feed included.
+pub fn regenerate_feeds(
+    calendar_repo: Arc<dyn CalendarRepository + Send + Sync>,
+    feed_repo: Arc<dyn FeedRepository + Send + Sync>,
+) -> Result<usize> {
+    let num_feeds = regenerate_todays_feeds(&calendar_repo, &feed_repo)?;
+    info!("Regenerated today's items of {num_feeds} feeds");
+    Ok(num_feeds)
+}
+
+/// Searches the Bandcamp pages of the artists that were never searched, or that were
+/// not found a month ago.
+///
+/// Returns the number of artists whose page was found.
+pub async fn update_bandcamp<R>(calendar_repo: R) -> Result<usize>
+where
+    R: CalendarRepository,
+{
+    let client = MainClient::new(reqwest::Client::new());
+    calendar_repo.update_bandcamp(&client).await
+}
+
+/// Deletes the runs of the jobs older than `JOB_RUNS_RETENTION_DAYS`, then compacts
+/// the database.
+///
+/// Returns the number of deleted runs.
+pub fn maintain_database<J>(runs_repo: J) -> Result<usize>
+where
+    J: JobRunRepository,
+{
+    let before = date_now() - Duration::days(JOB_RUNS_RETENTION_DAYS);
+    let num_deleted = runs_repo.delete_before(before.unix_timestamp())?;
+    optimize_database()?;
+
+    info!("Deleted {num_deleted} job runs and compacted the database");
+    Ok(num_deleted)
+}
+
+/// Leaves out the calendars of the years no source listed any release for, so that
+/// their stored releases are kept.
+fn non_empty(calendars: Vec<Calendar>) -> Vec<Calendar> {
+    calendars
+        .into_iter()
+        .filter(|calendar| {
+            if calendar.is_empty() {
+                warn!(
+                    "No releases found for {}, keeping the stored ones.",
+                    calendar.year
+                );
+            }
+            !calendar.is_empty()
+        })
+        .collect()
+}
+
+/// Fetches the calendars of the years from every source and merges them per year.
+///
+/// The outcome of each source is recorded in its health status. The years a source
+/// failed to fetch are left out, see `merge_fetched`.
+async fn fetch_merged<H>(
+    sources: &[Arc<dyn ReleaseSource>],
+    health_repo: &H,
+    years: &[i32],
+) -> Vec<Calendar>
+where
+    H: SourceHealthRepository,
+{
+    let mut fetched = Vec::new();
+
+    for source in sources {
+        info!("Fetching {:?} from {}", years, source.name());
+        let now = date_now().unix_timestamp();
+
+        match source.fetch(years).await {
+            Ok(calendars) => {
+                let num_releases = calendars.iter().map(Calendar::len).sum::<usize>();
+                if let Err(err) =
+                    health_repo.record_success(source.name(), now, num_releases as i32)
+                {
+                    error!("Error recording the health of {}: {err}", source.name());
+                }
+                fetched.push((source.name(), Some(calendars)));
+            }
+            Err(err) => {
+                error!("Error fetching the releases from {}: {err}", source.name());
+                if let Err(err) = health_repo.record_failure(source.name(), now, &err.to_string()) {
+                    error!("Error recording the health of {}: {err}", source.name());
+                }
+                fetched.push((source.name(), None));
+            }
+        }
+    }
+
+    merge_fetched(years, fetched)
+}
+
+/// Merges the calendars fetched from each source, named along with them, per year and
+/// returns them in the order of the years.
+///
+/// The calendars are matched to the years by their `year` field. A year is left out when
+/// a source failed, i.e. has no calendars, or did not list it, so that the releases only
+/// that source lists are not marked as removed until it lists them again.
+fn merge_fetched(
+    years: &[i32],
+    fetched: Vec<(&'static str, Option<Vec<Calendar>>)>,
+) -> Vec<Calendar> {
+    let mut merged: HashMap<i32, Calendar> = HashMap::new();
+    let mut incomplete = HashSet::new();
+
+    for (name, calendars) in fetched {
+        let Some(calendars) = calendars else {
+            incomplete.extend(years.iter().copied());
+            continue;
+        };
+
+        let mut by_year = calendars
+            .into_iter()
+            .map(|calendar| (calendar.year, calendar))
+            .collect::<HashMap<_, _>>();
+
+        for year in years {
+            let Some(calendar) = by_year.remove(year) else {
+                warn!("{name} did not list the releases of {year}");
+                incomplete.insert(*year);
+                continue;
+            };
+
+            let calendar = match merged.remove(year) {
+                Some(previous) => previous.merge(&calendar),
+                None => calendar,
+            };
+            merged.insert(*year, calendar);
+        }
+    }
+
+    years
+        .iter()
+        .filter_map(|year| {
+            if incomplete.contains(year) {
+                warn!("Skipping {year} because a source failed, keeping the stored releases.");
+                return None;
+            }
+            merged.remove(year)
+        })
+        .collect()
+}
+
+/// Scrapes the past years that have no releases in the database yet from the
+/// enabled sources that list past years.
+async fn backfill<R, H>(
+    calendar_repo: &R,
+    health_repo: &H,
+    registry: &SourceRegistry,
+    from_year: i32,
+    to_year: i32,
+) -> Result<()>
 where
     R: CalendarRepository,
+    H: SourceHealthRepository,
 {
-    let http_client = reqwest::Client::new();
-    let client = MainClient::new(http_client);
-    let year = date_now().year();
+    let sources = registry
+        .enabled()
+        .into_iter()
+        .filter(|source| source.capabilities().past_years)
+        .collect::<Vec<_>>();
+    if sources.is_empty() {
+        warn!("Skipping the backfill because no enabled source lists past years.");
+        return Ok(());
+    }
 
-    let calendar1 = crate::scraper::metallum::scrape(&client, year).await?;
-    let calendar2 = crate::scraper::wiki::scrape(&client, year).await?;
-    let calendar = calendar1.merge(&calendar2);
+    let stored = calendar_repo.years()?;
 
-    calendar_repo.create_or_update(calendar).await?;
-    calendar_repo.update_bandcamp(&client).await?;
+    for year in (from_year..to_year).filter(|year| !stored.contains(year)) {
+        info!("Backfilling the calendar of {year}");
+        match fetch_merged(&sources, health_repo, &[year]).await.pop() {
+            Some(calendar) if !calendar.is_empty() => {
+                calendar_repo.create_or_update(vec![calendar]).await?
+            }
+            _ => warn!("No releases found for {year}."),
+        }
+    }
 
     Ok(())
 }
+
+/// Posts today's releases to the registered webhooks.
+///
+/// Returns the number of today's releases.
+pub async fn notify_todays_releases<C, F, W>(
+    calendar_repo: C,
+    feed_repo: F,
+    webhook_repo: W,
+) -> Result<usize>
+where
+    C: CalendarRepository,
+    F: FeedRepository,
+    W: WebhookRepository,
+{
+    let today = date_now().date();
+    let releases = calendar_repo.get_between(today, today)?;
+
+    notify_webhooks(
+        &feed_repo,
+        &webhook_repo,
+        &Notifier::new(),
+        &format!("Heavy metal releases of {today}"),
+        &releases,
+    )
+    .await?;
+    Ok(releases.len())
+}
+
+/// Posts the releases announced since the last calendar update to the registered webhooks.
+pub async fn notify_new_releases<F, W>(
+    feed_repo: F,
+    webhook_repo: W,
+    releases: Vec<(Release, Artist)>,
+) -> Result<()>
+where
+    F: FeedRepository,
+    W: WebhookRepository,
+{
+    info!("Notifying webhooks of {} new releases", releases.len());
+
+    notify_webhooks(
+        &feed_repo,
+        &webhook_repo,
+        &Notifier::new(),
+        "Newly announced heavy metal releases",
+        &releases,
+    )
+    .await
+}
+
+/// Emails the digest of the releases to the confirmed subscribers of the given frequency.
+///
+/// The daily digest lists today's releases and the weekly digest lists the releases of
+/// the next seven days, starting today. Subscribers to a custom feed only receive its
+/// matching releases. No email is sent to a subscriber without any release to list.
+///
+/// Returns the number of digests sent.
+pub async fn send_digests<C, F, S>(
+    calendar_repo: C,
+    feed_repo: F,
+    subscriber_repo: S,
+    frequency: Frequency,
+) -> Result<usize>
+where
+    C: CalendarRepository,
+    F: FeedRepository,
+    S: SubscriberRepository,
+{
+    let (Some(smtp), Some(key)) = (&config().smtp, &config().SECRET_KEY) else {
+        warn!(
+            "Skipping the {} digests because email subscriptions are disabled.",
+            frequency.as_str()
+        );
+        return Ok(0);
+    };
+
+    let from = date_now().date();
+    let to = from + Duration::days(frequency.num_days() - 1);
+    let releases = calendar_repo.get_between(from, to)?;
+
+    let subscribers = subscriber_repo.confirmed(frequency)?;
+    info!(
+        "Sending the {} digest to {} subscribers",
+        frequency.as_str(),
+        subscribers.len()
+    );
+
+    let mut num_sent = 0;
+    for subscriber in subscribers {
+        let matching = if subscriber.custom_feed_id > -1 {
+            match feed_repo.get_custom_feed(subscriber.custom_feed_id) {
+                Ok(custom_feed) => releases
+                    .iter()
+                    .filter(|(release, artist)| custom_feed.matches(release, artist))
+                    .collect::<Vec<_>>(),
+                Err(err) => {
+                    error!(
+                        "Error fetching custom feed of subscriber {}: {err}",
+                        subscriber.id
+                    );
+                    continue;
+                }
+            }
+        } else {
+            releases.iter().collect::<Vec<_>>()
+        };
+
+        if matching.is_empty() {
+            continue;
+        }
+
+        let unsubscribe_url = format!(
+            "{}/subscriptions/{}/unsubscribe?token={}",
+            config().HOST_URL,
+            subscriber.id,
+            token::sign(key, Purpose::Unsubscribe, subscriber.id, &subscriber.email)
+        );
+        let html = digest_email(from, to, &matching, &unsubscribe_url);
+        let subject = match frequency {
+            Frequency::Daily => format!("Heavy metal releases of {from}"),
+            Frequency::Weekly => format!("Heavy metal releases of the week of {from}"),
+        };
+
+        let email = subscriber.email.clone();
+        let sent = tokio::task::spawn_blocking(move || {
+            send_html_email(smtp, &email, &subject, html, Some(&unsubscribe_url))
+        })
+        .await;
+
+        match sent {
+            Ok(Ok(())) => {
+                subscriber_repo.mark_sent(subscriber.id, date_now().unix_timestamp())?;
+                num_sent += 1;
+            }
+            Ok(Err(err)) => error!(
+                "Error sending digest to subscriber {}: {err}",
+                subscriber.id
+            ),
+            Err(err) => error!(
+                "Error joining digest task of subscriber {}: {err}",
+                subscriber.id
+            ),
+        }
+    }
+
+    Ok(num_sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_running_guard_prevents_overlap_ok() {
+        let guard = RunningGuard::acquire("test-job");
+
+        assert!(guard.is_some());
+        assert!(RunningGuard::acquire("test-job").is_none());
+        assert!(RunningGuard::acquire("other-test-job").is_some());
+
+        drop(guard);
+        assert!(RunningGuard::acquire("test-job").is_some());
+    }
+
+    #[test]
+    fn test_scheduled_job_from_str_ok() -> Result<()> {
+        pretty_assertions::assert_eq!(
+            "daily-digest".parse::<ScheduledJob>()?,
+            ScheduledJob::DailyDigest
+        );
+        assert!("Calendar".parse::<ScheduledJob>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_scheduled_job_names_are_unique_ok() {
+        let names = ScheduledJob::ALL
+            .iter()
+            .map(ScheduledJob::name)
+            .collect::<HashSet<_>>();
+
+        pretty_assertions::assert_eq!(names.len(), ScheduledJob::ALL.len());
+    }
+
+    fn a_calendar(year: i32, artist: &str, album: &str) -> Calendar {
+        let mut calendar = Calendar::new(year);
+        calendar.add_release(Month::March, 1, calendar::Release::new(artist, album));
+        calendar
+    }
+
+    #[test]
+    fn test_merge_fetched_ok() {
+        let fetched = vec![
+            (
+                "metallum",
+                Some(vec![
+                    a_calendar(2026, "Wintersun", "Time III"),
+                    a_calendar(2025, "Faidra", "Dies Irae"),
+                ]),
+            ),
+            (
+                "wikipedia",
+                Some(vec![
+                    a_calendar(2025, "Hazzerd", "Next"),
+                    a_calendar(2026, "Wardruna", "Birna"),
+                ]),
+            ),
+        ];
+
+        let got = merge_fetched(&[2025, 2026], fetched)
+            .iter()
+            .map(|calendar| (calendar.year, calendar.len()))
+            .collect::<Vec<_>>();
+
+        pretty_assertions::assert_eq!(got, vec![(2025, 2), (2026, 2)]);
+    }
+
+    #[test]
+    fn test_merge_fetched_skips_incomplete_years_ok() {
+        let failed = vec![
+            (
+                "metallum",
+                Some(vec![a_calendar(2025, "Faidra", "Dies Irae")]),
+            ),
+            ("wikipedia", None),
+        ];
+        let short = vec![
+            (
+                "metallum",
+                Some(vec![a_calendar(2025, "Faidra", "Dies Irae")]),
+            ),
+            (
+                "wikipedia",
+                Some(vec![a_calendar(2026, "Wardruna", "Birna")]),
+            ),
+        ];
+
+        assert!(merge_fetched(&[2025], failed).is_empty());
+        assert!(merge_fetched(&[2025, 2026], short).is_empty());
+    }
+}
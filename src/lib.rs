@@ -2,27 +2,37 @@
 //!
 //! The application works by reading the heavy metal album releases throughout the year from
 //! [Wikipedia's heavy metal releases](https://en.wikipedia.org/wiki/2024_in_heavy_metal_music)
-//! page and [Metallum](https://www.metal-archives.com/release/upcoming). It is updated at 12:00 AM,
-//! on day 1 and 15 of the month.
+//! page and [Metallum](https://www.metal-archives.com/release/upcoming). It is updated every
+//! Sunday at 12:00 AM by default, along with the other scheduled jobs of the `jobs` module.
 
 mod calendar;
 mod error;
-mod scraper;
 mod support;
 
 pub mod config;
 pub mod jobs;
 pub mod model;
+pub mod notifier;
+pub mod scraper;
 pub mod web;
 
 pub use error::{Error, Result};
-use time::OffsetDateTime;
+use time::{Date, Month, OffsetDateTime};
 
 /// Returns the current date and time.
 pub fn date_now() -> OffsetDateTime {
     OffsetDateTime::now_local().unwrap_or(OffsetDateTime::now_utc())
 }
 
+/// Parses a date written as `YYYY-MM-DD`, e.g. by a date input.
+pub fn parse_date(value: &str) -> Option<Date> {
+    let mut parts = value.trim().splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = Month::try_from(parts.next()?.parse::<u8>().ok()?).ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Date::from_calendar_date(year, month, day).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,4 +50,28 @@ mod tests {
         assert_eq!(got.year(), now.year());
         Ok(())
     }
+
+    #[test]
+    fn test_parse_date_ok() {
+        pretty_assertions::assert_eq!(
+            parse_date("2026-10-17"),
+            Date::from_calendar_date(2026, Month::October, 17).ok()
+        );
+        pretty_assertions::assert_eq!(parse_date(" 2024-02-29 ").map(|date| date.day()), Some(29));
+    }
+
+    #[test]
+    fn test_parse_date_invalid_ok() {
+        for value in [
+            "",
+            "2026-10",
+            "2026-13-01",
+            "2025-02-29",
+            "17/10/2026",
+            "yesterday",
+            "2026-10-17-1",
+        ] {
+            pretty_assertions::assert_eq!(parse_date(value), None, "{value}");
+        }
+    }
 }
@@ -1,53 +1,271 @@
-mod error;
+mod cli;
 
-use std::{fs, io, sync::Arc};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
+use clap::Parser;
 use dotenvy::dotenv;
 use tokio::{net::TcpListener, signal};
 use tokio_cron_scheduler::{Job, JobScheduler};
 use tracing::{error, info, warn};
 
-use heavy_metal_notifier::model::{CalendarBmc, EntitiesBmc, FeedBmc};
+use cli::{Cli, Command, ConfigCommand, FeedsCommand, OverridesCommand};
+use heavy_metal_notifier::jobs::ScheduledJob;
+use heavy_metal_notifier::model::{
+    CalendarBmc, EntitiesBmc, FeedBmc, JobRunBmc, JobRunRepository, OverrideBmc, OverrideEntry,
+    OverrideRepository, SourceHealthBmc, SubscriberBmc, WebhookBmc, run_migrations,
+};
+use heavy_metal_notifier::scraper::source::SourceRegistry;
 use heavy_metal_notifier::web::AppState;
-use heavy_metal_notifier::{Result, config::config, jobs, web::routes};
+use heavy_metal_notifier::{
+    Result,
+    config::{self, Config, config},
+    date_now, jobs,
+    web::routes,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenv().ok();
-    tracing_subscriber::fmt().with_target(false).init();
+    tracing_subscriber::fmt()
+        .with_target(false)
+        .with_writer(io::stderr)
+        .init();
 
-    let data_folder = "data";
-    match fs::create_dir(data_folder) {
-        Ok(_) => info!("Data folder created successfully!"),
-        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
-            warn!("Data folder already exists.");
+    let cli = Cli::parse();
+    let config_file = cli.config.clone();
+    let command = cli.command();
+
+    if let Command::Config {
+        command: ConfigCommand::Check,
+    } = command
+    {
+        return check_config(config_file.as_deref());
+    }
+
+    if let Err(err) = config::init(config_file.as_deref()) {
+        eprintln!("Invalid configuration:\n{err}");
+        std::process::exit(1);
+    }
+
+    if let Some(folder) = Path::new(&config().DATABASE_PATH).parent() {
+        fs::create_dir_all(folder)?;
+    }
+
+    match command {
+        Command::Serve { no_initial_scrape } => serve(no_initial_scrape).await,
+        Command::Scrape { years, sources } => scrape(years, sources).await,
+        Command::Export { year, output } => export(year, output),
+        Command::Import { files } => import(files).await,
+        Command::Migrate => migrate(),
+        Command::Overrides { command } => overrides(command),
+        Command::Feeds {
+            command: FeedsCommand::Regenerate,
+        } => jobs::regenerate_feeds(Arc::new(CalendarBmc), Arc::new(FeedBmc)).map(|_| ()),
+        Command::Config { .. } => Ok(()),
+    }
+}
+
+/// Reports whether the configuration is valid, or lists its problems and exits.
+fn check_config(file: Option<&Path>) -> Result<()> {
+    let (path, _) = Config::file_path(file);
+
+    match Config::load(file) {
+        Ok(_) if path.exists() => {
+            println!("The configuration of {} is valid.", path.display());
+            Ok(())
+        }
+        Ok(_) => {
+            println!("The configuration is valid. No file was read.");
+            Ok(())
         }
-        Err(e) => {
-            eprintln!("Error creating data folder: {}", e);
+        Err(err) => {
+            eprintln!("Invalid configuration:\n{err}");
             std::process::exit(1);
         }
     }
+}
+
+/// Scrapes the calendars of the years from the sources and saves them.
+///
+/// Without sources, the enabled sources able to list every year are used.
+async fn scrape(years: Vec<i32>, sources: Vec<String>) -> Result<()> {
+    let year = date_now().year();
+    let years = if years.is_empty() {
+        vec![year, year + 1]
+    } else {
+        years
+    };
+
+    let registry = SourceRegistry::with_defaults();
+    let sources = if sources.is_empty() {
+        registry
+            .enabled()
+            .into_iter()
+            .filter(|source| {
+                let capabilities = source.capabilities();
+                years.iter().all(|&y| {
+                    if y < year {
+                        capabilities.past_years
+                    } else {
+                        capabilities.upcoming
+                    }
+                })
+            })
+            .collect()
+    } else {
+        registry.named(&sources)?
+    };
+
+    if sources.is_empty() {
+        warn!("No enabled source lists the releases of {years:?}.");
+        return Ok(());
+    }
 
-    config();
+    let num_releases =
+        jobs::scrape_calendars(CalendarBmc, SourceHealthBmc, &sources, &years).await?;
+    info!("Saved {num_releases} releases of {years:?}");
+    Ok(())
+}
+
+/// Writes the calendar of the year to the file, or to the standard output.
+fn export(year: Option<i32>, output: Option<PathBuf>) -> Result<()> {
+    let year = year.unwrap_or(date_now().year());
+    let json = jobs::export_calendar(CalendarBmc, year)?;
+
+    match output {
+        Some(path) => {
+            fs::write(&path, json)?;
+            info!("Exported the calendar of {year} to {}", path.display());
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
 
-    info!("Fetching and storing calendar");
-    jobs::update_calendar(CalendarBmc).await?;
+/// Saves the calendars of the files in the database at once.
+async fn import(files: Vec<PathBuf>) -> Result<()> {
+    let jsons = files
+        .iter()
+        .map(fs::read_to_string)
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let imported = jobs::import_calendars(CalendarBmc, &jsons).await?;
+
+    for (path, (year, num_releases)) in files.iter().zip(imported) {
+        info!(
+            "Imported {num_releases} releases of {year} from {}",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Lists, saves, removes, imports or exports the manual overrides.
+///
+/// The overrides apply from the next time a calendar is saved, e.g. by the `scrape` command.
+fn overrides(command: OverridesCommand) -> Result<()> {
+    match command {
+        OverridesCommand::List => {
+            for o in OverrideBmc.all()? {
+                let album = if o.album.is_empty() { "*" } else { &o.album };
+                let value = o
+                    .value
+                    .map(|value| format!(" = {value}"))
+                    .unwrap_or_default();
+                let note = o.note.map(|note| format!(" ({note})")).unwrap_or_default();
+                println!(
+                    "{:>4}  {} - {album}: {}{value}{note}",
+                    o.id, o.artist, o.field
+                );
+            }
+        }
+        OverridesCommand::Set {
+            artist,
+            album,
+            note,
+            field,
+            value,
+        } => {
+            let saved = OverrideBmc.set(&OverrideEntry {
+                artist,
+                album: album.unwrap_or_default(),
+                field,
+                value,
+                note,
+            })?;
+            info!("Saved the override {} of {}", saved.id, saved.artist);
+        }
+        OverridesCommand::Remove { id } => {
+            OverrideBmc.delete(id)?;
+            info!("Removed the override {id}");
+        }
+        OverridesCommand::Import { files } => {
+            for path in files {
+                let json = fs::read_to_string(&path)?;
+                let num_overrides = jobs::import_overrides(OverrideBmc, &json)?;
+                info!("Imported {num_overrides} overrides from {}", path.display());
+            }
+        }
+        OverridesCommand::Export { output } => {
+            let json = jobs::export_overrides(OverrideBmc)?;
+            match output {
+                Some(path) => {
+                    fs::write(&path, json)?;
+                    info!("Exported the overrides to {}", path.display());
+                }
+                None => println!("{json}"),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Applies the pending database migrations.
+fn migrate() -> Result<()> {
+    let versions = run_migrations()?;
+    if versions.is_empty() {
+        info!("The database is up to date");
+    } else {
+        info!("Applied the migrations {}", versions.join(", "));
+    }
+    Ok(())
+}
+
+/// Serves the website and schedules the periodic jobs.
+async fn serve(no_initial_scrape: bool) -> Result<()> {
+    let num_interrupted = JobRunBmc.interrupt_running(date_now().unix_timestamp())?;
+    if num_interrupted > 0 {
+        warn!("Marked {num_interrupted} job runs left running as interrupted");
+    }
+
+    if no_initial_scrape {
+        info!("Skipping the initial calendar update");
+    } else {
+        info!("Fetching and storing calendar");
+        if let Err(err) = jobs::run_job(ScheduledJob::Calendar, &JobRunBmc).await {
+            error!(
+                "Error running the {} job: {err}",
+                ScheduledJob::Calendar.name()
+            )
+        }
+    }
 
     info!("Scheduling jobs");
     let sched = JobScheduler::new().await?;
-    sched
-        .add(Job::new_async("0 0 0 * * 0", |_uuid, _l| {
-            Box::pin({
-                async move {
-                    info!("Updating calendar");
-                    if let Err(err) = jobs::update_calendar(CalendarBmc).await {
-                        error!("Error updating calendar: {err}")
-                    };
-                    info!("Calendar updated")
-                }
-            })
-        })?)
-        .await?;
+    for job in ScheduledJob::ALL {
+        sched
+            .add(Job::new_async(job.cron().as_str(), move |_uuid, _l| {
+                Box::pin(async move {
+                    if let Err(err) = jobs::run_job(job, &JobRunBmc).await {
+                        error!("Error running the {} job: {err}", job.name())
+                    }
+                })
+            })?)
+            .await?;
+    }
     sched.shutdown_on_ctrl_c();
     sched.start().await?;
 
@@ -63,6 +281,10 @@ async fn main() -> Result<()> {
         Arc::new(CalendarBmc),
         Arc::new(EntitiesBmc),
         Arc::new(FeedBmc),
+        Arc::new(JobRunBmc),
+        Arc::new(SourceHealthBmc),
+        Arc::new(SubscriberBmc),
+        Arc::new(WebhookBmc),
     ));
 
     axum::serve(listener, router)
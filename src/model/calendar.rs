@@ -1,18 +1,33 @@
 use diesel::prelude::*;
+use maud::html;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, hash_map::Entry};
+use time::Date;
 use tracing::{error, info, warn};
 
-use super::ModelManager;
+use super::{
+    ModelManager,
+    history::{ReleaseChange, ReleaseChangeForInsert, human_date, int_to_date},
+    overrides::Override,
+    tracklist::{ReleaseDetailsForInsert, TrackForInsert},
+};
 use crate::{
     calendar::Calendar,
     config::config,
     date_now,
     error::{Error, Result},
-    scraper::client::Client,
+    scraper::{
+        bandcamp,
+        client::{BandcampClient, MetallumClient},
+        cover::{self, CoverClient, CoverQuery},
+        metallum,
+    },
+    support::{fuzzy, ical},
 };
 
 /// This struct corresponds to a row in the `artists` table in the database.
 /// Each artist has a unique `id` and a `name`.
-#[derive(Queryable, Identifiable, Selectable, Debug, PartialEq, AsChangeset)]
+#[derive(Queryable, Identifiable, Selectable, Debug, PartialEq, AsChangeset, Serialize)]
 #[diesel(table_name = super::schema::artists)]
 #[diesel(check_for_backend(diesel::sqlite::Sqlite))]
 pub struct Artist {
@@ -21,6 +36,21 @@ pub struct Artist {
     pub genre: Option<String>,
     pub url_bandcamp: Option<String>,
     pub url_metallum: Option<String>,
+    /// The MusicBrainz identifier of the artist, if known.
+    pub musicbrainz_id: Option<String>,
+    /// How likely the Bandcamp page belongs to the artist, from 0 to 1.
+    pub bandcamp_confidence: Option<f32>,
+    /// The Unix timestamp of the last search for the Bandcamp page.
+    #[serde(skip)]
+    pub bandcamp_checked_at: Option<i64>,
+    /// Whether the Bandcamp link was set by an admin, so it is never searched again.
+    #[serde(skip)]
+    pub bandcamp_locked: bool,
+    /// The country of origin of the artist, e.g. `Finland`.
+    pub country: Option<String>,
+    /// The Unix timestamp of the last scrape of the artist's Metallum page.
+    #[serde(skip)]
+    pub metallum_checked_at: Option<i64>,
 }
 
 /// Represents a new artist to be inserted into the database.
@@ -34,6 +64,7 @@ struct ArtistForInsert {
     pub genre: Option<String>,
     pub url_bandcamp: Option<String>,
     pub url_metallum: Option<String>,
+    pub musicbrainz_id: Option<String>,
 }
 
 impl ArtistForInsert {
@@ -41,12 +72,14 @@ impl ArtistForInsert {
         name: impl Into<String>,
         genre: Option<String>,
         url_metallum: Option<String>,
+        musicbrainz_id: Option<String>,
     ) -> Self {
         Self {
             name: name.into(),
             genre,
             url_bandcamp: None,
             url_metallum,
+            musicbrainz_id,
         }
     }
 }
@@ -57,7 +90,7 @@ impl ArtistForInsert {
 /// It stores information about an artist's album release,
 /// including the release date (year, month, day) and the album's
 /// title.
-#[derive(Queryable, Identifiable, Selectable, Associations, Debug, PartialEq)]
+#[derive(Queryable, Identifiable, Selectable, Associations, Debug, PartialEq, Serialize)]
 #[diesel(belongs_to(Artist))]
 #[diesel(table_name = super::schema::releases)]
 #[diesel(check_for_backend(diesel::sqlite::Sqlite))]
@@ -71,64 +104,233 @@ pub struct Release {
     pub release_type: Option<String>,
     pub url_youtube: String,
     pub url_metallum: Option<String>,
+    /// The Unix timestamp of the calendar update that first listed the release.
+    pub first_seen: i64,
+    /// The Unix timestamp of the last calendar update that listed the release.
+    pub last_seen: i64,
+    /// Whether the release vanished from the calendar since it was last seen.
+    #[serde(skip)]
+    pub is_removed: bool,
+    /// The previous date of the release, as `YYYYMMDD`, if it was ever moved.
+    pub moved_from: Option<i32>,
+    /// The MusicBrainz identifier of the release group, if known.
+    pub musicbrainz_id: Option<String>,
+    /// The record label releasing the album, e.g. `Nuclear Blast`.
+    pub label: Option<String>,
+    /// The Unix timestamp of the last scrape of the album's Metallum page.
+    #[serde(skip)]
+    pub metallum_checked_at: Option<i64>,
+    /// The file name of the cover's thumbnail in the covers folder, if one was found.
+    pub cover: Option<String>,
+    /// The Unix timestamp of the last search for the cover.
+    #[serde(skip)]
+    pub cover_checked_at: Option<i64>,
+    /// The size of the cover's thumbnail, in bytes.
+    #[serde(skip)]
+    pub cover_size: Option<i32>,
+    /// Whether an admin hid the release, so it stays removed whatever the sources list.
+    #[serde(skip)]
+    pub is_hidden: bool,
+    /// The ID of the release an admin merged this duplicate into, if any.
+    #[serde(skip)]
+    pub merged_into: Option<i32>,
+    /// Whether an admin added the release, so it is kept when no source lists it.
+    #[serde(skip)]
+    pub is_manual: bool,
 }
 
 impl Release {
     /// Converts the release and associated artist information into an HTML string.
     ///
-    /// This function generates a `<li>` element containing the release's title and the artist's name,
+    /// This function generates a `<li>` element containing the release's title and the artist's name
+    /// linking to the release's page,
+    /// a "Postponed" or "Moved" badge if its date changed, the thumbnail of its cover if one was found,
     /// followed by a nested `<ul>` list. The list includes optional details such as:
-    /// - The artist's genre, if available.
-    /// - The type of release (e.g., album, single), if specified.
+    /// - The artist's genre and country of origin, if available.
+    /// - The type of release (e.g., album, single) and its record label, if specified.
     /// - Links to YouTube, Bandcamp, and Metallum pages related to the artist or release.
+    ///
+    /// The scraped values are escaped.
     pub fn to_html(&self, artist: &Artist) -> String {
-        let mut html = format!(
-            "<li style=\"margin-bottom: 1rem\"><b>{} - {}</b>",
-            artist.name, self.album
-        );
+        let link_class = "link link-primary visited:link-secondary focus:link-accent";
+
+        html!(
+            li style="margin-bottom: 1rem" {
+                b {
+                    a href=(self.permalink(artist, &config().HOST_URL)) class="link link-hover" {
+                        (artist.name) " - " (self.album)
+                    }
+                }
+                @if let Some(previous) = self.moved_from_date() {
+                    " "
+                    span class="badge badge-warning" title={ "Previously on " (human_date(previous)) } {
+                        @if previous < self.date() { "Postponed" } @else { "Moved" }
+                    }
+                }
+                @if let Some(url) = self.cover_url(&config().HOST_URL) {
+                    br;
+                    img src=(url) alt={ "Cover of " (self.album) } width="150" loading="lazy" style="margin-top: 0.5rem";
+                }
+                ul {
+                    @if let Some(genre) = &artist.genre {
+                        li { (genre) }
+                    }
+                    @if let Some(country) = &artist.country {
+                        li { (country) }
+                    }
+                    @if let Some(release_type) = &self.release_type {
+                        li { (release_type) }
+                    }
+                    @if let Some(label) = &self.label {
+                        li { (label) }
+                    }
+                    li { a href=(self.url_youtube) target="_blank" class=(link_class) { "Youtube" } }
+                    @if let Some(url) = &artist.url_bandcamp {
+                        li { a href=(url) target="_blank" class=(link_class) { "Bandcamp" } }
+                    }
+                    @if let Some(url) = &artist.url_metallum {
+                        li { a href=(url) target="_blank" class=(link_class) { "Metallum (band)" } }
+                    }
+                    @if let Some(url) = &self.url_metallum {
+                        li { a href=(url) target="_blank" class=(link_class) { "Metallum (album)" } }
+                    }
+                }
+            }
+        )
+        .into_string()
+    }
+
+    /// Returns the URL of the thumbnail of the cover served by the host, if one was found,
+    /// e.g. `https://www.metal-releases.com/covers/3f2a…9c.jpg`.
+    pub fn cover_url(&self, host: &str) -> Option<String> {
+        self.cover
+            .as_ref()
+            .map(|cover| format!("{host}/covers/{cover}"))
+    }
+
+    /// Returns the part of the release's page URL naming it, e.g. `wintersun-time-ii`.
+    pub fn slug(&self, artist: &Artist) -> String {
+        fuzzy::normalize(&format!("{} {}", artist.name, self.album)).replace(' ', "-")
+    }
 
-        html.push_str("<ul>");
+    /// Returns the URL of the release's page on the host, made of its ID and its slug,
+    /// e.g. `https://www.metal-releases.com/releases/42-wintersun-time-ii`.
+    pub fn permalink(&self, artist: &Artist, host: &str) -> String {
+        format!(
+            "{host}/releases/{}-{}",
+            self.id,
+            url_escape::encode_path(&self.slug(artist))
+        )
+    }
+
+    /// Returns an identifier derived from the release's row ID, e.g. `release-1`.
+    ///
+    /// The row is kept whenever the calendar is updated, even when the release is
+    /// moved to another year, so the identifier stays the same in the feeds and calendars.
+    pub fn uid(&self) -> String {
+        format!("release-{}", self.id)
+    }
+
+    /// Returns the date the release was expected on before it was last moved.
+    pub fn moved_from_date(&self) -> Option<Date> {
+        self.moved_from.and_then(int_to_date)
+    }
+
+    /// Returns the release date.
+    pub fn date(&self) -> Date {
+        Date::from_calendar_date(
+            self.year,
+            time::Month::try_from(self.month as u8).unwrap_or(time::Month::January),
+            self.day as u8,
+        )
+        .unwrap_or(Date::MIN)
+    }
+
+    /// Converts the release and associated artist information into an iCalendar `VEVENT`.
+    ///
+    /// The event spans the whole release day. Its UID is derived from `Release::uid`. The
+    /// description lists the genre, the country, the type of release, the label and the external links.
+    ///
+    /// The `dtstamp` must be formatted as a UTC date-time, e.g. `20240831T000000Z`.
+    pub fn to_vevent(&self, artist: &Artist, dtstamp: &str, host: &str) -> String {
+        let start = self.date();
+        let end = start.next_day().unwrap_or(start);
+
+        let mut description = Vec::new();
         if let Some(genre) = &artist.genre {
-            html.push_str(&format!("<li>{genre}</li>"));
+            description.push(format!("Genre: {genre}"));
+        }
+        if let Some(country) = &artist.country {
+            description.push(format!("Country: {country}"));
         }
-
         if let Some(release_type) = &self.release_type {
-            html.push_str(&format!("<li>{release_type}</li>"));
+            description.push(format!("Type: {release_type}"));
         }
-
-        html.push_str(&format!(
-            "<li><a href=\"{}\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Youtube</a></li>",
-            self.url_youtube
-        ));
-
+        if let Some(label) = &self.label {
+            description.push(format!("Label: {label}"));
+        }
+        description.push(format!("YouTube: {}", self.url_youtube));
         if let Some(url) = &artist.url_bandcamp {
-            html.push_str(&format!(
-                "<li><a href=\"{url}\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Bandcamp</a></li>"
-            ));
+            description.push(format!("Bandcamp: {url}"));
         }
-
         if let Some(url) = &artist.url_metallum {
-            html.push_str(&format!(
-                "<li><a href=\"{url}\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Metallum (band)</a></li>"
-            ));
+            description.push(format!("Metallum (band): {url}"));
         }
-
         if let Some(url) = &self.url_metallum {
-            html.push_str(&format!(
-                "<li><a href=\"{url}\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Metallum (album)</a></li>"
-            ));
+            description.push(format!("Metallum (album): {url}"));
+        }
+
+        let mut lines = vec![
+            String::from("BEGIN:VEVENT"),
+            format!("UID:{}@{host}", self.uid()),
+            format!("DTSTAMP:{dtstamp}"),
+            format!("DTSTART;VALUE=DATE:{}", ical_date(start)),
+            format!("DTEND;VALUE=DATE:{}", ical_date(end)),
+            format!(
+                "SUMMARY:{}",
+                ical::escape(&format!("{} - {}", artist.name, self.album))
+            ),
+            format!("DESCRIPTION:{}", ical::escape(&description.join("\n"))),
+        ];
+        if let Some(genre) = &artist.genre {
+            lines.push(format!("CATEGORIES:{}", ical::escape(genre)));
         }
+        lines.push(format!(
+            "URL:{}",
+            self.url_metallum.as_ref().unwrap_or(&self.url_youtube)
+        ));
+        lines.push(String::from("TRANSP:TRANSPARENT"));
+        lines.push(String::from("END:VEVENT"));
 
-        html.push_str("</ul></li>");
-        html
+        lines.iter().map(|line| ical::fold(line)).collect()
     }
 }
 
+/// Normalizes an album title so that the same album is recognized across calendar
+/// updates despite differences of case or punctuation, e.g. `time-ii` for "Time II".
+fn normalize_album(album: &str) -> String {
+    album
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn ical_date(date: Date) -> String {
+    format!(
+        "{:04}{:02}{:02}",
+        date.year(),
+        date.month() as u8,
+        date.day()
+    )
+}
+
 /// Represents a new release to be inserted into the database.
 ///
 /// This struct is used when creating new records in the `releases` table.
 /// It doesn't include the `id` field because the database will generate it.
-#[derive(Insertable, Associations)]
+#[derive(Insertable, Associations, Debug, PartialEq)]
 #[diesel(belongs_to(Artist))]
 #[diesel(table_name = super::schema::releases)]
 struct ReleaseForInsert {
@@ -140,6 +342,248 @@ struct ReleaseForInsert {
     pub release_type: Option<String>,
     pub url_youtube: String,
     pub url_metallum: Option<String>,
+    pub first_seen: i64,
+    pub last_seen: i64,
+    pub musicbrainz_id: Option<String>,
+    pub label: Option<String>,
+}
+
+impl ReleaseForInsert {
+    /// Whether the scraped release differs from its stored version.
+    fn differs_from(&self, release: &Release) -> bool {
+        self.month != release.month
+            || self.day != release.day
+            || self.album != release.album
+            || self.release_type != release.release_type
+            || self.url_metallum != release.url_metallum
+            || (self.musicbrainz_id.is_some() && self.musicbrainz_id != release.musicbrainz_id)
+            || (self.label.is_some() && self.label != release.label)
+    }
+}
+
+/// The changes to apply to the stored releases of a year so that they match
+/// a freshly scraped calendar.
+#[derive(Debug, Default, PartialEq)]
+struct ReleasesDiff {
+    /// The releases that were never seen before.
+    inserts: Vec<ReleaseForInsert>,
+    /// The IDs of the stored releases that changed, along with their new values.
+    updates: Vec<(i32, ReleaseForInsert)>,
+    /// The IDs of the stored releases that are still listed as they are.
+    unchanged: Vec<i32>,
+    /// The IDs of the stored releases that are no longer listed.
+    removals: Vec<i32>,
+}
+
+/// Saves the artists of the calendar's releases and returns the releases as they would be
+/// inserted into the `releases` table.
+fn scraped_releases(
+    conn: &mut SqliteConnection,
+    calendar: &Calendar,
+    now: i64,
+) -> Result<Vec<ReleaseForInsert>> {
+    use super::schema::*;
+
+    let mut scraped = Vec::new();
+
+    for (month, data) in calendar.data.iter() {
+        for (day, releases) in data.iter() {
+            for release in releases.iter() {
+                let artist_name = release.artist.clone();
+
+                let genre = release.genre().map(String::from);
+
+                let url_metallum = release
+                    .metallum_info
+                    .as_ref()
+                    .map(|info| info.artist_link.clone());
+
+                let artist_musicbrainz_id = release
+                    .musicbrainz_info
+                    .as_ref()
+                    .and_then(|info| info.artist_id.clone());
+
+                let artist_id: i32 = match diesel::insert_or_ignore_into(artists::table)
+                    .values(&ArtistForInsert::new(
+                        &artist_name,
+                        genre,
+                        url_metallum,
+                        artist_musicbrainz_id.clone(),
+                    ))
+                    .returning(artists::id)
+                    .get_result(conn)
+                {
+                    Ok(id) => id,
+                    Err(_) => artists::table
+                        .filter(artists::name.eq(&artist_name))
+                        .limit(1)
+                        .select(artists::id)
+                        .get_result(conn)?,
+                };
+
+                if let Some(genre) = &release.override_genre {
+                    diesel::update(artists::table.find(artist_id))
+                        .set(artists::genre.eq(genre))
+                        .execute(conn)?;
+                }
+
+                if let Some(mbid) = &artist_musicbrainz_id {
+                    diesel::update(
+                        artists::table
+                            .find(artist_id)
+                            .filter(artists::musicbrainz_id.is_null()),
+                    )
+                    .set(artists::musicbrainz_id.eq(mbid))
+                    .execute(conn)?;
+                }
+
+                scraped.push(ReleaseForInsert {
+                    year: calendar.year,
+                    month: *month as i32,
+                    day: *day as i32,
+                    artist_id,
+                    album: release.album.clone(),
+                    release_type: release.release_type().map(String::from),
+                    url_youtube: youtube_search_url(&artist_name, &release.album),
+                    url_metallum: release
+                        .metallum_info
+                        .as_ref()
+                        .map(|info| info.album_link.clone()),
+                    first_seen: now,
+                    last_seen: now,
+                    musicbrainz_id: release
+                        .musicbrainz_info
+                        .as_ref()
+                        .map(|info| info.release_group_id.clone()),
+                    label: release.label.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(scraped)
+}
+
+/// Compares the stored releases of a year with the scraped ones.
+///
+/// The releases are matched by artist and normalized album title. A removed
+/// release that is listed again is restored through an update. When the same
+/// album is scraped or stored more than once, only the first one is kept. The
+/// corrections of the admins are kept: a hidden release is never restored and a
+/// release added by hand is never removed.
+fn diff_releases(stored: &[Release], scraped: Vec<ReleaseForInsert>) -> ReleasesDiff {
+    let mut diff = ReleasesDiff::default();
+
+    let mut stored_by_key = HashMap::new();
+    for release in stored {
+        let key = (release.artist_id, normalize_album(&release.album));
+        match stored_by_key.entry(key) {
+            Entry::Occupied(_) if !release.is_removed => diff.removals.push(release.id),
+            Entry::Occupied(_) => {}
+            Entry::Vacant(entry) => {
+                entry.insert(release);
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for release in scraped {
+        let key = (release.artist_id, normalize_album(&release.album));
+        if !seen.insert(key.clone()) {
+            continue;
+        }
+
+        match stored_by_key.remove(&key) {
+            None => diff.inserts.push(release),
+            Some(old) if old.is_hidden => diff.unchanged.push(old.id),
+            Some(old) if old.is_removed || release.differs_from(old) => {
+                diff.updates.push((old.id, release))
+            }
+            Some(old) => diff.unchanged.push(old.id),
+        }
+    }
+
+    diff.removals.extend(
+        stored_by_key
+            .into_values()
+            .filter(|release| !release.is_removed && !release.is_manual)
+            .map(|release| release.id),
+    );
+    diff.removals.sort_unstable();
+
+    diff
+}
+
+/// The changes to apply to the stored releases of a year, along with them.
+struct YearDiff {
+    year: i32,
+    stored: Vec<Release>,
+    diff: ReleasesDiff,
+}
+
+/// Pairs the new releases of each year with the release of the same artist and album
+/// that left the previous or the next year, either earlier or in the same update.
+///
+/// The paired releases are taken out of the inserts and the removals of the years, so
+/// that they are moved rather than inserted again. A hidden release is never moved.
+fn match_moves(
+    years: &mut [YearDiff],
+    removed_nearby: Vec<Release>,
+) -> Vec<(Release, ReleaseForInsert)> {
+    let listed = years
+        .iter()
+        .flat_map(|year_diff| {
+            let diff = &year_diff.diff;
+            diff.updates
+                .iter()
+                .map(|(id, _)| *id)
+                .chain(diff.unchanged.iter().copied())
+        })
+        .collect::<HashSet<_>>();
+
+    let mut leaving_rows = Vec::new();
+    for year_diff in years.iter_mut() {
+        let (leaving, kept) = std::mem::take(&mut year_diff.stored)
+            .into_iter()
+            .partition::<Vec<_>, _>(|release| year_diff.diff.removals.contains(&release.id));
+        year_diff.stored = kept;
+        leaving_rows.extend(leaving);
+    }
+    let mut candidates = removed_nearby
+        .into_iter()
+        .filter(|release| !release.is_hidden && !listed.contains(&release.id))
+        .chain(leaving_rows)
+        .map(|release| {
+            let key = (
+                release.year,
+                release.artist_id,
+                normalize_album(&release.album),
+            );
+            (key, release)
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut moves = Vec::new();
+    for year_diff in years.iter_mut() {
+        let year = year_diff.year;
+        for release in std::mem::take(&mut year_diff.diff.inserts) {
+            let album = normalize_album(&release.album);
+            let old = [year - 1, year + 1]
+                .into_iter()
+                .find_map(|other| candidates.remove(&(other, release.artist_id, album.clone())));
+            match old {
+                Some(old) => moves.push((old, release)),
+                None => year_diff.diff.inserts.push(release),
+            }
+        }
+    }
+
+    let moved = moves.iter().map(|(old, _)| old.id).collect::<HashSet<_>>();
+    for year_diff in years.iter_mut() {
+        year_diff.diff.removals.retain(|id| !moved.contains(id));
+    }
+
+    moves
 }
 
 #[axum::async_trait]
@@ -148,12 +592,17 @@ struct ReleaseForInsert {
 /// It can be implemented by any backend service or repository pattern to support
 // different data storage and retrieval strategies.
 pub trait CalendarRepository {
-    /// Creates or updates a calendar with the provided data.
+    /// Creates or updates the calendars of one or more years with the provided data.
     ///
-    /// This method inserts new releases into the `releases` table
-    /// or updates existing ones based on the calendar data. It
-    /// handles linking artists and adding external links (YouTube, Bandcamp).
-    async fn create_or_update(&self, calendar: Calendar) -> Result<()>;
+    /// This method applies the manual overrides to the calendars, then inserts the new
+    /// releases into the `releases` table and updates the existing ones based on the
+    /// calendar data, keeping their IDs.
+    /// The releases that are no longer listed are marked as removed rather than
+    /// deleted, except those added by an admin, and the hidden ones stay removed.
+    /// The calendars are saved at once so that a release moved to the previous or
+    /// next year keeps its ID, whichever year is listed first.
+    /// It handles linking artists and adding external links (YouTube, Bandcamp).
+    async fn create_or_update(&self, calendars: Vec<Calendar>) -> Result<()>;
 
     /// Retrieves releases for the current date.
     ///
@@ -173,6 +622,9 @@ pub trait CalendarRepository {
         target_day: u8,
     ) -> Result<Vec<(Release, Artist)>>;
 
+    /// Retrieves the releases between two dates, both inclusive, ordered by date.
+    fn get_between(&self, from: Date, to: Date) -> Result<Vec<(Release, Artist)>>;
+
     /// Fetches the number of releases for the given date.
     fn fetch_releases(
         &self,
@@ -181,14 +633,24 @@ pub trait CalendarRepository {
         target_day: u8,
     ) -> Result<Vec<(Release, Artist)>>;
 
+    /// Returns the distinct years having releases, in ascending order.
+    fn years(&self) -> Result<Vec<i32>>;
+
+    /// Retrieves the date changes detected since the given Unix timestamp, along
+    /// with their release and artist, the most recent first.
+    fn date_changes(&self, since: i64) -> Result<Vec<(ReleaseChange, Release, Artist)>>;
+
     /// Returns the number of releases for a specific date, if any.
     fn num_releases(&self, target_year: u32, target_month: u8, target_day: u8) -> Option<i64>;
 
-    /// Asynchronously updates Bandcamp URLs for artists missing them in the database.
+    /// Asynchronously searches the Bandcamp pages of the artists and stores the verified links.
     ///
-    /// This function fetches Bandcamp links for artists whose `url_bandcamp` field is `NULL`
-    /// and updates the corresponding records in the database. The function only runs in
-    /// production mode. If not, it logs a warning and exits early.
+    /// The artists never checked are searched, as well as those without a page that were
+    /// checked over a month ago. A page is only kept when it matches the artist's name and
+    /// albums, along with its confidence score. The links set by an admin are left as they
+    /// are. The function only runs in production mode. If not, it logs a warning and exits early.
+    ///
+    /// Returns the number of artists whose page was found.
     ///
     /// # Errors
     ///
@@ -197,9 +659,66 @@ pub trait CalendarRepository {
     /// - Updating the artist records in the database fails.
     /// - Fetching Bandcamp links encounters an error.
     ///
-    async fn update_bandcamp(&self, client: &(dyn Client + Sync)) -> Result<()>;
+    async fn update_bandcamp(&self, client: &(dyn BandcampClient + Sync)) -> Result<usize>;
+
+    /// Asynchronously scrapes the countries of origin of the artists and the record labels
+    /// of the upcoming releases that the calendars lacked from The Metal Archives.
+    ///
+    /// Each band and album page is fetched once, the soonest releases first, and fetched
+    /// again on the next update if it could not be. At most `MAX_METALLUM_PAGES` pages of
+    /// each kind are fetched per update because of the crawl delay. The labels are left to
+    /// `update_tracklists` when `FETCH_TRACKLISTS` is set, which scrapes the same album pages.
+    /// The function only runs in production mode. If not, it logs a warning and exits early.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if there is an issue querying or updating the database.
+    async fn update_metallum_details(&self, client: &(dyn MetallumClient + Sync)) -> Result<()>;
+
+    /// Asynchronously finds the album covers of the upcoming releases and stores their
+    /// thumbnails in the covers folder.
+    ///
+    /// The releases never checked are searched, the soonest first, as well as those without
+    /// a cover that were checked over a week ago because covers are often revealed close to
+    /// the release. At most `MAX_COVERS` releases are searched per update. The function only
+    /// runs in production mode. If not, it logs a warning and exits early.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if there is an issue querying or updating the database.
+    async fn update_covers(&self, client: &(dyn CoverClient + Sync)) -> Result<()>;
+
+    /// Asynchronously scrapes the format, label, tracklist and running time of the recent and
+    /// upcoming releases from their album page on The Metal Archives.
+    ///
+    /// The releases of the past month and later that were never checked are scraped, the
+    /// soonest first, as well as those still without a running time that were checked over a
+    /// week ago because tracklists are often revealed close to the release. The pages that
+    /// could not be fetched are tried again on the next update. The label of a
+    /// release is set when it has none. At most `MAX_METALLUM_PAGES` pages are fetched per
+    /// update. The function only runs in production mode. If not, it logs a warning and exits early.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if there is an issue querying or updating the database.
+    async fn update_tracklists(&self, client: &(dyn MetallumClient + Sync)) -> Result<()>;
 }
 
+/// The delay after which the Bandcamp page of an artist that had none is searched again.
+const BANDCAMP_RECHECK_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// The number of band pages, and of album pages, scraped from The Metal Archives per update.
+const MAX_METALLUM_PAGES: i64 = 500;
+
+/// The delay after which the cover of a release that had none is searched again.
+const COVER_RECHECK_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// The number of releases whose cover is searched per update.
+const MAX_COVERS: i64 = 300;
+
+/// The delay after which the album page of a release without a running time is scraped again.
+const TRACKLIST_RECHECK_SECS: i64 = 7 * 24 * 60 * 60;
+
 /// `CalendarBmc` is a backend model controller responsible for
 /// managing calendar-related operations.
 ///
@@ -209,68 +728,87 @@ pub struct CalendarBmc;
 
 #[axum::async_trait]
 impl CalendarRepository for CalendarBmc {
-    async fn create_or_update(&self, calendar: Calendar) -> Result<()> {
+    async fn create_or_update(&self, calendars: Vec<Calendar>) -> Result<()> {
         use super::schema::*;
 
+        let now = date_now().unix_timestamp();
         let conn = &mut ModelManager::new().conn;
         conn.transaction::<_, Error, _>(|conn| {
-            diesel::delete(releases::table.filter(releases::year.eq(calendar.year)))
-                .execute(conn)?;
+            let overrides = overrides::table
+                .select(Override::as_select())
+                .load(conn)?;
 
-            for (month, data) in calendar.data.iter() {
-                for (day, releases) in data.iter() {
-                    for release in releases.iter() {
-                        let artist_name = release.artist.clone();
-
-                        let genre = release
-                            .metallum_info
-                            .as_ref()
-                            .map(|info| info.genre.clone());
-
-                        let url_metallum = release
-                            .metallum_info
-                            .as_ref()
-                            .map(|info| info.artist_link.clone());
-
-                        let artist_id: i32 = match diesel::insert_or_ignore_into(artists::table)
-                            .values(&ArtistForInsert::new(&artist_name, genre, url_metallum))
-                            .returning(artists::id)
-                            .get_result(conn)
-                        {
-                            Ok(id) => id,
-                            Err(_) => artists::table
-                                .filter(artists::name.eq(&artist_name))
-                                .limit(1)
-                                .select(artists::id)
-                                .get_result(conn)?,
-                        };
-
-                        let query = format!("{} {} full album", artist_name, release.album.clone());
-                        let mut query_encoded = String::new();
-                        url_escape::encode_query_to_string(query, &mut query_encoded);
-
-                        diesel::insert_into(releases::table)
-                            .values(&ReleaseForInsert {
-                                year: calendar.year,
-                                month: *month as i32,
-                                day: *day as i32,
-                                artist_id,
-                                album: release.album.clone(),
-                                release_type: release
-                                    .metallum_info
-                                    .as_ref()
-                                    .map(|info| info.release_type.clone()),
-                                url_youtube: format!(
-                                    "https://www.youtube.com/results?search_query={query_encoded}"
-                                ),
-                                url_metallum: release
-                                    .metallum_info
-                                    .as_ref()
-                                    .map(|info| info.album_link.clone()),
-                            })
-                            .execute(conn)?;
-                    }
+            let mut years = Vec::new();
+            for calendar in calendars {
+                let calendar = calendar.apply_overrides(&overrides);
+                let scraped = scraped_releases(conn, &calendar, now)?;
+
+                let stored: Vec<Release> = releases::table
+                    .filter(releases::year.eq(calendar.year))
+                    .select(Release::as_select())
+                    .load(conn)?;
+
+                let diff = diff_releases(&stored, scraped);
+                years.push(YearDiff {
+                    year: calendar.year,
+                    stored,
+                    diff,
+                });
+            }
+
+            let removed_nearby = releases::table
+                .filter(
+                    releases::year.eq_any(
+                        years
+                            .iter()
+                            .flat_map(|year| [year.year - 1, year.year + 1])
+                            .collect::<Vec<_>>(),
+                    ),
+                )
+                .filter(releases::is_removed.eq(true))
+                .filter(releases::is_hidden.eq(false))
+                .select(Release::as_select())
+                .load::<Release>(conn)?;
+
+            let moves = match_moves(&mut years, removed_nearby);
+
+            for YearDiff { year, stored, diff } in &years {
+                info!(
+                    "Calendar {year}: {} new, {} updated, {} moved from another year, {} unchanged and {} removed releases",
+                    diff.inserts.len(),
+                    diff.updates.len(),
+                    moves.iter().filter(|(_, release)| release.year == *year).count(),
+                    diff.unchanged.len(),
+                    diff.removals.len()
+                );
+
+                diesel::insert_into(releases::table)
+                    .values(&diff.inserts)
+                    .execute(conn)?;
+
+                let stored_by_id = stored
+                    .iter()
+                    .map(|release| (release.id, release))
+                    .collect::<HashMap<_, _>>();
+
+                for (release_id, release) in &diff.updates {
+                    let Some(old) = stored_by_id.get(release_id) else {
+                        continue;
+                    };
+                    update_release(conn, old, release, now)?;
                 }
+
+                diesel::update(releases::table.filter(releases::id.eq_any(&diff.unchanged)))
+                    .set(releases::last_seen.eq(now))
+                    .execute(conn)?;
+
+                diesel::update(releases::table.filter(releases::id.eq_any(&diff.removals)))
+                    .set(releases::is_removed.eq(true))
+                    .execute(conn)?;
+            }
+
+            for (old, release) in &moves {
+                update_release(conn, old, release, now)?;
             }
 
             Ok(())
@@ -296,6 +834,20 @@ impl CalendarRepository for CalendarBmc {
         Ok(releases)
     }
 
+    fn get_between(&self, from: Date, to: Date) -> Result<Vec<(Release, Artist)>> {
+        use super::schema::{artists::dsl::*, releases::dsl::*};
+
+        let results = releases
+            .inner_join(artists)
+            .filter((year * 10000 + month * 100 + day).between(date_int(from), date_int(to)))
+            .filter(is_removed.eq(false))
+            .order((year.asc(), month.asc(), day.asc(), name.asc()))
+            .select((Release::as_select(), Artist::as_select()))
+            .load(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+
     fn fetch_releases(
         &self,
         target_year: u32,
@@ -311,6 +863,7 @@ impl CalendarRepository for CalendarBmc {
                     .and(month.eq(target_month as i32))
                     .and(day.eq(target_day as i32)),
             )
+            .filter(is_removed.eq(false))
             .order(name.asc())
             .select((Release::as_select(), Artist::as_select()))
             .load(&mut ModelManager::new().conn)?;
@@ -327,6 +880,7 @@ impl CalendarRepository for CalendarBmc {
                     .and(month.eq(target_month as i32))
                     .and(day.eq(target_day as i32)),
             )
+            .filter(is_removed.eq(false))
             .count()
             .get_result(&mut ModelManager::new().conn)
             .map_err(|err| error!("Failed to fetch num_releases in StatisticsBmc: {err}"))
@@ -334,80 +888,718 @@ impl CalendarRepository for CalendarBmc {
             .filter(|&num| num > 0)
     }
 
-    async fn update_bandcamp(&self, client: &(dyn Client + Sync)) -> Result<()> {
+    fn years(&self) -> Result<Vec<i32>> {
+        use super::schema::releases::dsl::*;
+
+        let results = releases
+            .filter(is_removed.eq(false))
+            .select(year)
+            .distinct()
+            .order(year.asc())
+            .load::<i32>(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+
+    fn date_changes(&self, since: i64) -> Result<Vec<(ReleaseChange, Release, Artist)>> {
+        use super::schema::*;
+
+        let results = release_history::table
+            .inner_join(releases::table.inner_join(artists::table))
+            .filter(release_history::changed_at.ge(since))
+            .filter(releases::is_removed.eq(false))
+            .order((
+                release_history::changed_at.desc(),
+                release_history::id.desc(),
+            ))
+            .select((
+                ReleaseChange::as_select(),
+                Release::as_select(),
+                Artist::as_select(),
+            ))
+            .load(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+
+    async fn update_bandcamp(&self, client: &(dyn BandcampClient + Sync)) -> Result<usize> {
         use super::schema::*;
 
         if !config().IS_PROD {
             warn!("Can only fetch Bandcamp links when in production.");
-            return Ok(());
+            return Ok(0);
         }
 
         let conn = &mut ModelManager::new().conn;
+        let now = date_now().unix_timestamp();
 
-        let mut all_artists: Vec<Artist> = artists::table
-            .filter(artists::url_bandcamp.is_null())
+        let candidates: Vec<Artist> = artists::table
+            .filter(artists::bandcamp_locked.eq(false))
+            .filter(
+                artists::bandcamp_checked_at
+                    .is_null()
+                    .or(artists::url_bandcamp
+                        .is_null()
+                        .and(artists::bandcamp_checked_at.lt(now - BANDCAMP_RECHECK_SECS))),
+            )
             .select(Artist::as_select())
             .load(conn)?;
 
-        info!("Fetching {} Bandcamp links", all_artists.len());
+        let mut albums_by_artist: HashMap<i32, Vec<String>> = HashMap::new();
+        for (artist_id, album) in releases::table
+            .filter(releases::artist_id.eq_any(candidates.iter().map(|artist| artist.id)))
+            .filter(releases::is_removed.eq(false))
+            .select((releases::artist_id, releases::album))
+            .load::<(i32, String)>(conn)?
+        {
+            albums_by_artist.entry(artist_id).or_default().push(album);
+        }
 
-        let mut num_success = 0;
-        for artist in &mut all_artists {
-            artist.url_bandcamp = client
-                .get_bandcamp_link(artist.name.clone())
-                .await
-                .map(|url| url.to_string());
+        info!(
+            "Searching the Bandcamp pages of {} artists",
+            candidates.len()
+        );
 
-            if artist.url_bandcamp.is_some() {
+        let mut num_success = 0;
+        for artist in &candidates {
+            let albums = albums_by_artist.remove(&artist.id).unwrap_or_default();
+            let found = bandcamp::find_artist_page(client, &artist.name, &albums).await;
+            if found.is_some() {
                 num_success += 1;
             }
+
+            diesel::update(artists::table.find(artist.id))
+                .set((
+                    artists::url_bandcamp.eq(found.as_ref().map(|found| found.url.clone())),
+                    artists::bandcamp_confidence.eq(found.map(|found| found.confidence)),
+                    artists::bandcamp_checked_at.eq(now),
+                ))
+                .execute(conn)?;
         }
 
         info!(
             "{num_success}/{} artists have a Bandcamp page.",
-            all_artists.len()
+            candidates.len()
         );
 
-        for artist in &all_artists {
-            diesel::update(artists::table.find(artist.id))
-                .set(artist)
+        Ok(num_success)
+    }
+
+    async fn update_metallum_details(&self, client: &(dyn MetallumClient + Sync)) -> Result<()> {
+        use super::schema::*;
+
+        if !config().IS_PROD {
+            warn!("Can only fetch the Metallum pages when in production.");
+            return Ok(());
+        }
+
+        let conn = &mut ModelManager::new().conn;
+        let now = date_now();
+        let today = date_int(now.date());
+
+        let bands: Vec<(i32, String)> = artists::table
+            .filter(artists::country.is_null())
+            .filter(artists::metallum_checked_at.is_null())
+            .filter(artists::url_metallum.is_not_null())
+            .order(artists::id.desc())
+            .limit(MAX_METALLUM_PAGES)
+            .select((artists::id, artists::url_metallum.assume_not_null()))
+            .load(conn)?;
+
+        info!("Scraping the countries of {} artists", bands.len());
+        for (artist_id, url) in &bands {
+            let country = match metallum::scrape_band_country(client, url).await {
+                Ok(country) => country,
+                Err(err) => {
+                    warn!("Could not fetch the band page of artist {artist_id}: {err}");
+                    continue;
+                }
+            };
+
+            diesel::update(artists::table.find(artist_id))
+                .set((
+                    artists::country.eq(country),
+                    artists::metallum_checked_at.eq(now.unix_timestamp()),
+                ))
+                .execute(conn)?;
+        }
+
+        if config().FETCH_TRACKLISTS {
+            info!("Leaving the labels to the scrape of the tracklists");
+            return Ok(());
+        }
+
+        let albums: Vec<(i32, String)> = releases::table
+            .filter(releases::label.is_null())
+            .filter(releases::metallum_checked_at.is_null())
+            .filter(releases::url_metallum.is_not_null())
+            .filter(releases::is_removed.eq(false))
+            .filter((releases::year * 10000 + releases::month * 100 + releases::day).ge(today))
+            .order((
+                releases::year.asc(),
+                releases::month.asc(),
+                releases::day.asc(),
+            ))
+            .limit(MAX_METALLUM_PAGES)
+            .select((releases::id, releases::url_metallum.assume_not_null()))
+            .load(conn)?;
+
+        info!("Scraping the labels of {} releases", albums.len());
+        for (release_id, url) in &albums {
+            let label = match metallum::scrape_album_label(client, url).await {
+                Ok(label) => label,
+                Err(err) => {
+                    warn!("Could not fetch the album page of release {release_id}: {err}");
+                    continue;
+                }
+            };
+
+            diesel::update(releases::table.find(release_id))
+                .set((
+                    releases::label.eq(label),
+                    releases::metallum_checked_at.eq(now.unix_timestamp()),
+                ))
+                .execute(conn)?;
+        }
+
+        Ok(())
+    }
+
+    async fn update_covers(&self, client: &(dyn CoverClient + Sync)) -> Result<()> {
+        use super::schema::*;
+
+        if !config().IS_PROD {
+            warn!("Can only fetch the album covers when in production.");
+            return Ok(());
+        }
+
+        let conn = &mut ModelManager::new().conn;
+        let now = date_now();
+        let today = date_int(now.date());
+
+        let candidates: Vec<(Release, Artist)> = releases::table
+            .inner_join(artists::table)
+            .filter(releases::cover.is_null())
+            .filter(
+                releases::cover_checked_at
+                    .is_null()
+                    .or(releases::cover_checked_at.lt(now.unix_timestamp() - COVER_RECHECK_SECS)),
+            )
+            .filter(releases::is_removed.eq(false))
+            .filter((releases::year * 10000 + releases::month * 100 + releases::day).ge(today))
+            .order((
+                releases::year.asc(),
+                releases::month.asc(),
+                releases::day.asc(),
+            ))
+            .limit(MAX_COVERS)
+            .select((Release::as_select(), Artist::as_select()))
+            .load(conn)?;
+
+        info!("Searching the covers of {} releases", candidates.len());
+
+        let covers_dir = std::path::Path::new(&config().COVERS_DIR);
+        let mut num_success = 0;
+        for (release, artist) in &candidates {
+            let query = CoverQuery {
+                album: &release.album,
+                musicbrainz_id: release.musicbrainz_id.as_deref(),
+                url_metallum: release.url_metallum.as_deref(),
+                url_bandcamp: artist.url_bandcamp.as_deref(),
+            };
+
+            let stored = match cover::find_cover(client, &query).await {
+                Some(image) => match cover::store_thumbnail(&image, covers_dir) {
+                    Ok(stored) => Some(stored),
+                    Err(err) => {
+                        error!("Could not store the cover of release {}: {err}", release.id);
+                        None
+                    }
+                },
+                None => None,
+            };
+            if stored.is_some() {
+                num_success += 1;
+            }
+            let (name, size) = stored.unzip();
+
+            diesel::update(releases::table.find(release.id))
+                .set((
+                    releases::cover.eq(name),
+                    releases::cover_size.eq(size.map(|size| size as i32)),
+                    releases::cover_checked_at.eq(now.unix_timestamp()),
+                ))
                 .execute(conn)?;
         }
 
+        info!("{num_success}/{} releases have a cover.", candidates.len());
+
+        Ok(())
+    }
+
+    async fn update_tracklists(&self, client: &(dyn MetallumClient + Sync)) -> Result<()> {
+        use super::schema::*;
+
+        if !config().IS_PROD {
+            warn!("Can only fetch the tracklists when in production.");
+            return Ok(());
+        }
+
+        let conn = &mut ModelManager::new().conn;
+        let now = date_now();
+        let since = date_int(now.date() - time::Duration::days(30));
+
+        let albums: Vec<(i32, String)> = releases::table
+            .left_join(release_details::table)
+            .filter(releases::url_metallum.is_not_null())
+            .filter(releases::is_removed.eq(false))
+            .filter((releases::year * 10000 + releases::month * 100 + releases::day).ge(since))
+            .filter(release_details::release_id.nullable().is_null().or(
+                release_details::runtime.is_null().and(
+                    release_details::checked_at.lt(now.unix_timestamp() - TRACKLIST_RECHECK_SECS),
+                ),
+            ))
+            .order((
+                releases::year.asc(),
+                releases::month.asc(),
+                releases::day.asc(),
+            ))
+            .limit(MAX_METALLUM_PAGES)
+            .select((releases::id, releases::url_metallum.assume_not_null()))
+            .load(conn)?;
+
+        info!("Scraping the tracklists of {} releases", albums.len());
+
+        let mut num_success = 0;
+        for (release_id, url) in &albums {
+            let Some(details) = metallum::scrape_album_details(client, url).await else {
+                warn!("Could not fetch the album page of release {release_id}");
+                continue;
+            };
+            if !details.tracks.is_empty() {
+                num_success += 1;
+            }
+
+            conn.transaction::<_, Error, _>(|conn| {
+                let row = ReleaseDetailsForInsert::new(*release_id, &details, now.unix_timestamp());
+                diesel::insert_into(release_details::table)
+                    .values(&row)
+                    .on_conflict(release_details::release_id)
+                    .do_update()
+                    .set(&row)
+                    .execute(conn)?;
+
+                diesel::delete(tracks::table.filter(tracks::release_id.eq(release_id)))
+                    .execute(conn)?;
+                diesel::insert_into(tracks::table)
+                    .values(
+                        details
+                            .tracks
+                            .iter()
+                            .map(|track| TrackForInsert::new(*release_id, track))
+                            .collect::<Vec<_>>(),
+                    )
+                    .execute(conn)?;
+
+                if let Some(label) = &details.label {
+                    diesel::update(
+                        releases::table
+                            .find(release_id)
+                            .filter(releases::label.is_null()),
+                    )
+                    .set(releases::label.eq(label))
+                    .execute(conn)?;
+                }
+
+                Ok(())
+            })?;
+        }
+
+        info!("{num_success}/{} releases have a tracklist.", albums.len());
+
         Ok(())
     }
 }
 
+/// Updates a stored release to match its scraped version and restores it if it
+/// was removed. A change of date is recorded in the `release_history` table.
+fn update_release(
+    conn: &mut SqliteConnection,
+    old: &Release,
+    release: &ReleaseForInsert,
+    now: i64,
+) -> Result<()> {
+    use super::schema::*;
+
+    let old_date = date_int(old.date());
+    let new_date = release.year * 10000 + release.month * 100 + release.day;
+
+    let moved_from = if old_date == new_date {
+        old.moved_from
+    } else {
+        diesel::insert_into(release_history::table)
+            .values(&ReleaseChangeForInsert {
+                release_id: old.id,
+                old_date,
+                new_date,
+                changed_at: now,
+            })
+            .execute(conn)?;
+        Some(old_date)
+    };
+
+    diesel::update(releases::table.find(old.id))
+        .set((
+            releases::year.eq(release.year),
+            releases::month.eq(release.month),
+            releases::day.eq(release.day),
+            releases::album.eq(&release.album),
+            releases::release_type.eq(&release.release_type),
+            releases::url_youtube.eq(&release.url_youtube),
+            releases::url_metallum.eq(&release.url_metallum),
+            releases::last_seen.eq(now),
+            releases::is_removed.eq(false),
+            releases::moved_from.eq(moved_from),
+            releases::musicbrainz_id.eq(release
+                .musicbrainz_id
+                .as_ref()
+                .or(old.musicbrainz_id.as_ref())),
+            releases::label.eq(release.label.as_ref().or(old.label.as_ref())),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Returns the URL of the YouTube search for the full album of the artist.
+pub(in crate::model) fn youtube_search_url(artist: &str, album: &str) -> String {
+    let mut query_encoded = String::new();
+    url_escape::encode_query_to_string(format!("{artist} {album} full album"), &mut query_encoded);
+    format!("https://www.youtube.com/results?search_query={query_encoded}")
+}
+
+/// Converts a date to its `YYYYMMDD` integer representation.
+fn date_int(date: Date) -> i32 {
+    date.year() * 10000 + date.month() as i32 * 100 + date.day() as i32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::fixtures;
 
     #[test]
     fn test_release_all_fields_to_html_ok() {
         let artist = Artist {
-            id: 1,
-            name: String::from("Wintersun"),
             genre: Some(String::from("Symphonic Melodic Death Metal")),
             url_bandcamp: Some(String::from("https://wintersun.bandcamp.com")),
             url_metallum: Some(String::from(
                 "https://www.metal-archives.com/band/wintersun",
             )),
+            country: Some(String::from("Finland")),
+            ..fixtures::an_artist()
         };
         let release = Release {
-            id: 1,
-            year: 2024,
-            month: 8,
             day: 31,
-            artist_id: 1,
-            album: String::from("Time II"),
             release_type: Some(String::from("Full-Length")),
-            url_youtube: String::from("https://www.youtube.com"),
             url_metallum: Some(String::from("https://www.metal-archives.com")),
+            label: Some(String::from("Nuclear Blast")),
+            ..fixtures::a_release()
         };
 
         let got = release.to_html(&artist);
 
-        let want = "<li style=\"margin-bottom: 1rem\"><b>Wintersun - Time II</b><ul><li>Symphonic Melodic Death Metal</li><li>Full-Length</li><li><a href=\"https://www.youtube.com\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Youtube</a></li><li><a href=\"https://wintersun.bandcamp.com\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Bandcamp</a></li><li><a href=\"https://www.metal-archives.com/band/wintersun\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Metallum (band)</a></li><li><a href=\"https://www.metal-archives.com\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Metallum (album)</a></li></ul></li>";
+        let want = format!(
+            "<li style=\"margin-bottom: 1rem\"><b><a href=\"{}/releases/1-wintersun-time-ii\" class=\"link link-hover\">Wintersun - Time II</a></b><ul><li>Symphonic Melodic Death Metal</li><li>Finland</li><li>Full-Length</li><li>Nuclear Blast</li><li><a href=\"https://www.youtube.com\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Youtube</a></li><li><a href=\"https://wintersun.bandcamp.com\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Bandcamp</a></li><li><a href=\"https://www.metal-archives.com/band/wintersun\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Metallum (band)</a></li><li><a href=\"https://www.metal-archives.com\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Metallum (album)</a></li></ul></li>",
+            config().HOST_URL
+        );
         pretty_assertions::assert_eq!(got, want);
     }
+
+    #[test]
+    fn test_release_moved_to_html_ok() {
+        let artist = fixtures::an_artist();
+        let release = Release {
+            month: 10,
+            day: 4,
+            moved_from: Some(20240830),
+            ..fixtures::a_release()
+        };
+
+        let got = release.to_html(&artist);
+
+        let want = format!(
+            "<li style=\"margin-bottom: 1rem\"><b><a href=\"{}/releases/1-wintersun-time-ii\" class=\"link link-hover\">Wintersun - Time II</a></b> <span class=\"badge badge-warning\" title=\"Previously on August 30, 2024\">Postponed</span><ul><li><a href=\"https://www.youtube.com\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Youtube</a></li></ul></li>",
+            config().HOST_URL
+        );
+        pretty_assertions::assert_eq!(got, want);
+    }
+
+    #[test]
+    fn test_release_escaped_to_html_ok() {
+        let artist = Artist {
+            country: Some(String::from("<script>alert(1)</script>")),
+            ..fixtures::an_artist()
+        };
+        let release = Release {
+            album: String::from("Time \"II\" & <III>"),
+            label: Some(String::from("Nuclear <b>Blast</b>")),
+            cover: Some(String::from("0123456789abcdef.jpg")),
+            ..fixtures::a_release()
+        };
+
+        let got = release.to_html(&artist);
+
+        let want = format!(
+            "<li style=\"margin-bottom: 1rem\"><b><a href=\"{0}/releases/1-wintersun-time-ii-and-iii\" class=\"link link-hover\">Wintersun - Time &quot;II&quot; &amp; &lt;III&gt;</a></b><br><img src=\"{0}/covers/0123456789abcdef.jpg\" alt=\"Cover of Time &quot;II&quot; &amp; &lt;III&gt;\" width=\"150\" loading=\"lazy\" style=\"margin-top: 0.5rem\"><ul><li>&lt;script&gt;alert(1)&lt;/script&gt;</li><li>Nuclear &lt;b&gt;Blast&lt;/b&gt;</li><li><a href=\"https://www.youtube.com\" target=\"_blank\" class=\"link link-primary visited:link-secondary focus:link-accent\">Youtube</a></li></ul></li>",
+            config().HOST_URL
+        );
+        pretty_assertions::assert_eq!(got, want);
+    }
+
+    #[test]
+    fn test_release_to_vevent_ok() {
+        let artist = Artist {
+            id: 7,
+            genre: Some(String::from("Symphonic Melodic Death Metal")),
+            ..fixtures::an_artist()
+        };
+        let release = Release {
+            day: 31,
+            artist_id: 7,
+            release_type: Some(String::from("Full-Length")),
+            ..fixtures::a_release()
+        };
+
+        let got = release.to_vevent(&artist, "20240801T000000Z", "localhost");
+
+        let want = "BEGIN:VEVENT\r\nUID:release-1@localhost\r\nDTSTAMP:20240801T000000Z\r\nDTSTART;VALUE=DATE:20240831\r\nDTEND;VALUE=DATE:20240901\r\nSUMMARY:Wintersun - Time II\r\nDESCRIPTION:Genre: Symphonic Melodic Death Metal\\nType: Full-Length\\nYouTub\r\n e: https://www.youtube.com\r\nCATEGORIES:Symphonic Melodic Death Metal\r\nURL:https://www.youtube.com\r\nTRANSP:TRANSPARENT\r\nEND:VEVENT\r\n";
+        pretty_assertions::assert_eq!(got, want);
+    }
+
+    #[test]
+    fn test_release_permalink_ok() {
+        let artist = Artist {
+            name: String::from("Motörhead"),
+            ..fixtures::an_artist()
+        };
+        let release = stored_release(42, 1, "Ace of Spades (40th Anniversary)", 8, 31, false);
+
+        let got = release.permalink(&artist, "https://www.metal-releases.com");
+
+        pretty_assertions::assert_eq!(
+            got,
+            "https://www.metal-releases.com/releases/42-motorhead-ace-of-spades-40th-anniversary"
+        );
+    }
+
+    #[test]
+    fn test_release_cover_url_ok() {
+        let mut release = stored_release(1, 1, "Time II", 8, 31, false);
+
+        let got_without = release.cover_url("https://www.metal-releases.com");
+        release.cover = Some(String::from("0123456789abcdef.jpg"));
+        let got = release.cover_url("https://www.metal-releases.com");
+
+        pretty_assertions::assert_eq!(got_without, None);
+        pretty_assertions::assert_eq!(
+            got,
+            Some(String::from(
+                "https://www.metal-releases.com/covers/0123456789abcdef.jpg"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_diff_releases_ok() {
+        let stored = vec![
+            stored_release(1, 1, "Time II", 8, 30, false),
+            stored_release(2, 2, "Moved", 9, 1, false),
+            stored_release(3, 3, "Gone", 9, 2, false),
+            stored_release(4, 4, "Back", 9, 3, true),
+            stored_release(5, 5, "Still Gone", 9, 4, true),
+        ];
+        let scraped = vec![
+            scraped_release(1, "TIME II", 8, 30),
+            scraped_release(2, "Moved", 10, 1),
+            scraped_release(4, "Back", 9, 3),
+            scraped_release(6, "New", 11, 11),
+        ];
+
+        let got = diff_releases(&stored, scraped);
+
+        pretty_assertions::assert_eq!(
+            got,
+            ReleasesDiff {
+                inserts: vec![scraped_release(6, "New", 11, 11)],
+                updates: vec![
+                    (1, scraped_release(1, "TIME II", 8, 30)),
+                    (2, scraped_release(2, "Moved", 10, 1)),
+                    (4, scraped_release(4, "Back", 9, 3)),
+                ],
+                unchanged: vec![],
+                removals: vec![3],
+            }
+        );
+    }
+
+    #[test]
+    fn test_match_moves_ok() {
+        let next_year = |id, artist_id, album, is_removed| Release {
+            year: 2025,
+            ..stored_release(id, artist_id, album, 1, 10, is_removed)
+        };
+        let mut years = vec![
+            YearDiff {
+                year: 2024,
+                stored: vec![],
+                diff: ReleasesDiff {
+                    inserts: vec![
+                        scraped_release(1, "Brought Forward", 12, 20),
+                        scraped_release(2, "Back Again", 12, 21),
+                        scraped_release(3, "Hidden", 12, 22),
+                        scraped_release(4, "New", 12, 23),
+                    ],
+                    ..Default::default()
+                },
+            },
+            YearDiff {
+                year: 2025,
+                stored: vec![next_year(1, 1, "Brought Forward", false)],
+                diff: ReleasesDiff {
+                    removals: vec![1],
+                    ..Default::default()
+                },
+            },
+        ];
+        let removed_nearby = vec![
+            next_year(2, 2, "Back Again", true),
+            Release {
+                is_hidden: true,
+                ..next_year(3, 3, "Hidden", true)
+            },
+        ];
+
+        let got = match_moves(&mut years, removed_nearby)
+            .into_iter()
+            .map(|(old, release)| (old.id, release.album))
+            .collect::<HashMap<_, _>>();
+
+        pretty_assertions::assert_eq!(
+            got,
+            HashMap::from([
+                (1, String::from("Brought Forward")),
+                (2, String::from("Back Again")),
+            ])
+        );
+        pretty_assertions::assert_eq!(
+            years[0].diff.inserts,
+            vec![
+                scraped_release(3, "Hidden", 12, 22),
+                scraped_release(4, "New", 12, 23),
+            ]
+        );
+        pretty_assertions::assert_eq!(years[1].diff.removals, Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_diff_releases_unchanged_ok() {
+        let stored = vec![stored_release(1, 1, "Time II", 8, 30, false)];
+        let scraped = vec![scraped_release(1, "Time II", 8, 30)];
+
+        let got = diff_releases(&stored, scraped);
+
+        pretty_assertions::assert_eq!(
+            got,
+            ReleasesDiff {
+                unchanged: vec![1],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_diff_releases_duplicates_ok() {
+        let stored = vec![
+            stored_release(1, 1, "Time II", 8, 30, false),
+            stored_release(2, 1, "Time II", 8, 30, false),
+        ];
+        let scraped = vec![
+            scraped_release(1, "Time II", 8, 30),
+            scraped_release(1, "Time: II", 8, 31),
+            scraped_release(2, "Time II", 8, 30),
+        ];
+
+        let got = diff_releases(&stored, scraped);
+
+        pretty_assertions::assert_eq!(
+            got,
+            ReleasesDiff {
+                inserts: vec![scraped_release(2, "Time II", 8, 30)],
+                unchanged: vec![1],
+                removals: vec![2],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_diff_releases_keeps_corrections_ok() {
+        let hidden = Release {
+            is_hidden: true,
+            ..stored_release(1, 1, "Time 2", 8, 30, true)
+        };
+        let manual = Release {
+            is_manual: true,
+            ..stored_release(2, 2, "Unlisted", 9, 1, false)
+        };
+        let stored = vec![hidden, manual];
+        let scraped = vec![scraped_release(1, "Time 2", 8, 30)];
+
+        let got = diff_releases(&stored, scraped);
+
+        pretty_assertions::assert_eq!(
+            got,
+            ReleasesDiff {
+                unchanged: vec![1],
+                ..Default::default()
+            }
+        );
+    }
+
+    fn stored_release(
+        id: i32,
+        artist_id: i32,
+        album: &str,
+        month: i32,
+        day: i32,
+        is_removed: bool,
+    ) -> Release {
+        Release {
+            id,
+            month,
+            day,
+            artist_id,
+            album: String::from(album),
+            is_removed,
+            ..fixtures::a_release()
+        }
+    }
+
+    fn scraped_release(artist_id: i32, album: &str, month: i32, day: i32) -> ReleaseForInsert {
+        ReleaseForInsert {
+            year: 2024,
+            month,
+            day,
+            artist_id,
+            album: String::from(album),
+            release_type: None,
+            url_youtube: String::from("https://www.youtube.com"),
+            url_metallum: None,
+            first_seen: 1,
+            last_seen: 1,
+            musicbrainz_id: None,
+            label: None,
+        }
+    }
 }
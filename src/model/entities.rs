@@ -1,6 +1,56 @@
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use diesel::{
+    define_sql_function,
+    prelude::*,
+    sql_types::{BigInt, Integer, Text},
+};
 
-use crate::model::ModelManager;
+use time::Date;
+
+use super::calendar::youtube_search_url;
+use crate::{
+    date_now,
+    error::{Error, Result},
+    model::{Artist, ModelManager, Release, ReleaseDetails, Track},
+};
+
+define_sql_function!(fn lower(x: Text) -> Text);
+
+/// The corrections of an admin to the genre and the links of an artist.
+#[derive(Debug, PartialEq)]
+pub struct ArtistEdit {
+    pub genre: Option<String>,
+    pub url_bandcamp: Option<String>,
+    pub url_metallum: Option<String>,
+}
+
+/// A release added by an admin because no source lists it.
+#[derive(Debug, PartialEq)]
+pub struct NewRelease {
+    /// The name of the artist, who is created when unknown.
+    pub artist: String,
+    /// The genre of the artist, used when the artist is created.
+    pub genre: Option<String>,
+    pub album: String,
+    pub date: Date,
+    pub release_type: Option<String>,
+    pub label: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::releases)]
+struct ManualReleaseForInsert {
+    year: i32,
+    month: i32,
+    day: i32,
+    artist_id: i32,
+    album: String,
+    release_type: Option<String>,
+    url_youtube: String,
+    first_seen: i64,
+    last_seen: i64,
+    label: Option<String>,
+    is_manual: bool,
+}
 
 /// A trait defining the interface for querying a entities of heavy metal releases.
 ///
@@ -9,8 +59,105 @@ use crate::model::ModelManager;
 pub trait EntitiesRepository {
     /// Fetches and returns a sorted list of band names from the database.
     fn bands(&self) -> Vec<String>;
+
+    /// Fetches the artist with the given ID.
+    ///
+    /// Returns an `Error::EntityNotFound` if there is no such artist.
+    fn artist(&self, artist_id: i32) -> Result<Artist>;
+
+    /// Fetches the artist with the given name, ignoring the case.
+    ///
+    /// Returns an `Error::EntityNotFound` if there is no such artist.
+    fn artist_by_name(&self, artist_name: &str) -> Result<Artist>;
+
+    /// Fetches every release of the artist across years, ordered by date.
+    fn artist_releases(&self, artist_id: i32) -> Result<Vec<Release>>;
+
+    /// Fetches the release with the given ID along with its artist.
+    ///
+    /// Returns an `Error::EntityNotFound` if there is no such release.
+    fn release(&self, release_id: i32) -> Result<(Release, Artist)>;
+
+    /// Fetches the details scraped from the album page of the release, if any, and its
+    /// tracklist ordered by disc and position.
+    fn release_tracklist(&self, release_id: i32) -> Result<(Option<ReleaseDetails>, Vec<Track>)>;
+
+    /// Fetches and returns a sorted list of the distinct genres of the artists.
+    fn genres(&self) -> Result<Vec<String>>;
+
+    /// Fetches and returns a sorted list of the distinct countries of origin of the artists.
+    fn countries(&self) -> Vec<String>;
+
+    /// Fetches and returns a sorted list of the distinct record labels of the releases.
+    fn labels(&self) -> Vec<String>;
+
+    /// Searches the releases by artist name and album title, best matches first.
+    ///
+    /// Every word of the query must match the beginning of a word of the artist
+    /// or the album, ignoring the case and the diacritics. The first `offset`
+    /// matches are skipped and at most `limit` releases are returned.
+    fn search(&self, query: &str, offset: i64, limit: i64) -> Result<Vec<(Release, Artist)>>;
+
+    /// Counts the releases matching the search, like `search` without the limit.
+    fn count_search(&self, query: &str) -> Result<i64>;
+
+    /// Sets the Bandcamp link of the artist by hand, or removes it when `None`.
+    ///
+    /// The link is locked so that the calendar updates never search it again.
+    /// Returns an `Error::EntityNotFound` if there is no such artist.
+    fn override_bandcamp(&self, artist_id: i32, url: Option<&str>) -> Result<Artist>;
+
+    /// Sets the genre and the links of the artist by hand.
+    ///
+    /// The calendar updates never change the genre and the Metallum link of a known
+    /// artist, and a changed Bandcamp link is locked like with `override_bandcamp`.
+    /// Returns an `Error::EntityNotFound` if there is no such artist.
+    fn edit_artist(&self, artist_id: i32, edit: &ArtistEdit) -> Result<Artist>;
+
+    /// Hides the release from the calendar, the feeds and the API, or shows it again.
+    ///
+    /// A hidden release stays hidden whatever the sources list. Showing a release
+    /// again also undoes its merge, and leaves it removed if the sources no longer list it.
+    /// Returns an `Error::EntityNotFound` if there is no such release.
+    fn hide_release(&self, release_id: i32, is_hidden: bool) -> Result<()>;
+
+    /// Merges a duplicate release into the release to keep.
+    ///
+    /// The duplicate is hidden and its page redirects to the kept release, which
+    /// inherits the MusicBrainz ID, label and cover it lacks.
+    /// Returns an `Error::EntityNotFound` if either release does not exist, or an
+    /// `Error::InvalidParam` if the releases are the same or the kept one was merged.
+    fn merge_releases(&self, keep_id: i32, duplicate_id: i32) -> Result<()>;
+
+    /// Adds a release that no source lists, along with its artist if unknown.
+    ///
+    /// The release is never removed by the calendar updates.
+    fn add_release(&self, release: &NewRelease) -> Result<(Release, Artist)>;
+
+    /// Fetches the releases hidden or added by the admins, along with their artist,
+    /// the latest first.
+    fn corrected_releases(&self) -> Result<Vec<(Release, Artist)>>;
+}
+
+/// A row of the `releases_search` full-text index matching a search.
+#[derive(QueryableByName)]
+struct SearchHit {
+    #[diesel(sql_type = Integer)]
+    release_id: i32,
+}
+
+/// The number of rows of the `releases_search` full-text index matching a search.
+#[derive(QueryableByName)]
+struct SearchCount {
+    #[diesel(sql_type = BigInt)]
+    count: i64,
 }
 
+/// Selects the rows matching a search, leaving out the removed releases.
+const SEARCH_MATCHES: &str = "FROM releases_search \
+     JOIN releases ON releases.id = releases_search.release_id \
+     WHERE releases_search MATCH ? AND releases.is_removed = 0";
+
 /// `EntitiesBmc` is a backend model controller responsible for
 /// querying what belongs to heavy metal music.
 pub struct EntitiesBmc;
@@ -25,4 +172,398 @@ impl EntitiesRepository for EntitiesBmc {
             .load::<String>(&mut ModelManager::new().conn)
             .unwrap_or_else(|_| vec![])
     }
+
+    fn artist(&self, artist_id: i32) -> Result<Artist> {
+        use super::schema::artists::dsl::*;
+
+        artists
+            .find(artist_id)
+            .select(Artist::as_select())
+            .first(&mut ModelManager::new().conn)
+            .optional()?
+            .ok_or(Error::EntityNotFound {
+                entity: "artist",
+                id: artist_id as i64,
+            })
+    }
+
+    fn artist_by_name(&self, artist_name: &str) -> Result<Artist> {
+        use super::schema::artists::dsl::*;
+
+        artists
+            .filter(lower(name).eq(artist_name.to_lowercase()))
+            .select(Artist::as_select())
+            .first(&mut ModelManager::new().conn)
+            .optional()?
+            .ok_or_else(|| Error::EntityNotFoundByName {
+                entity: "artist",
+                name: artist_name.to_string(),
+            })
+    }
+
+    fn artist_releases(&self, target_artist_id: i32) -> Result<Vec<Release>> {
+        use super::schema::releases::dsl::*;
+
+        let results = releases
+            .filter(artist_id.eq(target_artist_id))
+            .filter(is_removed.eq(false))
+            .order((year.asc(), month.asc(), day.asc(), album.asc()))
+            .select(Release::as_select())
+            .load(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+
+    fn release(&self, target_release_id: i32) -> Result<(Release, Artist)> {
+        use super::schema::{artists, releases};
+
+        releases::table
+            .inner_join(artists::table)
+            .filter(releases::id.eq(target_release_id))
+            .select((Release::as_select(), Artist::as_select()))
+            .first(&mut ModelManager::new().conn)
+            .optional()?
+            .ok_or(Error::EntityNotFound {
+                entity: "release",
+                id: target_release_id as i64,
+            })
+    }
+
+    fn release_tracklist(
+        &self,
+        target_release_id: i32,
+    ) -> Result<(Option<ReleaseDetails>, Vec<Track>)> {
+        use super::schema::{release_details, tracks};
+
+        let conn = &mut ModelManager::new().conn;
+
+        let details = release_details::table
+            .find(target_release_id)
+            .select(ReleaseDetails::as_select())
+            .first(conn)
+            .optional()?;
+
+        let tracklist = tracks::table
+            .filter(tracks::release_id.eq(target_release_id))
+            .order((tracks::disc.asc(), tracks::position.asc()))
+            .select(Track::as_select())
+            .load(conn)?;
+
+        Ok((details, tracklist))
+    }
+
+    fn genres(&self) -> Result<Vec<String>> {
+        use super::schema::artists::dsl::*;
+
+        let results = artists
+            .select(genre.assume_not_null())
+            .filter(genre.is_not_null())
+            .distinct()
+            .order(genre.asc())
+            .load::<String>(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+
+    fn countries(&self) -> Vec<String> {
+        use super::schema::artists::dsl::*;
+
+        artists
+            .select(country.assume_not_null())
+            .filter(country.is_not_null())
+            .distinct()
+            .order(country.asc())
+            .load::<String>(&mut ModelManager::new().conn)
+            .unwrap_or_else(|_| vec![])
+    }
+
+    fn labels(&self) -> Vec<String> {
+        use super::schema::releases::dsl::*;
+
+        releases
+            .select(label.assume_not_null())
+            .filter(label.is_not_null())
+            .filter(is_removed.eq(false))
+            .distinct()
+            .order(label.asc())
+            .load::<String>(&mut ModelManager::new().conn)
+            .unwrap_or_else(|_| vec![])
+    }
+
+    fn search(&self, query: &str, offset: i64, limit: i64) -> Result<Vec<(Release, Artist)>> {
+        use super::schema::{artists, releases};
+
+        let Some(match_query) = match_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let conn = &mut ModelManager::new().conn;
+
+        // The artist column weighs twice as much as the album column in the ranking. The
+        // removed releases are left out before the limit so that they do not take the places
+        // of those listed.
+        let ids = diesel::sql_query(format!(
+            "SELECT releases_search.release_id {SEARCH_MATCHES} \
+             ORDER BY bm25(releases_search, 2.0, 1.0) LIMIT ? OFFSET ?"
+        ))
+        .bind::<Text, _>(match_query)
+        .bind::<BigInt, _>(limit)
+        .bind::<BigInt, _>(offset)
+        .load::<SearchHit>(conn)?
+        .into_iter()
+        .map(|hit| hit.release_id)
+        .collect::<Vec<_>>();
+
+        let mut results = releases::table
+            .inner_join(artists::table)
+            .filter(releases::id.eq_any(&ids))
+            .select((Release::as_select(), Artist::as_select()))
+            .load::<(Release, Artist)>(conn)?;
+
+        results.sort_by_key(|(release, _)| ids.iter().position(|id| *id == release.id));
+        Ok(results)
+    }
+
+    fn count_search(&self, query: &str) -> Result<i64> {
+        let Some(match_query) = match_query(query) else {
+            return Ok(0);
+        };
+
+        let hits = diesel::sql_query(format!("SELECT COUNT(*) AS count {SEARCH_MATCHES}"))
+            .bind::<Text, _>(match_query)
+            .get_result::<SearchCount>(&mut ModelManager::new().conn)?;
+        Ok(hits.count)
+    }
+
+    fn override_bandcamp(&self, target_artist_id: i32, url: Option<&str>) -> Result<Artist> {
+        use super::schema::artists::dsl::*;
+
+        diesel::update(artists.find(target_artist_id))
+            .set((
+                url_bandcamp.eq(url),
+                bandcamp_confidence.eq(url.map(|_| 1.0)),
+                bandcamp_checked_at.eq(date_now().unix_timestamp()),
+                bandcamp_locked.eq(true),
+            ))
+            .returning(Artist::as_returning())
+            .get_result(&mut ModelManager::new().conn)
+            .optional()?
+            .ok_or(Error::EntityNotFound {
+                entity: "artist",
+                id: target_artist_id as i64,
+            })
+    }
+
+    fn edit_artist(&self, target_artist_id: i32, edit: &ArtistEdit) -> Result<Artist> {
+        use super::schema::artists::dsl::*;
+
+        let artist = self.artist(target_artist_id)?;
+        if artist.url_bandcamp != edit.url_bandcamp {
+            self.override_bandcamp(target_artist_id, edit.url_bandcamp.as_deref())?;
+        }
+
+        diesel::update(artists.find(target_artist_id))
+            .set((genre.eq(&edit.genre), url_metallum.eq(&edit.url_metallum)))
+            .returning(Artist::as_returning())
+            .get_result(&mut ModelManager::new().conn)
+            .map_err(Error::from)
+    }
+
+    fn hide_release(&self, target_release_id: i32, hidden: bool) -> Result<()> {
+        use super::schema::releases::dsl::*;
+
+        let conn = &mut ModelManager::new().conn;
+        let release = releases
+            .find(target_release_id)
+            .select(Release::as_select())
+            .first(conn)
+            .optional()?
+            .ok_or(Error::EntityNotFound {
+                entity: "release",
+                id: target_release_id as i64,
+            })?;
+
+        // A release shown again is only restored if the sources still list it, so
+        // that those the scrapers dropped stay removed.
+        let removed = if hidden {
+            true
+        } else {
+            let last_save = releases
+                .filter(year.eq(release.year))
+                .filter(id.ne(release.id))
+                .filter(is_manual.eq(false))
+                .select(diesel::dsl::max(last_seen))
+                .first::<Option<i64>>(conn)?;
+            !is_still_listed(release.is_manual, release.last_seen, last_save)
+        };
+
+        diesel::update(releases.find(target_release_id))
+            .set((
+                is_hidden.eq(hidden),
+                is_removed.eq(removed),
+                merged_into.eq(None::<i32>),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    fn merge_releases(&self, keep_id: i32, duplicate_id: i32) -> Result<()> {
+        use super::schema::releases::dsl::*;
+
+        let (keep, _) = self.release(keep_id)?;
+        let (duplicate, _) = self.release(duplicate_id)?;
+        if keep_id == duplicate_id || keep.merged_into.is_some() {
+            return Err(Error::InvalidParam("release"));
+        }
+
+        let (kept_cover, kept_cover_size) = if keep.cover.is_some() {
+            (keep.cover, keep.cover_size)
+        } else {
+            (duplicate.cover, duplicate.cover_size)
+        };
+
+        let conn = &mut ModelManager::new().conn;
+        conn.transaction::<_, Error, _>(|conn| {
+            diesel::update(releases.find(keep_id))
+                .set((
+                    musicbrainz_id.eq(keep.musicbrainz_id.or(duplicate.musicbrainz_id)),
+                    label.eq(keep.label.or(duplicate.label)),
+                    cover.eq(kept_cover),
+                    cover_size.eq(kept_cover_size),
+                ))
+                .execute(conn)?;
+
+            diesel::update(releases.find(duplicate_id))
+                .set((
+                    is_hidden.eq(true),
+                    is_removed.eq(true),
+                    merged_into.eq(Some(keep_id)),
+                ))
+                .execute(conn)?;
+
+            // The releases merged into the duplicate follow it.
+            diesel::update(releases.filter(merged_into.eq(duplicate_id)))
+                .set(merged_into.eq(keep_id))
+                .execute(conn)?;
+
+            Ok(())
+        })
+    }
+
+    fn add_release(&self, release: &NewRelease) -> Result<(Release, Artist)> {
+        use super::schema::{artists, releases};
+
+        let artist_id = match self.artist_by_name(&release.artist) {
+            Ok(artist) => artist.id,
+            Err(Error::EntityNotFoundByName { .. }) => diesel::insert_into(artists::table)
+                .values((
+                    artists::name.eq(&release.artist),
+                    artists::genre.eq(&release.genre),
+                ))
+                .returning(artists::id)
+                .get_result(&mut ModelManager::new().conn)?,
+            Err(err) => return Err(err),
+        };
+
+        let now = date_now().unix_timestamp();
+        let release_id = diesel::insert_into(releases::table)
+            .values(&ManualReleaseForInsert {
+                year: release.date.year(),
+                month: release.date.month() as i32,
+                day: release.date.day() as i32,
+                artist_id,
+                album: release.album.clone(),
+                release_type: release.release_type.clone(),
+                url_youtube: youtube_search_url(&release.artist, &release.album),
+                first_seen: now,
+                last_seen: now,
+                label: release.label.clone(),
+                is_manual: true,
+            })
+            .returning(releases::id)
+            .get_result(&mut ModelManager::new().conn)?;
+
+        self.release(release_id)
+    }
+
+    fn corrected_releases(&self) -> Result<Vec<(Release, Artist)>> {
+        use super::schema::{artists, releases};
+
+        let results = releases::table
+            .inner_join(artists::table)
+            .filter(
+                releases::is_hidden
+                    .eq(true)
+                    .or(releases::is_manual.eq(true)),
+            )
+            .order((
+                releases::year.desc(),
+                releases::month.desc(),
+                releases::day.desc(),
+            ))
+            .select((Release::as_select(), Artist::as_select()))
+            .load(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+}
+
+/// Whether the sources still listed a release last seen at the given Unix timestamp when
+/// the calendar of its year was last saved, since every save marks the releases it lists
+/// as seen. The releases added by an admin are always listed.
+fn is_still_listed(is_manual: bool, last_seen: i64, last_save: Option<i64>) -> bool {
+    is_manual || last_save.is_none_or(|at| last_seen >= at)
+}
+
+/// Converts the user's query into an FTS5 query where every word is a prefix.
+///
+/// Each word is quoted so that the characters of the FTS5 syntax lose their
+/// meaning. Returns `None` when the query has no word to search.
+fn match_query(query: &str) -> Option<String> {
+    let terms = query
+        .split_whitespace()
+        .filter(|term| term.chars().any(char::is_alphanumeric))
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_still_listed_ok() {
+        pretty_assertions::assert_eq!(is_still_listed(false, 2_000, Some(2_000)), true);
+        pretty_assertions::assert_eq!(is_still_listed(false, 1_000, Some(2_000)), false);
+        pretty_assertions::assert_eq!(is_still_listed(true, 1_000, Some(2_000)), true);
+        pretty_assertions::assert_eq!(is_still_listed(false, 1_000, None), true);
+    }
+
+    #[test]
+    fn test_match_query_prefixes_every_word_ok() {
+        let got = match_query("  Motörhead ace  ");
+
+        pretty_assertions::assert_eq!(got, Some(String::from("\"Motörhead\"* \"ace\"*")));
+    }
+
+    #[test]
+    fn test_match_query_escapes_syntax_ok() {
+        let got = match_query("AC/DC \"NEAR\" - OR");
+
+        pretty_assertions::assert_eq!(
+            got,
+            Some(String::from("\"AC/DC\"* \"\"\"NEAR\"\"\"* \"OR\"*"))
+        );
+    }
+
+    #[test]
+    fn test_match_query_empty_ok() {
+        pretty_assertions::assert_eq!(match_query(" - * "), None);
+    }
 }
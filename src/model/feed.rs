@@ -1,8 +1,27 @@
 use diesel::prelude::*;
 
-use super::{ModelManager, schema};
+use super::{Artist, ModelManager, Release, schema};
 use crate::error::Result;
 
+/// The syndication formats a feed can be cached in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+impl FeedFormat {
+    /// Returns the value stored in the `format` column of the `feeds` table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "rss",
+            FeedFormat::Atom => "atom",
+            FeedFormat::Json => "json",
+        }
+    }
+}
+
 /// Represents a row in the `feeds` table, providing access to
 /// the RSS feed data stored in the SQLite database.
 #[derive(Queryable, Identifiable, Selectable, Debug, PartialEq)]
@@ -12,8 +31,10 @@ pub struct Feed {
     pub id: i32,
     /// The date when the feed was published.
     pub date: i32,
-    /// The content of the RSS feed.
+    /// The content of the feed.
     pub feed: String,
+    /// The syndication format of the feed, e.g. `rss`, `atom` or `json`.
+    pub format: String,
 }
 
 #[derive(Insertable)]
@@ -23,6 +44,7 @@ struct FeedForInsert {
     pub date: i32,
     pub feed: String,
     pub custom_feed_id: i32,
+    pub format: String,
 }
 
 /// Represents a row in the `custom_feeds` table, providing access to
@@ -34,6 +56,100 @@ pub struct CustomFeed {
     pub id: i32,
     pub bands: String,
     pub genres: String,
+    pub countries: String,
+    pub labels: String,
+}
+
+impl CustomFeed {
+    /// Returns the bands the feed filters on.
+    pub fn bands_list(&self) -> Vec<String> {
+        split_filter(&self.bands)
+    }
+
+    /// Returns the genres the feed filters on.
+    pub fn genres_list(&self) -> Vec<String> {
+        split_filter(&self.genres)
+    }
+
+    /// Returns the countries of origin the feed filters on.
+    pub fn countries_list(&self) -> Vec<String> {
+        split_filter(&self.countries)
+    }
+
+    /// Returns the record labels the feed filters on.
+    pub fn labels_list(&self) -> Vec<String> {
+        split_filter(&self.labels)
+    }
+
+    /// Whether the artist's release belongs in this custom feed.
+    ///
+    /// A custom feed filters on bands, genres or both. When the genres are
+    /// `none`, only the bands are considered. When the bands are `none`, only
+    /// the genres are considered. Otherwise, an artist matching either is kept.
+    ///
+    /// The countries and labels, when set, further narrow the releases down to
+    /// the artists from one of the countries and the albums of one of the labels.
+    pub fn matches(&self, release: &Release, artist: &Artist) -> bool {
+        self.matches_artist(artist) && self.matches_country(artist) && self.matches_label(release)
+    }
+
+    fn matches_artist(&self, artist: &Artist) -> bool {
+        if self.bands.is_empty() && self.genres.is_empty() {
+            return true;
+        }
+
+        let is_band_in_want = self.bands.contains(&artist.name.to_lowercase());
+
+        if self.genres == "none" {
+            return is_band_in_want;
+        }
+
+        let is_genre_in_want = artist.genre.as_ref().is_some_and(|genre| {
+            contains_any_keywords(&genre.to_lowercase().replace(" metal", ""), &self.genres)
+        });
+
+        if self.bands == "none" {
+            is_genre_in_want
+        } else {
+            is_genre_in_want || is_band_in_want
+        }
+    }
+
+    fn matches_country(&self, artist: &Artist) -> bool {
+        self.countries.is_empty()
+            || artist
+                .country
+                .as_ref()
+                .is_some_and(|country| self.countries_list().contains(&country.to_lowercase()))
+    }
+
+    fn matches_label(&self, release: &Release) -> bool {
+        self.labels.is_empty()
+            || release.label.as_ref().is_some_and(|label| {
+                let label = label.to_lowercase();
+                self.labels_list().iter().any(|want| label.contains(want))
+            })
+    }
+}
+
+fn split_filter(filter: &str) -> Vec<String> {
+    filter
+        .split('@')
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn contains_any_keywords(genre: &str, keywords: &str) -> bool {
+    let normalized_genre = genre.to_lowercase();
+    let genre_words: Vec<&str> = normalized_genre
+        .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+        .collect();
+
+    keywords
+        .to_lowercase()
+        .split('@')
+        .any(|keyword| genre_words.iter().any(|&word| word.contains(keyword)))
 }
 
 #[derive(Insertable)]
@@ -41,6 +157,8 @@ pub struct CustomFeed {
 struct CustomFeedForInsert {
     pub bands: String,
     pub genres: String,
+    pub countries: String,
+    pub labels: String,
 }
 
 /// A trait defining the interface for querying a entities of heavy metal releases.
@@ -52,29 +170,48 @@ pub trait FeedRepository {
     ///
     /// This method accepts a `FeedForCreate` object and inserts it into the `feeds` table.
     /// The insert operation is ignored if a record with the same data already exists.
-    fn create(&self, date_c: i32, feed_c: &str, custom_feed: i32) -> Result<()>;
+    fn create(
+        &self,
+        date_c: i32,
+        feed_c: &str,
+        custom_feed: i32,
+        format_c: FeedFormat,
+    ) -> Result<()>;
 
-    /// Retrieves the most recent feed records from the database.
+    /// Retrieves the most recent feed records of the given format from the database.
     ///
     /// This method fetches a limited number of feed records from the
     /// `feeds` table, ordered by date in descending order.
-    fn get(&self, num: i64, custom_feed: i32) -> Result<Vec<Feed>>;
+    fn get(&self, num: i64, custom_feed: i32, format_c: FeedFormat) -> Result<Vec<Feed>>;
+
+    /// Deletes the feed records cached for the given date, in every format and for
+    /// every custom feed, so that they are generated again.
+    ///
+    /// Returns the number of deleted records.
+    fn delete_on(&self, date_c: i32) -> Result<usize>;
 
     /// Retrieves a `CustomFeed` by its ID.
     fn get_custom_feed(&self, custom_feed_id: i32) -> Result<CustomFeed>;
 
-    /// Retrieves or creates a custom feed based on the specified bands and genres.
+    /// Retrieves every user-defined `CustomFeed`, ordered by ID.
+    fn custom_feeds(&self) -> Result<Vec<CustomFeed>>;
+
+    /// Retrieves or creates a custom feed based on the specified bands, genres, countries and labels.
     ///
     /// This function first normalizes the input bands and genres vectors:
     /// - If `bands_vec` contains "All", it is cleared (no specific bands filter).
     /// - If `bands_vec` contains "None", it is replaced with a single "none" entry.
     /// - If `genres_vec` contains "All", it is cleared (no specific genres filter).
     /// - If `genres_vec` contains "None", it is replaced with a single "none" entry.
+    /// - If `countries_vec` or `labels_vec` contains "All", it is cleared.
     ///
+    /// Returns `None` when the feed would not filter anything.
     fn get_or_create_custom_feed(
         &self,
         bands_vec: Vec<String>,
         genres_vec: Vec<String>,
+        countries_vec: Vec<String>,
+        labels_vec: Vec<String>,
     ) -> Option<i32>;
 }
 
@@ -85,7 +222,13 @@ pub trait FeedRepository {
 pub struct FeedBmc;
 
 impl FeedRepository for FeedBmc {
-    fn create(&self, date_c: i32, feed_c: &str, custom_feed: i32) -> Result<()> {
+    fn create(
+        &self,
+        date_c: i32,
+        feed_c: &str,
+        custom_feed: i32,
+        format_c: FeedFormat,
+    ) -> Result<()> {
         use schema::feeds::dsl::*;
 
         diesel::insert_or_ignore_into(feeds)
@@ -93,17 +236,22 @@ impl FeedRepository for FeedBmc {
                 date: date_c,
                 feed: feed_c.into(),
                 custom_feed_id: custom_feed,
+                format: format_c.as_str().to_string(),
             })
             .execute(&mut ModelManager::new().conn)?;
 
         Ok(())
     }
 
-    fn get(&self, num: i64, custom_feed: i32) -> Result<Vec<Feed>> {
+    fn get(&self, num: i64, custom_feed: i32, format_c: FeedFormat) -> Result<Vec<Feed>> {
         use schema::feeds::dsl::*;
 
         let results = feeds
-            .filter(custom_feed_id.eq(custom_feed))
+            .filter(
+                custom_feed_id
+                    .eq(custom_feed)
+                    .and(format.eq(format_c.as_str())),
+            )
             .order(date.desc())
             .limit(num)
             .select(Feed::as_select())
@@ -112,6 +260,15 @@ impl FeedRepository for FeedBmc {
         Ok(results)
     }
 
+    fn delete_on(&self, date_c: i32) -> Result<usize> {
+        use schema::feeds::dsl::*;
+
+        let num_deleted =
+            diesel::delete(feeds.filter(date.eq(date_c))).execute(&mut ModelManager::new().conn)?;
+
+        Ok(num_deleted)
+    }
+
     fn get_custom_feed(&self, custom_feed_id: i32) -> Result<CustomFeed> {
         use schema::custom_feeds::dsl::*;
 
@@ -122,10 +279,24 @@ impl FeedRepository for FeedBmc {
         Ok(feed)
     }
 
+    fn custom_feeds(&self) -> Result<Vec<CustomFeed>> {
+        use schema::custom_feeds::dsl::*;
+
+        let results = custom_feeds
+            .filter(id.gt(-1))
+            .order(id.asc())
+            .select(CustomFeed::as_select())
+            .load(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+
     fn get_or_create_custom_feed(
         &self,
         mut bands_vec: Vec<String>,
         mut genres_vec: Vec<String>,
+        mut countries_vec: Vec<String>,
+        mut labels_vec: Vec<String>,
     ) -> Option<i32> {
         use schema::custom_feeds::dsl::*;
 
@@ -147,17 +318,37 @@ impl FeedRepository for FeedBmc {
                 .collect(),
         };
 
-        if bands_vec.is_empty() && genres_vec.is_empty() {
+        if countries_vec.contains(&all) {
+            countries_vec.clear();
+        }
+
+        if labels_vec.contains(&all) {
+            labels_vec.clear();
+        }
+
+        if bands_vec.is_empty()
+            && genres_vec.is_empty()
+            && countries_vec.is_empty()
+            && labels_vec.is_empty()
+        {
             return None;
         }
 
         let bands_all = bands_vec.join("@").to_lowercase();
         let genres_all = genres_vec.join("@").to_lowercase();
+        let countries_all = countries_vec.join("@").to_lowercase();
+        let labels_all = labels_vec.join("@").to_lowercase();
 
         let conn = &mut ModelManager::new().conn;
 
         custom_feeds
-            .filter(bands.eq(&bands_all).and(genres.eq(&genres_all)))
+            .filter(
+                bands
+                    .eq(&bands_all)
+                    .and(genres.eq(&genres_all))
+                    .and(countries.eq(&countries_all))
+                    .and(labels.eq(&labels_all)),
+            )
             .select(id)
             .first::<i32>(conn)
             .optional()
@@ -167,6 +358,8 @@ impl FeedRepository for FeedBmc {
                     .values(&CustomFeedForInsert {
                         bands: bands_all,
                         genres: genres_all,
+                        countries: countries_all,
+                        labels: labels_all,
                     })
                     .returning(id)
                     .get_result::<i32>(conn)
@@ -174,3 +367,124 @@ impl FeedRepository for FeedBmc {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::fixtures;
+
+    #[test]
+    fn test_custom_feed_matches_bands_only_ok() {
+        let feed = a_custom_feed("wintersun@norther", "none");
+
+        assert!(feed.matches(
+            &a_release(None),
+            &an_artist("Wintersun", Some("Melodic Death Metal"))
+        ));
+        assert!(!feed.matches(
+            &a_release(None),
+            &an_artist("Amorphis", Some("Melodic Death Metal"))
+        ));
+    }
+
+    #[test]
+    fn test_custom_feed_matches_genres_only_ok() {
+        let feed = a_custom_feed("none", "death@doom");
+
+        assert!(feed.matches(
+            &a_release(None),
+            &an_artist("Amorphis", Some("Melodic Death Metal"))
+        ));
+        assert!(feed.matches(
+            &a_release(None),
+            &an_artist("Candlemass", Some("Epic Doom Metal"))
+        ));
+        assert!(!feed.matches(
+            &a_release(None),
+            &an_artist("Darkthrone", Some("Black Metal"))
+        ));
+        assert!(!feed.matches(&a_release(None), &an_artist("Unknown", None)));
+    }
+
+    #[test]
+    fn test_custom_feed_matches_bands_or_genres_ok() {
+        let feed = a_custom_feed("darkthrone", "doom");
+
+        assert!(feed.matches(
+            &a_release(None),
+            &an_artist("Darkthrone", Some("Black Metal"))
+        ));
+        assert!(feed.matches(
+            &a_release(None),
+            &an_artist("Candlemass", Some("Epic Doom Metal"))
+        ));
+        assert!(!feed.matches(
+            &a_release(None),
+            &an_artist("Amorphis", Some("Melodic Death Metal"))
+        ));
+    }
+
+    #[test]
+    fn test_custom_feed_matches_countries_ok() {
+        let feed = CustomFeed {
+            countries: String::from("finland@sweden"),
+            ..a_custom_feed("", "")
+        };
+
+        let finnish = Artist {
+            country: Some(String::from("Finland")),
+            ..an_artist("Wintersun", Some("Melodic Death Metal"))
+        };
+        let norwegian = Artist {
+            country: Some(String::from("Norway")),
+            ..an_artist("Darkthrone", Some("Black Metal"))
+        };
+
+        assert!(feed.matches(&a_release(None), &finnish));
+        assert!(!feed.matches(&a_release(None), &norwegian));
+        assert!(!feed.matches(&a_release(None), &an_artist("Unknown", None)));
+    }
+
+    #[test]
+    fn test_custom_feed_matches_genres_and_labels_ok() {
+        let feed = CustomFeed {
+            labels: String::from("nuclear blast"),
+            ..a_custom_feed("none", "death")
+        };
+        let artist = an_artist("Wintersun", Some("Melodic Death Metal"));
+
+        assert!(feed.matches(&a_release(Some("Nuclear Blast Records")), &artist));
+        assert!(!feed.matches(&a_release(Some("Century Media")), &artist));
+        assert!(!feed.matches(&a_release(None), &artist));
+        assert!(!feed.matches(
+            &a_release(Some("Nuclear Blast")),
+            &an_artist("Darkthrone", Some("Black Metal"))
+        ));
+    }
+
+    fn a_custom_feed(bands: &str, genres: &str) -> CustomFeed {
+        CustomFeed {
+            id: 1,
+            bands: bands.to_string(),
+            genres: genres.to_string(),
+            countries: String::new(),
+            labels: String::new(),
+        }
+    }
+
+    fn a_release(label: Option<&str>) -> Release {
+        Release {
+            url_youtube: String::new(),
+            label: label.map(String::from),
+            ..fixtures::a_release()
+        }
+    }
+
+    fn an_artist(name: &str, genre: Option<&str>) -> Artist {
+        Artist {
+            name: name.to_string(),
+            genre: genre.map(String::from),
+            ..fixtures::an_artist()
+        }
+    }
+}
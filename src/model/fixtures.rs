@@ -0,0 +1,49 @@
+//! The releases and artists the tests start from, overriding only the fields they check.
+
+use super::{Artist, Release};
+
+/// Returns `Time II` of the artist with ID 1, released on 2024-08-30, without any
+/// optional detail.
+pub fn a_release() -> Release {
+    Release {
+        id: 1,
+        year: 2024,
+        month: 8,
+        day: 30,
+        artist_id: 1,
+        album: String::from("Time II"),
+        release_type: None,
+        url_youtube: String::from("https://www.youtube.com"),
+        url_metallum: None,
+        first_seen: 0,
+        last_seen: 0,
+        is_removed: false,
+        moved_from: None,
+        musicbrainz_id: None,
+        label: None,
+        metallum_checked_at: None,
+        cover: None,
+        cover_checked_at: None,
+        cover_size: None,
+        is_hidden: false,
+        merged_into: None,
+        is_manual: false,
+    }
+}
+
+/// Returns Wintersun with the ID 1, without any optional detail.
+pub fn an_artist() -> Artist {
+    Artist {
+        id: 1,
+        name: String::from("Wintersun"),
+        genre: None,
+        url_bandcamp: None,
+        url_metallum: None,
+        musicbrainz_id: None,
+        bandcamp_confidence: None,
+        bandcamp_checked_at: None,
+        bandcamp_locked: false,
+        country: None,
+        metallum_checked_at: None,
+    }
+}
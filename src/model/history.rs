@@ -0,0 +1,162 @@
+use diesel::prelude::*;
+use serde::Serialize;
+use time::Date;
+
+use super::{Artist, Release};
+
+/// Represents a row in the `release_history` table, i.e. a change of the
+/// date of a release detected while updating the calendar.
+///
+/// The dates are stored as `YYYYMMDD` integers.
+#[derive(Queryable, Identifiable, Selectable, Associations, Debug, PartialEq, Serialize)]
+#[diesel(belongs_to(Release))]
+#[diesel(table_name = super::schema::release_history)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ReleaseChange {
+    pub id: i32,
+    pub release_id: i32,
+    pub old_date: i32,
+    pub new_date: i32,
+    /// The Unix timestamp of the calendar update that detected the change.
+    pub changed_at: i64,
+}
+
+impl ReleaseChange {
+    /// Returns the date the release was expected on before the change.
+    pub fn previous(&self) -> Date {
+        int_to_date(self.old_date).unwrap_or(Date::MIN)
+    }
+
+    /// Returns the date the release is now expected on.
+    pub fn current(&self) -> Date {
+        int_to_date(self.new_date).unwrap_or(Date::MIN)
+    }
+
+    /// Whether the release was pushed back rather than brought forward.
+    pub fn is_postponed(&self) -> bool {
+        self.new_date > self.old_date
+    }
+
+    /// Returns a title announcing the change, e.g. `Postponed: Wintersun – Time II`.
+    pub fn title(&self, release: &Release, artist: &Artist) -> String {
+        format!(
+            "{}: {} – {}",
+            if self.is_postponed() {
+                "Postponed"
+            } else {
+                "Moved"
+            },
+            artist.name,
+            release.album
+        )
+    }
+
+    /// Returns an identifier of the change that stays the same across feed builds.
+    pub fn uid(&self, release: &Release) -> String {
+        format!("{}-moved-{}", release.uid(), self.new_date)
+    }
+
+    /// Converts the change into an HTML `<li>` element describing the old and new dates.
+    pub fn to_html(&self, release: &Release, artist: &Artist) -> String {
+        format!(
+            "<li style=\"margin-bottom: 1rem\"><b>{} - {}</b> was {} from {} to {}.</li>",
+            artist.name,
+            release.album,
+            if self.is_postponed() {
+                "postponed"
+            } else {
+                "moved"
+            },
+            human_date(self.previous()),
+            human_date(self.current())
+        )
+    }
+}
+
+/// Represents a new change of a release date to be inserted into the database.
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::release_history)]
+pub(in crate::model) struct ReleaseChangeForInsert {
+    pub release_id: i32,
+    pub old_date: i32,
+    pub new_date: i32,
+    pub changed_at: i64,
+}
+
+/// Converts a `YYYYMMDD` integer into a date.
+pub(in crate::model) fn int_to_date(value: i32) -> Option<Date> {
+    let month = time::Month::try_from(((value / 100) % 100) as u8).ok()?;
+    Date::from_calendar_date(value / 10000, month, (value % 100) as u8).ok()
+}
+
+/// Formats a date for people, e.g. `August 30, 2024`.
+pub(in crate::model) fn human_date(date: Date) -> String {
+    format!("{} {}, {}", date.month(), date.day(), date.year())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::fixtures;
+
+    #[test]
+    fn test_release_change_postponed_ok() {
+        let (release, artist) = a_release();
+        let change = ReleaseChange {
+            id: 1,
+            release_id: 1,
+            old_date: 20240830,
+            new_date: 20241004,
+            changed_at: 0,
+        };
+
+        pretty_assertions::assert_eq!(
+            change.title(&release, &artist),
+            "Postponed: Wintersun – Time II"
+        );
+        pretty_assertions::assert_eq!(change.uid(&release), "release-1-moved-20241004");
+        pretty_assertions::assert_eq!(
+            change.to_html(&release, &artist),
+            "<li style=\"margin-bottom: 1rem\"><b>Wintersun - Time II</b> was postponed from August 30, 2024 to October 4, 2024.</li>"
+        );
+    }
+
+    #[test]
+    fn test_release_change_moved_forward_ok() {
+        let (release, artist) = a_release();
+        let change = ReleaseChange {
+            id: 1,
+            release_id: 1,
+            old_date: 20241004,
+            new_date: 20240830,
+            changed_at: 0,
+        };
+
+        assert!(!change.is_postponed());
+        pretty_assertions::assert_eq!(
+            change.title(&release, &artist),
+            "Moved: Wintersun – Time II"
+        );
+    }
+
+    #[test]
+    fn test_int_to_date_ok() {
+        pretty_assertions::assert_eq!(
+            int_to_date(20240229),
+            Date::from_calendar_date(2024, time::Month::February, 29).ok()
+        );
+        pretty_assertions::assert_eq!(int_to_date(20241301), None);
+    }
+
+    fn a_release() -> (Release, Artist) {
+        (
+            Release {
+                month: 10,
+                day: 4,
+                moved_from: Some(20240830),
+                ..fixtures::a_release()
+            },
+            fixtures::an_artist(),
+        )
+    }
+}
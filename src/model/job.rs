@@ -0,0 +1,209 @@
+use diesel::prelude::*;
+use serde::Serialize;
+
+use super::{ModelManager, schema};
+use crate::error::Result;
+
+/// The outcome of a run of a scheduled job.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JobStatus {
+    Running,
+    Success,
+    Failure,
+    /// The application stopped before the run finished.
+    Interrupted,
+}
+
+impl JobStatus {
+    /// Returns the value stored in the `status` column of the `job_runs` table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Running => "running",
+            JobStatus::Success => "success",
+            JobStatus::Failure => "failure",
+            JobStatus::Interrupted => "interrupted",
+        }
+    }
+}
+
+/// Represents a row in the `job_runs` table, i.e. a run of a scheduled job.
+#[derive(Queryable, Identifiable, Selectable, Clone, Debug, PartialEq, Serialize)]
+#[diesel(table_name = super::schema::job_runs)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct JobRun {
+    pub id: i32,
+    /// The name of the job, e.g. `calendar`.
+    pub name: String,
+    /// The Unix timestamp of the start of the run.
+    pub started_at: i64,
+    /// The Unix timestamp of the end of the run, unless it is still running.
+    pub finished_at: Option<i64>,
+    /// The outcome of the run, e.g. `success`.
+    pub status: String,
+    /// The error the run failed with.
+    pub error: Option<String>,
+    /// The number of items the run created or updated, e.g. releases or feeds.
+    pub items_changed: Option<i32>,
+}
+
+impl JobRun {
+    /// Returns how long the run took once it finished, e.g. `3m 07s`.
+    pub fn duration_text(&self) -> Option<String> {
+        self.finished_at.map(|at| {
+            let seconds = at - self.started_at;
+            match (seconds / 3600, seconds % 3600 / 60, seconds % 60) {
+                (0, 0, seconds) => format!("{seconds}s"),
+                (0, minutes, seconds) => format!("{minutes}m {seconds:02}s"),
+                (hours, minutes, _) => format!("{hours}h {minutes:02}m"),
+            }
+        })
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::job_runs)]
+struct JobRunForInsert<'a> {
+    name: &'a str,
+    started_at: i64,
+    status: &'static str,
+}
+
+/// A trait defining the interface for recording the runs of the scheduled jobs.
+///
+/// It can be implemented by any backend service or repository pattern to support
+// different data storage and retrieval strategies.
+pub trait JobRunRepository {
+    /// Records the start of a run of the job and returns the ID of the run.
+    fn start(&self, name_c: &str, at: i64) -> Result<i32>;
+
+    /// Records the successful end of the run along with the number of items it changed.
+    fn record_success(&self, run_id: i32, at: i64, items: i32) -> Result<()>;
+
+    /// Records the failed end of the run along with its error.
+    fn record_failure(&self, run_id: i32, at: i64, error_c: &str) -> Result<()>;
+
+    /// Marks the runs left running as interrupted, e.g. after the application restarted.
+    ///
+    /// Returns the number of runs that were interrupted.
+    fn interrupt_running(&self, at: i64) -> Result<usize>;
+
+    /// Retrieves the most recent runs of every job, latest first.
+    fn recent(&self, num: i64) -> Result<Vec<JobRun>>;
+
+    /// Deletes the runs that started before the given Unix timestamp.
+    ///
+    /// Returns the number of deleted runs.
+    fn delete_before(&self, at: i64) -> Result<usize>;
+}
+
+/// `JobRunBmc` is a backend model controller responsible for
+/// recording the runs of the scheduled jobs.
+pub struct JobRunBmc;
+
+impl JobRunRepository for JobRunBmc {
+    fn start(&self, name_c: &str, at: i64) -> Result<i32> {
+        use schema::job_runs::dsl::*;
+
+        let run_id = diesel::insert_into(job_runs)
+            .values(&JobRunForInsert {
+                name: name_c,
+                started_at: at,
+                status: JobStatus::Running.as_str(),
+            })
+            .returning(id)
+            .get_result(&mut ModelManager::new().conn)?;
+
+        Ok(run_id)
+    }
+
+    fn record_success(&self, run_id: i32, at: i64, items: i32) -> Result<()> {
+        use schema::job_runs::dsl::*;
+
+        diesel::update(job_runs.find(run_id))
+            .set((
+                finished_at.eq(Some(at)),
+                status.eq(JobStatus::Success.as_str()),
+                items_changed.eq(Some(items)),
+            ))
+            .execute(&mut ModelManager::new().conn)?;
+
+        Ok(())
+    }
+
+    fn record_failure(&self, run_id: i32, at: i64, error_c: &str) -> Result<()> {
+        use schema::job_runs::dsl::*;
+
+        diesel::update(job_runs.find(run_id))
+            .set((
+                finished_at.eq(Some(at)),
+                status.eq(JobStatus::Failure.as_str()),
+                error.eq(Some(error_c)),
+            ))
+            .execute(&mut ModelManager::new().conn)?;
+
+        Ok(())
+    }
+
+    fn interrupt_running(&self, at: i64) -> Result<usize> {
+        use schema::job_runs::dsl::*;
+
+        let num_updated = diesel::update(job_runs.filter(status.eq(JobStatus::Running.as_str())))
+            .set((
+                finished_at.eq(Some(at)),
+                status.eq(JobStatus::Interrupted.as_str()),
+            ))
+            .execute(&mut ModelManager::new().conn)?;
+
+        Ok(num_updated)
+    }
+
+    fn recent(&self, num: i64) -> Result<Vec<JobRun>> {
+        use schema::job_runs::dsl::*;
+
+        let results = job_runs
+            .order((started_at.desc(), id.desc()))
+            .limit(num)
+            .select(JobRun::as_select())
+            .load(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+
+    fn delete_before(&self, at: i64) -> Result<usize> {
+        use schema::job_runs::dsl::*;
+
+        let num_deleted = diesel::delete(job_runs.filter(started_at.lt(at)))
+            .execute(&mut ModelManager::new().conn)?;
+
+        Ok(num_deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_run_duration_text_ok() {
+        let run = |finished_at| JobRun {
+            id: 1,
+            name: String::from("calendar"),
+            started_at: 1_000,
+            finished_at,
+            status: String::from("success"),
+            error: None,
+            items_changed: Some(3),
+        };
+
+        pretty_assertions::assert_eq!(run(None).duration_text(), None);
+        pretty_assertions::assert_eq!(run(Some(1_042)).duration_text(), Some(String::from("42s")));
+        pretty_assertions::assert_eq!(
+            run(Some(1_187)).duration_text(),
+            Some(String::from("3m 07s"))
+        );
+        pretty_assertions::assert_eq!(
+            run(Some(8_500)).duration_text(),
+            Some(String::from("2h 05m"))
+        );
+    }
+}
@@ -3,13 +3,32 @@
 mod calendar;
 mod entities;
 mod feed;
+#[cfg(test)]
+pub(crate) mod fixtures;
+mod history;
+mod job;
+mod overrides;
+mod source;
 mod store;
+mod subscriber;
+mod tracklist;
+mod webhook;
 
 pub(in crate::model) mod schema;
 
 pub use calendar::{Artist, CalendarBmc, CalendarRepository, Release};
-pub use entities::{EntitiesBmc, EntitiesRepository};
-pub use feed::{Feed, FeedBmc, FeedRepository};
+pub use entities::{ArtistEdit, EntitiesBmc, EntitiesRepository, NewRelease};
+pub use feed::{CustomFeed, Feed, FeedBmc, FeedFormat, FeedRepository};
+pub use history::ReleaseChange;
+pub use job::{JobRun, JobRunBmc, JobRunRepository, JobStatus};
+pub use overrides::{
+    Override, OverrideBmc, OverrideEntry, OverrideField, OverrideRepository, OverridesFile,
+};
+pub use source::{SourceHealth, SourceHealthBmc, SourceHealthRepository};
+pub use store::{optimize_database, run_migrations};
+pub use subscriber::{Frequency, Subscriber, SubscriberBmc, SubscriberRepository};
+pub use tracklist::{ReleaseDetails, Track};
+pub use webhook::{Webhook, WebhookBmc, WebhookKind, WebhookRepository};
 
 use diesel::prelude::*;
 use store::establish_connection;
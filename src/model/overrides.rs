@@ -0,0 +1,351 @@
+use std::str::FromStr;
+
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ModelManager, schema};
+use crate::{
+    date_now,
+    error::{Error, Result},
+    parse_date,
+    support::fuzzy,
+};
+
+/// The field of the scraped releases an override corrects, or their suppression.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OverrideField {
+    /// Leaves the releases out of the calendar. It takes no value.
+    Suppress,
+    Artist,
+    /// Renames the album. It requires the album to be given.
+    Album,
+    /// Moves the release to the date written as `YYYY-MM-DD`, within the same year.
+    Date,
+    ReleaseType,
+    /// Sets the genre of the artist.
+    Genre,
+    Label,
+}
+
+impl OverrideField {
+    /// Returns the value stored in the `field` column of the `overrides` table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverrideField::Suppress => "suppress",
+            OverrideField::Artist => "artist",
+            OverrideField::Album => "album",
+            OverrideField::Date => "date",
+            OverrideField::ReleaseType => "release_type",
+            OverrideField::Genre => "genre",
+            OverrideField::Label => "label",
+        }
+    }
+}
+
+impl FromStr for OverrideField {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "suppress" => Ok(OverrideField::Suppress),
+            "artist" => Ok(OverrideField::Artist),
+            "album" => Ok(OverrideField::Album),
+            "date" => Ok(OverrideField::Date),
+            "release_type" => Ok(OverrideField::ReleaseType),
+            "genre" => Ok(OverrideField::Genre),
+            "label" => Ok(OverrideField::Label),
+            _ => Err(Error::InvalidParam("field")),
+        }
+    }
+}
+
+/// Represents a row in the `overrides` table, i.e. a manual correction applied to the
+/// scraped releases of an artist, or of one of their albums, before they are saved.
+///
+/// The artist and the album are matched by their normalized names, so that the
+/// override still applies when a source changes their case or punctuation.
+#[derive(Queryable, Identifiable, Selectable, Clone, Debug, PartialEq)]
+#[diesel(table_name = super::schema::overrides)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Override {
+    pub id: i32,
+    pub artist: String,
+    /// The album, or an empty string for every album of the artist.
+    pub album: String,
+    pub artist_key: String,
+    pub album_key: String,
+    /// The corrected field, e.g. `date`, or `suppress`.
+    pub field: String,
+    pub value: Option<String>,
+    /// Why the correction was made.
+    pub note: Option<String>,
+    /// The Unix timestamp of the creation of the override.
+    pub created_at: i64,
+}
+
+impl Override {
+    /// Whether the override applies to the release with the given normalized artist and album.
+    pub fn matches(&self, artist_key: &str, album_key: &str) -> bool {
+        self.artist_key == artist_key && (self.album_key.is_empty() || self.album_key == album_key)
+    }
+}
+
+/// An override as written in an overrides file, which the `overrides export` command
+/// writes and the `overrides import` command reads, e.g.
+/// `{"overrides":[{"artist":"Wintersun","album":"Time II","field":"date","value":"2024-08-30"}]}`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OverrideEntry {
+    pub artist: String,
+    /// The album, or none for every album of the artist.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub album: String,
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl OverrideEntry {
+    /// Checks that the entry names an artist and a known field along with a fitting value.
+    ///
+    /// # Errors
+    ///
+    /// This function returns `Error::InvalidParam` naming the faulty part of the entry.
+    pub fn validate(&self) -> Result<OverrideField> {
+        if fuzzy::artist_key(&self.artist).is_empty() {
+            return Err(Error::InvalidParam("artist"));
+        }
+
+        let field = self.field.parse::<OverrideField>()?;
+        let value = self.value.as_deref().map(str::trim).unwrap_or_default();
+
+        match field {
+            OverrideField::Suppress if !value.is_empty() => Err(Error::InvalidParam("value")),
+            OverrideField::Album if fuzzy::normalize(&self.album).is_empty() => {
+                Err(Error::InvalidParam("album"))
+            }
+            OverrideField::Date if parse_date(value).is_none() => Err(Error::InvalidParam("value")),
+            OverrideField::Suppress | OverrideField::Date => Ok(field),
+            _ if value.is_empty() => Err(Error::InvalidParam("value")),
+            _ => Ok(field),
+        }
+    }
+}
+
+impl From<Override> for OverrideEntry {
+    fn from(value: Override) -> Self {
+        Self {
+            artist: value.artist,
+            album: value.album,
+            field: value.field,
+            value: value.value,
+            note: value.note,
+        }
+    }
+}
+
+/// The JSON representation of the overrides read and written by the `overrides` command.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OverridesFile {
+    pub overrides: Vec<OverrideEntry>,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = super::schema::overrides)]
+struct OverrideForInsert<'a> {
+    artist: &'a str,
+    album: &'a str,
+    artist_key: String,
+    album_key: String,
+    field: &'static str,
+    value: Option<&'a str>,
+    note: Option<&'a str>,
+    created_at: i64,
+}
+
+/// A trait defining the interface for managing the manual overrides of the scraped releases.
+///
+/// It can be implemented by any backend service or repository pattern to support
+// different data storage and retrieval strategies.
+pub trait OverrideRepository {
+    /// Saves the override once validated, replacing the one of the same field for the
+    /// same artist and album.
+    fn set(&self, entry: &OverrideEntry) -> Result<Override>;
+
+    /// Saves the overrides of a file at once, or none of them if one is invalid.
+    ///
+    /// Returns the number of saved overrides.
+    fn import(&self, entries: &[OverrideEntry]) -> Result<usize>;
+
+    /// Retrieves every override, ordered by artist, album and field.
+    fn all(&self) -> Result<Vec<Override>>;
+
+    /// Deletes the override.
+    fn delete(&self, override_id: i32) -> Result<()>;
+}
+
+/// `OverrideBmc` is a backend model controller responsible for
+/// managing the manual overrides.
+pub struct OverrideBmc;
+
+impl OverrideRepository for OverrideBmc {
+    fn set(&self, entry: &OverrideEntry) -> Result<Override> {
+        save(&mut ModelManager::new().conn, entry)
+    }
+
+    fn import(&self, entries: &[OverrideEntry]) -> Result<usize> {
+        let conn = &mut ModelManager::new().conn;
+        conn.transaction::<_, Error, _>(|conn| {
+            for entry in entries {
+                save(conn, entry)?;
+            }
+            Ok(entries.len())
+        })
+    }
+
+    fn all(&self) -> Result<Vec<Override>> {
+        use schema::overrides::dsl::*;
+
+        let results = overrides
+            .order((artist_key.asc(), album_key.asc(), field.asc()))
+            .select(Override::as_select())
+            .load(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+
+    fn delete(&self, override_id: i32) -> Result<()> {
+        use schema::overrides::dsl::*;
+
+        let num_deleted =
+            diesel::delete(overrides.find(override_id)).execute(&mut ModelManager::new().conn)?;
+        if num_deleted == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "override",
+                id: override_id as i64,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Validates the entry and inserts it, or replaces the override it corrects again.
+fn save(conn: &mut SqliteConnection, entry: &OverrideEntry) -> Result<Override> {
+    use schema::overrides::dsl::*;
+
+    let kind = entry.validate()?;
+    let row = OverrideForInsert {
+        artist: entry.artist.trim(),
+        album: entry.album.trim(),
+        artist_key: fuzzy::artist_key(&entry.artist),
+        album_key: fuzzy::normalize(&entry.album),
+        field: kind.as_str(),
+        value: entry
+            .value
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty()),
+        note: entry
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty()),
+        created_at: date_now().unix_timestamp(),
+    };
+
+    let saved = diesel::insert_into(overrides)
+        .values(&row)
+        .on_conflict((artist_key, album_key, field))
+        .do_update()
+        .set(&row)
+        .returning(Override::as_returning())
+        .get_result(conn)?;
+
+    Ok(saved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(album: &str, field: &str, value: Option<&str>) -> OverrideEntry {
+        OverrideEntry {
+            artist: String::from("Wintersun"),
+            album: album.to_string(),
+            field: field.to_string(),
+            value: value.map(String::from),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_override_entry_validate_ok() {
+        let cases = [
+            (entry("", "suppress", None), OverrideField::Suppress),
+            (
+                entry("Time II", "album", Some("Time 2")),
+                OverrideField::Album,
+            ),
+            (
+                entry("Time II", "date", Some("2024-08-30")),
+                OverrideField::Date,
+            ),
+            (
+                entry("", "genre", Some("Symphonic Metal")),
+                OverrideField::Genre,
+            ),
+        ];
+
+        for (entry, want) in cases {
+            pretty_assertions::assert_eq!(entry.validate().ok(), Some(want));
+        }
+    }
+
+    #[test]
+    fn test_override_entry_validate_fails() {
+        let cases = [
+            (entry("", "colour", Some("red")), "field"),
+            (entry("", "suppress", Some("yes")), "value"),
+            (entry("", "album", Some("Time 2")), "album"),
+            (entry("Time II", "album", None), "value"),
+            (entry("Time II", "date", Some("2024-02-30")), "value"),
+            (entry("", "label", Some("  ")), "value"),
+            (
+                OverrideEntry {
+                    artist: String::from(" ! "),
+                    ..entry("", "suppress", None)
+                },
+                "artist",
+            ),
+        ];
+
+        for (entry, want) in cases {
+            match entry.validate() {
+                Err(Error::InvalidParam(got)) => pretty_assertions::assert_eq!(got, want),
+                got => panic!("expected an invalid {want}, got {got:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_override_matches_ok() {
+        let an_override = |album_key: &str| Override {
+            id: 1,
+            artist: String::from("Wintersun"),
+            album: String::new(),
+            artist_key: String::from("wintersun"),
+            album_key: album_key.to_string(),
+            field: String::from("suppress"),
+            value: None,
+            note: None,
+            created_at: 0,
+        };
+
+        assert!(an_override("").matches("wintersun", "time ii"));
+        assert!(an_override("time ii").matches("wintersun", "time ii"));
+        assert!(!an_override("time ii").matches("wintersun", "time i"));
+        assert!(!an_override("").matches("ensiferum", "time ii"));
+    }
+}
@@ -7,6 +7,12 @@ diesel::table! {
         genre -> Nullable<Text>,
         url_bandcamp -> Nullable<Text>,
         url_metallum -> Nullable<Text>,
+        musicbrainz_id -> Nullable<Text>,
+        bandcamp_confidence -> Nullable<Float>,
+        bandcamp_checked_at -> Nullable<BigInt>,
+        bandcamp_locked -> Bool,
+        country -> Nullable<Text>,
+        metallum_checked_at -> Nullable<BigInt>,
     }
 }
 
@@ -15,6 +21,8 @@ diesel::table! {
         id -> Integer,
         bands -> Text,
         genres -> Text,
+        countries -> Text,
+        labels -> Text,
     }
 }
 
@@ -24,6 +32,33 @@ diesel::table! {
         date -> Integer,
         feed -> Text,
         custom_feed_id -> Integer,
+        format -> Text,
+    }
+}
+
+diesel::table! {
+    job_runs (id) {
+        id -> Integer,
+        name -> Text,
+        started_at -> BigInt,
+        finished_at -> Nullable<BigInt>,
+        status -> Text,
+        error -> Nullable<Text>,
+        items_changed -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    overrides (id) {
+        id -> Integer,
+        artist -> Text,
+        album -> Text,
+        artist_key -> Text,
+        album_key -> Text,
+        field -> Text,
+        value -> Nullable<Text>,
+        note -> Nullable<Text>,
+        created_at -> BigInt,
     }
 }
 
@@ -38,10 +73,108 @@ diesel::table! {
         release_type -> Nullable<Text>,
         url_youtube -> Text,
         url_metallum -> Nullable<Text>,
+        first_seen -> BigInt,
+        last_seen -> BigInt,
+        is_removed -> Bool,
+        moved_from -> Nullable<Integer>,
+        musicbrainz_id -> Nullable<Text>,
+        label -> Nullable<Text>,
+        metallum_checked_at -> Nullable<BigInt>,
+        cover -> Nullable<Text>,
+        cover_checked_at -> Nullable<BigInt>,
+        is_hidden -> Bool,
+        merged_into -> Nullable<Integer>,
+        is_manual -> Bool,
+        cover_size -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    release_details (release_id) {
+        release_id -> Integer,
+        format -> Nullable<Text>,
+        label -> Nullable<Text>,
+        runtime -> Nullable<Integer>,
+        checked_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    release_history (id) {
+        id -> Integer,
+        release_id -> Integer,
+        old_date -> Integer,
+        new_date -> Integer,
+        changed_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    source_health (name) {
+        name -> Text,
+        last_run_at -> BigInt,
+        last_success_at -> Nullable<BigInt>,
+        last_error -> Nullable<Text>,
+        last_num_releases -> Integer,
+        consecutive_failures -> Integer,
+    }
+}
+
+diesel::table! {
+    subscribers (id) {
+        id -> Integer,
+        email -> Text,
+        custom_feed_id -> Integer,
+        frequency -> Text,
+        is_confirmed -> Bool,
+        created_at -> BigInt,
+        last_sent_at -> Nullable<BigInt>,
+        pending_frequency -> Nullable<Text>,
+        confirmation_sent_at -> Nullable<BigInt>,
+    }
+}
+
+diesel::table! {
+    tracks (id) {
+        id -> Integer,
+        release_id -> Integer,
+        disc -> Integer,
+        position -> Integer,
+        title -> Text,
+        duration -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    webhooks (id) {
+        id -> Integer,
+        url -> Text,
+        kind -> Text,
+        custom_feed_id -> Integer,
+        created_at -> BigInt,
+        last_sent_at -> Nullable<BigInt>,
     }
 }
 
 diesel::joinable!(feeds -> custom_feeds (custom_feed_id));
+diesel::joinable!(release_details -> releases (release_id));
+diesel::joinable!(release_history -> releases (release_id));
 diesel::joinable!(releases -> artists (artist_id));
+diesel::joinable!(subscribers -> custom_feeds (custom_feed_id));
+diesel::joinable!(tracks -> releases (release_id));
+diesel::joinable!(webhooks -> custom_feeds (custom_feed_id));
 
-diesel::allow_tables_to_appear_in_same_query!(artists, custom_feeds, feeds, releases,);
+diesel::allow_tables_to_appear_in_same_query!(
+    artists,
+    custom_feeds,
+    feeds,
+    job_runs,
+    overrides,
+    release_details,
+    release_history,
+    releases,
+    source_health,
+    subscribers,
+    tracks,
+    webhooks,
+);
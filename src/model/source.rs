@@ -0,0 +1,115 @@
+use diesel::prelude::*;
+use serde::Serialize;
+
+use super::{ModelManager, schema};
+use crate::error::Result;
+
+/// Represents a row in the `source_health` table, i.e. the outcome of the last
+/// runs of a release source while updating the calendar.
+#[derive(Queryable, Identifiable, Selectable, Insertable, Clone, Debug, PartialEq, Serialize)]
+#[diesel(table_name = super::schema::source_health)]
+#[diesel(primary_key(name))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct SourceHealth {
+    /// The name of the release source, e.g. `wikipedia`.
+    pub name: String,
+    /// The Unix timestamp of the last run, successful or not.
+    pub last_run_at: i64,
+    /// The Unix timestamp of the last successful run.
+    pub last_success_at: Option<i64>,
+    /// The error of the last run, if it failed.
+    pub last_error: Option<String>,
+    /// The number of releases fetched by the last successful run.
+    pub last_num_releases: i32,
+    /// The number of runs that failed since the last successful one.
+    pub consecutive_failures: i32,
+}
+
+impl SourceHealth {
+    /// Whether the last run of the source failed.
+    pub fn is_failing(&self) -> bool {
+        self.consecutive_failures > 0
+    }
+}
+
+/// A trait defining the interface for tracking the health of the release sources.
+///
+/// It can be implemented by any backend service or repository pattern to support
+// different data storage and retrieval strategies.
+pub trait SourceHealthRepository {
+    /// Records a successful run of the source that fetched the given number of releases.
+    fn record_success(&self, name_c: &str, at: i64, num_releases: i32) -> Result<()>;
+
+    /// Records a failed run of the source along with its error.
+    fn record_failure(&self, name_c: &str, at: i64, error: &str) -> Result<()>;
+
+    /// Retrieves the health of every source that ran at least once, ordered by name.
+    fn all(&self) -> Result<Vec<SourceHealth>>;
+}
+
+/// `SourceHealthBmc` is a backend model controller responsible for
+/// tracking the health of the release sources.
+pub struct SourceHealthBmc;
+
+impl SourceHealthRepository for SourceHealthBmc {
+    fn record_success(&self, name_c: &str, at: i64, num_releases: i32) -> Result<()> {
+        use schema::source_health::dsl::*;
+
+        diesel::insert_into(source_health)
+            .values(&SourceHealth {
+                name: name_c.to_string(),
+                last_run_at: at,
+                last_success_at: Some(at),
+                last_error: None,
+                last_num_releases: num_releases,
+                consecutive_failures: 0,
+            })
+            .on_conflict(name)
+            .do_update()
+            .set((
+                last_run_at.eq(at),
+                last_success_at.eq(Some(at)),
+                last_error.eq(None::<String>),
+                last_num_releases.eq(num_releases),
+                consecutive_failures.eq(0),
+            ))
+            .execute(&mut ModelManager::new().conn)?;
+
+        Ok(())
+    }
+
+    fn record_failure(&self, name_c: &str, at: i64, error: &str) -> Result<()> {
+        use schema::source_health::dsl::*;
+
+        diesel::insert_into(source_health)
+            .values(&SourceHealth {
+                name: name_c.to_string(),
+                last_run_at: at,
+                last_success_at: None,
+                last_error: Some(error.to_string()),
+                last_num_releases: 0,
+                consecutive_failures: 1,
+            })
+            .on_conflict(name)
+            .do_update()
+            .set((
+                last_run_at.eq(at),
+                last_error.eq(Some(error)),
+                consecutive_failures.eq(consecutive_failures + 1),
+            ))
+            .execute(&mut ModelManager::new().conn)?;
+
+        Ok(())
+    }
+
+    fn all(&self) -> Result<Vec<SourceHealth>> {
+        use schema::source_health::dsl::*;
+
+        let results = source_health
+            .order(name.asc())
+            .select(SourceHealth::as_select())
+            .load(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,10 @@
+CREATE TABLE subscribers (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    email TEXT NOT NULL,
+    custom_feed_id INTEGER NOT NULL DEFAULT -1 REFERENCES custom_feeds (id) ON DELETE CASCADE,
+    frequency TEXT NOT NULL,
+    is_confirmed BOOLEAN NOT NULL DEFAULT 0,
+    created_at BIGINT NOT NULL,
+    last_sent_at BIGINT,
+    UNIQUE (email, custom_feed_id)
+);
@@ -0,0 +1,9 @@
+CREATE TABLE webhooks (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    url TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    custom_feed_id INTEGER NOT NULL DEFAULT -1 REFERENCES custom_feeds (id) ON DELETE CASCADE,
+    created_at BIGINT NOT NULL,
+    last_sent_at BIGINT,
+    UNIQUE (url, custom_feed_id)
+);
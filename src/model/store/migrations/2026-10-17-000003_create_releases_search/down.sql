@@ -0,0 +1,5 @@
+DROP TRIGGER artists_search_after_update;
+DROP TRIGGER releases_search_after_update;
+DROP TRIGGER releases_search_after_delete;
+DROP TRIGGER releases_search_after_insert;
+DROP TABLE releases_search;
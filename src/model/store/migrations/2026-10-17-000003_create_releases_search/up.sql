@@ -0,0 +1,37 @@
+-- Full-text index of the releases by artist name and album title. The unicode61
+-- tokenizer with remove_diacritics folds accents, so that "Motorhead" matches "Motörhead".
+CREATE VIRTUAL TABLE releases_search USING fts5 (
+    artist,
+    album,
+    release_id UNINDEXED,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+INSERT INTO releases_search (artist, album, release_id)
+SELECT artists.name, releases.album, releases.id
+FROM releases
+INNER JOIN artists ON artists.id = releases.artist_id;
+
+CREATE TRIGGER releases_search_after_insert AFTER INSERT ON releases
+BEGIN
+    INSERT INTO releases_search (artist, album, release_id)
+    SELECT name, NEW.album, NEW.id FROM artists WHERE id = NEW.artist_id;
+END;
+
+CREATE TRIGGER releases_search_after_delete AFTER DELETE ON releases
+BEGIN
+    DELETE FROM releases_search WHERE release_id = OLD.id;
+END;
+
+CREATE TRIGGER releases_search_after_update AFTER UPDATE OF artist_id, album ON releases
+BEGIN
+    DELETE FROM releases_search WHERE release_id = OLD.id;
+    INSERT INTO releases_search (artist, album, release_id)
+    SELECT name, NEW.album, NEW.id FROM artists WHERE id = NEW.artist_id;
+END;
+
+CREATE TRIGGER artists_search_after_update AFTER UPDATE OF name ON artists
+BEGIN
+    UPDATE releases_search SET artist = NEW.name
+    WHERE release_id IN (SELECT id FROM releases WHERE artist_id = NEW.id);
+END;
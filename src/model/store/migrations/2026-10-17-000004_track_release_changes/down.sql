@@ -0,0 +1,4 @@
+DROP INDEX releases_artist_id_year;
+ALTER TABLE releases DROP COLUMN is_removed;
+ALTER TABLE releases DROP COLUMN last_seen;
+ALTER TABLE releases DROP COLUMN first_seen;
@@ -0,0 +1,9 @@
+-- The releases are no longer deleted and reinserted on every calendar update. They
+-- are matched by artist, album and year instead, so that their IDs stay the same.
+ALTER TABLE releases ADD COLUMN first_seen BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE releases ADD COLUMN last_seen BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE releases ADD COLUMN is_removed BOOLEAN NOT NULL DEFAULT 0;
+
+UPDATE releases SET first_seen = strftime('%s', 'now'), last_seen = strftime('%s', 'now');
+
+CREATE INDEX releases_artist_id_year ON releases (artist_id, year);
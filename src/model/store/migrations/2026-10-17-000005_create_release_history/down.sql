@@ -0,0 +1,3 @@
+DROP INDEX release_history_changed_at;
+DROP TABLE release_history;
+ALTER TABLE releases DROP COLUMN moved_from;
@@ -0,0 +1,12 @@
+-- The previous date of a release that was moved, as YYYYMMDD.
+ALTER TABLE releases ADD COLUMN moved_from INTEGER;
+
+CREATE TABLE release_history (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    release_id INTEGER NOT NULL REFERENCES releases (id) ON DELETE CASCADE,
+    old_date INTEGER NOT NULL,
+    new_date INTEGER NOT NULL,
+    changed_at BIGINT NOT NULL
+);
+
+CREATE INDEX release_history_changed_at ON release_history (changed_at);
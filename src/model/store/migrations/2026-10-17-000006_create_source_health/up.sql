@@ -0,0 +1,8 @@
+CREATE TABLE source_health (
+    name TEXT NOT NULL PRIMARY KEY,
+    last_run_at BIGINT NOT NULL,
+    last_success_at BIGINT,
+    last_error TEXT,
+    last_num_releases INTEGER NOT NULL DEFAULT 0,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0
+);
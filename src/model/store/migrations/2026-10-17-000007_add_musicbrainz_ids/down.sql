@@ -0,0 +1,2 @@
+ALTER TABLE releases DROP COLUMN musicbrainz_id;
+ALTER TABLE artists DROP COLUMN musicbrainz_id;
@@ -0,0 +1,3 @@
+-- The MusicBrainz identifiers (MBIDs) of the artists and of the release groups.
+ALTER TABLE artists ADD COLUMN musicbrainz_id TEXT;
+ALTER TABLE releases ADD COLUMN musicbrainz_id TEXT;
@@ -0,0 +1,3 @@
+ALTER TABLE artists DROP COLUMN bandcamp_locked;
+ALTER TABLE artists DROP COLUMN bandcamp_checked_at;
+ALTER TABLE artists DROP COLUMN bandcamp_confidence;
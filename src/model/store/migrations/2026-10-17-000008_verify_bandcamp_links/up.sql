@@ -0,0 +1,5 @@
+-- The Bandcamp links are verified against the artist pages. The existing links
+-- were guessed from the names only, so every artist is checked again.
+ALTER TABLE artists ADD COLUMN bandcamp_confidence REAL;
+ALTER TABLE artists ADD COLUMN bandcamp_checked_at BIGINT;
+ALTER TABLE artists ADD COLUMN bandcamp_locked BOOLEAN NOT NULL DEFAULT 0;
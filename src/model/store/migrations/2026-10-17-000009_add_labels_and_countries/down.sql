@@ -0,0 +1,17 @@
+CREATE TABLE custom_feeds_old (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    bands TEXT NOT NULL,
+    genres TEXT NOT NULL,
+    UNIQUE (bands, genres)
+);
+
+INSERT OR IGNORE INTO custom_feeds_old (id, bands, genres)
+SELECT id, bands, genres FROM custom_feeds ORDER BY id;
+
+DROP TABLE custom_feeds;
+ALTER TABLE custom_feeds_old RENAME TO custom_feeds;
+
+ALTER TABLE releases DROP COLUMN metallum_checked_at;
+ALTER TABLE releases DROP COLUMN label;
+ALTER TABLE artists DROP COLUMN metallum_checked_at;
+ALTER TABLE artists DROP COLUMN country;
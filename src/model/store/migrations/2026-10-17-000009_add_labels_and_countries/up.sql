@@ -0,0 +1,23 @@
+-- The country of origin of the artists and the record label of the releases. Those
+-- missing from the calendars are scraped from the pages of The Metal Archives.
+ALTER TABLE artists ADD COLUMN country TEXT;
+ALTER TABLE artists ADD COLUMN metallum_checked_at BIGINT;
+ALTER TABLE releases ADD COLUMN label TEXT;
+ALTER TABLE releases ADD COLUMN metallum_checked_at BIGINT;
+
+-- The custom feeds also filter on countries and labels. The table is recreated
+-- because its unique constraint spans every filter.
+CREATE TABLE custom_feeds_new (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    bands TEXT NOT NULL,
+    genres TEXT NOT NULL,
+    countries TEXT NOT NULL DEFAULT '',
+    labels TEXT NOT NULL DEFAULT '',
+    UNIQUE (bands, genres, countries, labels)
+);
+
+INSERT INTO custom_feeds_new (id, bands, genres)
+SELECT id, bands, genres FROM custom_feeds;
+
+DROP TABLE custom_feeds;
+ALTER TABLE custom_feeds_new RENAME TO custom_feeds;
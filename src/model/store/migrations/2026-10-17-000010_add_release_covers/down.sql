@@ -0,0 +1,2 @@
+ALTER TABLE releases DROP COLUMN cover_checked_at;
+ALTER TABLE releases DROP COLUMN cover;
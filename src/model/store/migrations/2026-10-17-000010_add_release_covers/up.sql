@@ -0,0 +1,4 @@
+-- The thumbnail of the album cover of the releases, stored as a file named after
+-- the hash of its content in the covers folder.
+ALTER TABLE releases ADD COLUMN cover TEXT;
+ALTER TABLE releases ADD COLUMN cover_checked_at BIGINT;
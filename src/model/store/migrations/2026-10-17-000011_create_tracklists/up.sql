@@ -0,0 +1,18 @@
+-- The details scraped from the album pages of The Metal Archives.
+CREATE TABLE release_details (
+    release_id INTEGER NOT NULL PRIMARY KEY REFERENCES releases (id) ON DELETE CASCADE,
+    format TEXT,
+    label TEXT,
+    runtime INTEGER,
+    checked_at BIGINT NOT NULL
+);
+
+CREATE TABLE tracks (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    release_id INTEGER NOT NULL REFERENCES releases (id) ON DELETE CASCADE,
+    disc INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    duration INTEGER,
+    UNIQUE (release_id, disc, position)
+);
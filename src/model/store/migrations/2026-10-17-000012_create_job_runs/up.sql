@@ -0,0 +1,11 @@
+CREATE TABLE job_runs (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    started_at BIGINT NOT NULL,
+    finished_at BIGINT,
+    status TEXT NOT NULL DEFAULT 'running',
+    error TEXT,
+    items_changed INTEGER
+);
+
+CREATE INDEX idx_job_runs_started_at ON job_runs (started_at);
@@ -0,0 +1,3 @@
+ALTER TABLE releases DROP COLUMN is_manual;
+ALTER TABLE releases DROP COLUMN merged_into;
+ALTER TABLE releases DROP COLUMN is_hidden;
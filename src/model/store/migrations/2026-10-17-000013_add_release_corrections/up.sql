@@ -0,0 +1,6 @@
+-- The corrections of the administrators. A hidden release stays removed whatever the
+-- sources list, a release merged into another is hidden in favour of it, and a release
+-- added by hand is never removed because the sources do not list it.
+ALTER TABLE releases ADD COLUMN is_hidden BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE releases ADD COLUMN merged_into INTEGER REFERENCES releases (id) ON DELETE SET NULL;
+ALTER TABLE releases ADD COLUMN is_manual BOOLEAN NOT NULL DEFAULT 0;
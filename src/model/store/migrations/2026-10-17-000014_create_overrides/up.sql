@@ -0,0 +1,12 @@
+CREATE TABLE overrides (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    artist TEXT NOT NULL,
+    album TEXT NOT NULL DEFAULT '',
+    artist_key TEXT NOT NULL,
+    album_key TEXT NOT NULL DEFAULT '',
+    field TEXT NOT NULL,
+    value TEXT,
+    note TEXT,
+    created_at BIGINT NOT NULL,
+    UNIQUE (artist_key, album_key, field)
+);
@@ -0,0 +1,2 @@
+-- The size in bytes of the cover's thumbnail, given as the length of the feeds' enclosures.
+ALTER TABLE releases ADD COLUMN cover_size INTEGER;
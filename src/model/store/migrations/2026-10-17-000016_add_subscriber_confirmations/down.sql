@@ -0,0 +1,2 @@
+ALTER TABLE subscribers DROP COLUMN confirmation_sent_at;
+ALTER TABLE subscribers DROP COLUMN pending_frequency;
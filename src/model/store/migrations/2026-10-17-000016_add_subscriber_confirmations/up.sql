@@ -0,0 +1,4 @@
+-- A confirmed subscriber asking for another frequency keeps the current one until the
+-- new confirmation link is clicked, and the confirmation emails are throttled.
+ALTER TABLE subscribers ADD COLUMN pending_frequency TEXT;
+ALTER TABLE subscribers ADD COLUMN confirmation_sent_at BIGINT;
@@ -3,22 +3,61 @@ use diesel::prelude::*;
 use diesel_migrations::MigrationHarness;
 use diesel_migrations::{EmbeddedMigrations, embed_migrations};
 
+use crate::{
+    config::config,
+    error::{Error, Result},
+};
+
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("src/model/store/migrations");
 
-/// Establishes a connection to the SQLite database.
+/// Establishes a connection to the SQLite database at `DATABASE_PATH`.
 ///
 /// # Panics
 ///
 /// This function panics if the application of database migrations fails.
 ///
 pub fn establish_connection() -> SqliteConnection {
-    let mut conn = SqliteConnection::establish("./data/metal.db").unwrap_or_else(|_| {
-        eprintln!("Error connecting to ./data/metal.db");
-        std::process::exit(1);
-    });
+    let mut conn = connect();
 
     conn.run_pending_migrations(MIGRATIONS)
         .expect("Migrations should have been applied");
 
     conn
 }
+
+/// Applies the pending database migrations.
+///
+/// Returns the versions of the migrations that were applied, e.g. `2026-10-17-000011`.
+///
+/// # Errors
+///
+/// This function returns an error if a migration fails.
+pub fn run_migrations() -> Result<Vec<String>> {
+    let mut conn = connect();
+    let versions = conn
+        .run_pending_migrations(MIGRATIONS)
+        .map_err(|err| Error::MigrationFail(err.to_string()))?;
+
+    Ok(versions.iter().map(|version| version.to_string()).collect())
+}
+
+/// Rebuilds the database file to reclaim the space of the deleted rows and refreshes
+/// the statistics of the query planner.
+///
+/// # Errors
+///
+/// This function returns an error if a statement fails, e.g. when the database is locked.
+pub fn optimize_database() -> Result<()> {
+    let mut conn = connect();
+    diesel::sql_query("VACUUM").execute(&mut conn)?;
+    diesel::sql_query("PRAGMA optimize").execute(&mut conn)?;
+    Ok(())
+}
+
+fn connect() -> SqliteConnection {
+    let path = &config().DATABASE_PATH;
+    SqliteConnection::establish(path).unwrap_or_else(|_| {
+        eprintln!("Error connecting to {path}");
+        std::process::exit(1);
+    })
+}
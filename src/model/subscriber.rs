@@ -0,0 +1,291 @@
+use std::str::FromStr;
+
+use diesel::prelude::*;
+
+use super::{ModelManager, schema};
+use crate::{
+    date_now,
+    error::{Error, Result},
+};
+
+/// The delay before another confirmation email can be sent to a subscriber.
+const CONFIRMATION_RESEND_SECS: i64 = 60 * 60;
+
+/// How often a subscriber receives the digest of releases.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+}
+
+impl Frequency {
+    /// Returns the value stored in the `frequency` column of the `subscribers` table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+        }
+    }
+
+    /// Returns the number of days of releases a digest covers.
+    pub fn num_days(&self) -> i64 {
+        match self {
+            Frequency::Daily => 1,
+            Frequency::Weekly => 7,
+        }
+    }
+}
+
+impl FromStr for Frequency {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "daily" => Ok(Frequency::Daily),
+            "weekly" => Ok(Frequency::Weekly),
+            _ => Err(Error::InvalidParam("frequency")),
+        }
+    }
+}
+
+/// Represents a row in the `subscribers` table, i.e. an email address
+/// subscribed to the digest of the global feed or of a custom feed.
+#[derive(Queryable, Identifiable, Selectable, Debug, PartialEq)]
+#[diesel(table_name = super::schema::subscribers)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Subscriber {
+    pub id: i32,
+    pub email: String,
+    /// The custom feed whose releases are sent, or -1 for the global feed.
+    pub custom_feed_id: i32,
+    /// Either `daily` or `weekly`.
+    pub frequency: String,
+    /// Whether the subscriber clicked the link of the confirmation email.
+    pub is_confirmed: bool,
+    /// The Unix timestamp of the subscription.
+    pub created_at: i64,
+    /// The Unix timestamp of the last digest sent.
+    pub last_sent_at: Option<i64>,
+    /// The frequency a confirmed subscriber asked for, applied once the new
+    /// confirmation link is clicked.
+    pub pending_frequency: Option<String>,
+    /// The Unix timestamp of the last confirmation email sent.
+    pub confirmation_sent_at: Option<i64>,
+}
+
+impl Subscriber {
+    /// Whether the subscription, or its change of frequency, awaits its confirmation.
+    pub fn awaits_confirmation(&self) -> bool {
+        !self.is_confirmed || self.pending_frequency.is_some()
+    }
+
+    /// Whether a confirmation email can be sent at the given Unix timestamp, i.e.
+    /// none was sent in the last `CONFIRMATION_RESEND_SECS`.
+    pub fn can_resend_confirmation(&self, now: i64) -> bool {
+        self.confirmation_sent_at
+            .is_none_or(|sent_at| now - sent_at >= CONFIRMATION_RESEND_SECS)
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::subscribers)]
+struct SubscriberForInsert {
+    pub email: String,
+    pub custom_feed_id: i32,
+    pub frequency: String,
+    pub created_at: i64,
+}
+
+/// A trait defining the interface for managing the email subscribers.
+///
+/// It can be implemented by any backend service or repository pattern to support
+// different data storage and retrieval strategies.
+pub trait SubscriberRepository {
+    /// Subscribes the email address to the digest of the custom feed.
+    ///
+    /// If the email address is already subscribed to the feed, the existing subscriber
+    /// is returned. The frequency of an unconfirmed subscription is updated, whereas the
+    /// one of a confirmed subscription is only applied once confirmed again, see `confirm`.
+    fn create(&self, email_c: &str, custom_feed: i32, frequency_c: Frequency)
+    -> Result<Subscriber>;
+
+    /// Retrieves a `Subscriber` by its ID.
+    fn get(&self, subscriber_id: i32) -> Result<Subscriber>;
+
+    /// Marks the subscription as confirmed and applies its pending frequency, if any.
+    fn confirm(&self, subscriber_id: i32) -> Result<()>;
+
+    /// Records when the last confirmation email was sent to the subscriber.
+    fn mark_confirmation_sent(&self, subscriber_id: i32, sent_at: i64) -> Result<()>;
+
+    /// Deletes the subscription.
+    fn delete(&self, subscriber_id: i32) -> Result<()>;
+
+    /// Retrieves the confirmed subscribers receiving digests at the given frequency.
+    fn confirmed(&self, frequency_c: Frequency) -> Result<Vec<Subscriber>>;
+
+    /// Records when the last digest was sent to the subscriber.
+    fn mark_sent(&self, subscriber_id: i32, sent_at: i64) -> Result<()>;
+}
+
+/// `SubscriberBmc` is a backend model controller responsible for
+/// managing the email subscribers.
+pub struct SubscriberBmc;
+
+impl SubscriberRepository for SubscriberBmc {
+    fn create(
+        &self,
+        email_c: &str,
+        custom_feed: i32,
+        frequency_c: Frequency,
+    ) -> Result<Subscriber> {
+        use schema::subscribers::dsl::*;
+
+        let conn = &mut ModelManager::new().conn;
+        let email_c = email_c.trim().to_lowercase();
+
+        let existing = subscribers
+            .filter(email.eq(&email_c).and(custom_feed_id.eq(custom_feed)))
+            .select(Subscriber::as_select())
+            .first(conn)
+            .optional()?;
+
+        let subscriber = match existing {
+            Some(subscriber) if !subscriber.is_confirmed => {
+                diesel::update(subscribers.find(subscriber.id))
+                    .set(frequency.eq(frequency_c.as_str()))
+                    .returning(Subscriber::as_returning())
+                    .get_result(conn)?
+            }
+            Some(subscriber) => {
+                let pending =
+                    Some(frequency_c.as_str()).filter(|&asked| asked != subscriber.frequency);
+                diesel::update(subscribers.find(subscriber.id))
+                    .set(pending_frequency.eq(pending))
+                    .returning(Subscriber::as_returning())
+                    .get_result(conn)?
+            }
+            None => diesel::insert_into(subscribers)
+                .values(&SubscriberForInsert {
+                    email: email_c,
+                    custom_feed_id: custom_feed,
+                    frequency: frequency_c.as_str().to_string(),
+                    created_at: date_now().unix_timestamp(),
+                })
+                .returning(Subscriber::as_returning())
+                .get_result(conn)?,
+        };
+
+        Ok(subscriber)
+    }
+
+    fn get(&self, subscriber_id: i32) -> Result<Subscriber> {
+        use schema::subscribers::dsl::*;
+
+        subscribers
+            .find(subscriber_id)
+            .select(Subscriber::as_select())
+            .first(&mut ModelManager::new().conn)
+            .optional()?
+            .ok_or(Error::EntityNotFound {
+                entity: "subscriber",
+                id: subscriber_id as i64,
+            })
+    }
+
+    fn confirm(&self, subscriber_id: i32) -> Result<()> {
+        use schema::subscribers::dsl::*;
+
+        let conn = &mut ModelManager::new().conn;
+        let subscriber = self.get(subscriber_id)?;
+
+        diesel::update(subscribers.find(subscriber_id))
+            .set((
+                is_confirmed.eq(true),
+                frequency.eq(subscriber.pending_frequency.unwrap_or(subscriber.frequency)),
+                pending_frequency.eq(None::<String>),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    fn mark_confirmation_sent(&self, subscriber_id: i32, sent_at: i64) -> Result<()> {
+        use schema::subscribers::dsl::*;
+
+        diesel::update(subscribers.find(subscriber_id))
+            .set(confirmation_sent_at.eq(Some(sent_at)))
+            .execute(&mut ModelManager::new().conn)?;
+
+        Ok(())
+    }
+
+    fn delete(&self, subscriber_id: i32) -> Result<()> {
+        use schema::subscribers::dsl::*;
+
+        diesel::delete(subscribers.find(subscriber_id)).execute(&mut ModelManager::new().conn)?;
+
+        Ok(())
+    }
+
+    fn confirmed(&self, frequency_c: Frequency) -> Result<Vec<Subscriber>> {
+        use schema::subscribers::dsl::*;
+
+        let results = subscribers
+            .filter(
+                is_confirmed
+                    .eq(true)
+                    .and(frequency.eq(frequency_c.as_str())),
+            )
+            .order(id.asc())
+            .select(Subscriber::as_select())
+            .load(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+
+    fn mark_sent(&self, subscriber_id: i32, sent_at: i64) -> Result<()> {
+        use schema::subscribers::dsl::*;
+
+        diesel::update(subscribers.find(subscriber_id))
+            .set(last_sent_at.eq(Some(sent_at)))
+            .execute(&mut ModelManager::new().conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[test]
+    fn test_frequency_from_str_ok() -> Result<()> {
+        pretty_assertions::assert_eq!("daily".parse::<Frequency>()?, Frequency::Daily);
+        pretty_assertions::assert_eq!("weekly".parse::<Frequency>()?, Frequency::Weekly);
+        assert!("monthly".parse::<Frequency>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_subscriber_can_resend_confirmation_ok() {
+        let subscriber = |confirmation_sent_at| Subscriber {
+            id: 1,
+            email: String::from("metal@example.com"),
+            custom_feed_id: -1,
+            frequency: String::from("daily"),
+            is_confirmed: false,
+            created_at: 0,
+            last_sent_at: None,
+            pending_frequency: None,
+            confirmation_sent_at,
+        };
+        let now = 10 * CONFIRMATION_RESEND_SECS;
+
+        assert!(subscriber(None).can_resend_confirmation(now));
+        assert!(subscriber(Some(now - CONFIRMATION_RESEND_SECS)).can_resend_confirmation(now));
+        assert!(!subscriber(Some(now - 60)).can_resend_confirmation(now));
+    }
+}
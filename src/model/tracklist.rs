@@ -0,0 +1,129 @@
+use diesel::prelude::*;
+use serde::Serialize;
+
+use super::Release;
+use crate::scraper::metallum::{AlbumDetails, AlbumTrack};
+
+/// Represents a row in the `release_details` table, i.e. the details scraped from
+/// the album page of a release on The Metal Archives.
+#[derive(Queryable, Identifiable, Selectable, Associations, Debug, PartialEq, Serialize)]
+#[diesel(belongs_to(Release))]
+#[diesel(table_name = super::schema::release_details)]
+#[diesel(primary_key(release_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ReleaseDetails {
+    #[serde(skip)]
+    pub release_id: i32,
+    /// The format of the release, e.g. `CD` or `Digital`.
+    pub format: Option<String>,
+    /// The record label printed on the album page.
+    pub label: Option<String>,
+    /// The total running time in seconds.
+    pub runtime: Option<i32>,
+    /// The Unix timestamp of the scrape of the album page.
+    #[serde(skip)]
+    pub checked_at: i64,
+}
+
+impl ReleaseDetails {
+    /// Returns the total running time formatted like on a CD case, e.g. `45:09`.
+    pub fn runtime_text(&self) -> Option<String> {
+        self.runtime.map(format_duration)
+    }
+}
+
+/// Represents a row in the `tracks` table, i.e. a track of the tracklist of a release.
+#[derive(Queryable, Identifiable, Selectable, Associations, Debug, PartialEq, Serialize)]
+#[diesel(belongs_to(Release))]
+#[diesel(table_name = super::schema::tracks)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Track {
+    #[serde(skip)]
+    pub id: i32,
+    #[serde(skip)]
+    pub release_id: i32,
+    /// The number of the disc, starting at 1.
+    pub disc: i32,
+    /// The number of the track on its disc, starting at 1.
+    pub position: i32,
+    pub title: String,
+    /// The duration in seconds, if known.
+    pub duration: Option<i32>,
+}
+
+impl Track {
+    /// Returns the duration formatted like on a CD case, e.g. `12:20`.
+    pub fn duration_text(&self) -> Option<String> {
+        self.duration.map(format_duration)
+    }
+}
+
+/// Represents new details of a release to be inserted into the database.
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = super::schema::release_details)]
+#[diesel(treat_none_as_null = true)]
+pub(in crate::model) struct ReleaseDetailsForInsert {
+    pub release_id: i32,
+    pub format: Option<String>,
+    pub label: Option<String>,
+    pub runtime: Option<i32>,
+    pub checked_at: i64,
+}
+
+impl ReleaseDetailsForInsert {
+    pub fn new(release_id: i32, details: &AlbumDetails, checked_at: i64) -> Self {
+        Self {
+            release_id,
+            format: details.format.clone(),
+            label: details.label.clone(),
+            runtime: details.runtime,
+            checked_at,
+        }
+    }
+}
+
+/// Represents a new track to be inserted into the database.
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::tracks)]
+pub(in crate::model) struct TrackForInsert {
+    pub release_id: i32,
+    pub disc: i32,
+    pub position: i32,
+    pub title: String,
+    pub duration: Option<i32>,
+}
+
+impl TrackForInsert {
+    pub fn new(release_id: i32, track: &AlbumTrack) -> Self {
+        Self {
+            release_id,
+            disc: track.disc,
+            position: track.position,
+            title: track.title.clone(),
+            duration: track.duration,
+        }
+    }
+}
+
+/// Formats a duration in seconds as `M:SS`, or `H:MM:SS` from an hour.
+fn format_duration(seconds: i32) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration_ok() {
+        pretty_assertions::assert_eq!(format_duration(0), "0:00");
+        pretty_assertions::assert_eq!(format_duration(112), "1:52");
+        pretty_assertions::assert_eq!(format_duration(2709), "45:09");
+        pretty_assertions::assert_eq!(format_duration(3723), "1:02:03");
+    }
+}
@@ -0,0 +1,182 @@
+use std::str::FromStr;
+
+use diesel::prelude::*;
+
+use super::{ModelManager, schema};
+use crate::{
+    date_now,
+    error::{Error, Result},
+};
+
+/// The kind of endpoint a webhook posts to, which determines the payload.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WebhookKind {
+    /// A Discord webhook receiving embeds.
+    Discord,
+    /// A Slack incoming webhook receiving Block Kit messages.
+    Slack,
+    /// Any endpoint receiving the releases as JSON, signed with HMAC-SHA256.
+    Json,
+}
+
+impl WebhookKind {
+    /// Returns the value stored in the `kind` column of the `webhooks` table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookKind::Discord => "discord",
+            WebhookKind::Slack => "slack",
+            WebhookKind::Json => "json",
+        }
+    }
+}
+
+impl FromStr for WebhookKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "discord" => Ok(WebhookKind::Discord),
+            "slack" => Ok(WebhookKind::Slack),
+            "json" => Ok(WebhookKind::Json),
+            _ => Err(Error::InvalidParam("kind")),
+        }
+    }
+}
+
+/// Represents a row in the `webhooks` table, i.e. an endpoint notified of
+/// the releases of the global feed or of a custom feed.
+#[derive(Queryable, Identifiable, Selectable, Clone, Debug, PartialEq)]
+#[diesel(table_name = super::schema::webhooks)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Webhook {
+    pub id: i32,
+    pub url: String,
+    /// Either `discord`, `slack` or `json`.
+    pub kind: String,
+    /// The custom feed whose releases are posted, or -1 for the global feed.
+    pub custom_feed_id: i32,
+    /// The Unix timestamp of the registration.
+    pub created_at: i64,
+    /// The Unix timestamp of the last successful notification.
+    pub last_sent_at: Option<i64>,
+}
+
+impl Webhook {
+    /// Returns the kind of the webhook, defaulting to generic JSON for unknown values.
+    pub fn kind(&self) -> WebhookKind {
+        self.kind.parse().unwrap_or(WebhookKind::Json)
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::webhooks)]
+struct WebhookForInsert {
+    pub url: String,
+    pub kind: String,
+    pub custom_feed_id: i32,
+    pub created_at: i64,
+}
+
+/// A trait defining the interface for managing the registered webhooks.
+///
+/// It can be implemented by any backend service or repository pattern to support
+// different data storage and retrieval strategies.
+pub trait WebhookRepository {
+    /// Registers the webhook for the custom feed.
+    ///
+    /// If the URL is already registered for the feed, its kind is updated
+    /// and the existing webhook is returned.
+    fn create(&self, url_c: &str, kind_c: WebhookKind, custom_feed: i32) -> Result<Webhook>;
+
+    /// Retrieves a `Webhook` by its ID.
+    fn get(&self, webhook_id: i32) -> Result<Webhook>;
+
+    /// Deletes the webhook.
+    fn delete(&self, webhook_id: i32) -> Result<()>;
+
+    /// Retrieves every registered webhook, ordered by ID.
+    fn all(&self) -> Result<Vec<Webhook>>;
+
+    /// Records when the webhook was last notified successfully.
+    fn mark_sent(&self, webhook_id: i32, sent_at: i64) -> Result<()>;
+}
+
+/// `WebhookBmc` is a backend model controller responsible for
+/// managing the webhooks.
+pub struct WebhookBmc;
+
+impl WebhookRepository for WebhookBmc {
+    fn create(&self, url_c: &str, kind_c: WebhookKind, custom_feed: i32) -> Result<Webhook> {
+        use schema::webhooks::dsl::*;
+
+        let conn = &mut ModelManager::new().conn;
+        let url_c = url_c.trim();
+
+        let existing = webhooks
+            .filter(url.eq(url_c).and(custom_feed_id.eq(custom_feed)))
+            .select(Webhook::as_select())
+            .first(conn)
+            .optional()?;
+
+        let webhook = match existing {
+            Some(webhook) => diesel::update(webhooks.find(webhook.id))
+                .set(kind.eq(kind_c.as_str()))
+                .returning(Webhook::as_returning())
+                .get_result(conn)?,
+            None => diesel::insert_into(webhooks)
+                .values(&WebhookForInsert {
+                    url: url_c.to_string(),
+                    kind: kind_c.as_str().to_string(),
+                    custom_feed_id: custom_feed,
+                    created_at: date_now().unix_timestamp(),
+                })
+                .returning(Webhook::as_returning())
+                .get_result(conn)?,
+        };
+
+        Ok(webhook)
+    }
+
+    fn get(&self, webhook_id: i32) -> Result<Webhook> {
+        use schema::webhooks::dsl::*;
+
+        webhooks
+            .find(webhook_id)
+            .select(Webhook::as_select())
+            .first(&mut ModelManager::new().conn)
+            .optional()?
+            .ok_or(Error::EntityNotFound {
+                entity: "webhook",
+                id: webhook_id as i64,
+            })
+    }
+
+    fn delete(&self, webhook_id: i32) -> Result<()> {
+        use schema::webhooks::dsl::*;
+
+        diesel::delete(webhooks.find(webhook_id)).execute(&mut ModelManager::new().conn)?;
+
+        Ok(())
+    }
+
+    fn all(&self) -> Result<Vec<Webhook>> {
+        use schema::webhooks::dsl::*;
+
+        let results = webhooks
+            .order(id.asc())
+            .select(Webhook::as_select())
+            .load(&mut ModelManager::new().conn)?;
+
+        Ok(results)
+    }
+
+    fn mark_sent(&self, webhook_id: i32, sent_at: i64) -> Result<()> {
+        use schema::webhooks::dsl::*;
+
+        diesel::update(webhooks.find(webhook_id))
+            .set(last_sent_at.eq(Some(sent_at)))
+            .execute(&mut ModelManager::new().conn)?;
+
+        Ok(())
+    }
+}
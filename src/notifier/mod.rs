@@ -0,0 +1,568 @@
+//! The `notifier` module posts releases to the registered webhooks.
+//!
+//! Discord webhooks receive embeds, Slack webhooks receive Block Kit messages and
+//! generic webhooks receive a JSON document signed with HMAC-SHA256. The signature
+//! is sent in the `X-Metal-Signature` header as `sha256=<hex digest>`, computed over
+//! the raw body with the secret shown when the webhook was registered.
+
+mod payload;
+
+use std::{net::IpAddr, sync::Arc, time::Duration};
+
+use reqwest::{
+    StatusCode, Url,
+    dns::{Addrs, Name, Resolve, Resolving},
+    header::CONTENT_TYPE,
+    redirect,
+};
+use tracing::{error, warn};
+use url::Host;
+
+use crate::{
+    config::config,
+    date_now,
+    error::{Error, Result},
+    model::{Artist, FeedRepository, Release, Webhook, WebhookKind, WebhookRepository},
+    support::token::{self, Purpose},
+};
+
+/// The header holding the signature of the generic JSON payloads.
+pub const SIGNATURE_HEADER: &str = "X-Metal-Signature";
+
+/// Posts the payloads to the webhooks, retrying with an exponential backoff
+/// when the endpoint cannot be reached or answers with a server error.
+///
+/// Only public addresses are posted to, and redirects are not followed, so that a
+/// webhook cannot make the server post to itself or to its private network.
+pub struct Notifier {
+    client: reqwest::Client,
+    max_attempts: u32,
+    backoff: Duration,
+    allows_local: bool,
+}
+
+impl Notifier {
+    /// Creates a `Notifier` making up to four attempts, waiting 2, 4 and 8 seconds between them.
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .redirect(redirect::Policy::none())
+                .dns_resolver(Arc::new(PublicResolver))
+                .build()
+                .unwrap_or_default(),
+            max_attempts: 4,
+            backoff: Duration::from_secs(2),
+            allows_local: false,
+        }
+    }
+
+    /// Creates a `Notifier` that also posts to local addresses, e.g. the stand-ins of the tests.
+    #[cfg(test)]
+    fn with_local_endpoints() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .redirect(redirect::Policy::none())
+                .build()
+                .unwrap_or_default(),
+            allows_local: true,
+            ..Self::new()
+        }
+    }
+
+    /// Sets the number of attempts and the delay before the first retry, which doubles every retry.
+    pub fn with_retries(mut self, max_attempts: u32, backoff: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.backoff = backoff;
+        self
+    }
+
+    /// Posts the releases to the webhook.
+    ///
+    /// The key is the application's secret key from which the signing secret
+    /// of the generic JSON webhooks is derived.
+    pub async fn notify(
+        &self,
+        webhook: &Webhook,
+        key: &str,
+        title: &str,
+        releases: &[&(Release, Artist)],
+    ) -> Result<()> {
+        match webhook.kind() {
+            WebhookKind::Discord => {
+                for body in payload::discord(title, releases) {
+                    self.post(&webhook.url, body.to_string(), None).await?;
+                }
+            }
+            WebhookKind::Slack => {
+                for body in payload::slack(title, releases) {
+                    self.post(&webhook.url, body.to_string(), None).await?;
+                }
+            }
+            WebhookKind::Json => {
+                let body = serde_json::to_string(&payload::json(title, releases))
+                    .map_err(|_| Error::ParseFail)?;
+                let signature = format!(
+                    "sha256={}",
+                    token::sign_bytes(&secret(key, webhook), body.as_bytes())
+                );
+                self.post(&webhook.url, body, Some(signature)).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn post(&self, url: &str, body: String, signature: Option<String>) -> Result<()> {
+        if !self.allows_local && !Url::parse(url).is_ok_and(|url| has_public_host(&url)) {
+            error!("Webhook {url} does not point to a public host");
+            return Err(Error::WebhookFail);
+        }
+
+        let mut delay = self.backoff;
+
+        for attempt in 1..=self.max_attempts {
+            let mut request = self
+                .client
+                .post(url)
+                .header(CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+
+            match request.send().await {
+                Ok(res) if res.status().is_success() => return Ok(()),
+                Ok(res) if !is_retryable(res.status()) => {
+                    error!("Webhook {url} rejected the payload: {}", res.status());
+                    return Err(Error::WebhookFail);
+                }
+                Ok(res) => warn!(
+                    "Webhook {url} failed on attempt {attempt}/{}: {}",
+                    self.max_attempts,
+                    res.status()
+                ),
+                Err(err) => warn!(
+                    "Webhook {url} failed on attempt {attempt}/{}: {err}",
+                    self.max_attempts
+                ),
+            }
+
+            if attempt < self.max_attempts {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+        }
+
+        Err(Error::WebhookFail)
+    }
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the secret with which the payloads of the generic JSON webhook are signed.
+pub fn secret(key: &str, webhook: &Webhook) -> String {
+    token::sign(key, Purpose::WebhookSecret, webhook.id, &webhook.url)
+}
+
+/// Whether the URL can be registered as a webhook of the given kind.
+///
+/// Discord and Slack webhooks must point to their official HTTPS endpoints,
+/// whereas generic webhooks only need an HTTP or HTTPS URL to a public host.
+/// The addresses the host resolves to are checked by `resolves_to_public`.
+pub fn is_valid_url(kind: WebhookKind, url: &str) -> bool {
+    let Ok(url) = Url::parse(url.trim()) else {
+        return false;
+    };
+    if !has_public_host(&url) {
+        return false;
+    }
+    let host = url.host_str().unwrap_or_default();
+
+    match kind {
+        WebhookKind::Discord => {
+            url.scheme() == "https"
+                && [
+                    "discord.com",
+                    "discordapp.com",
+                    "ptb.discord.com",
+                    "canary.discord.com",
+                ]
+                .contains(&host)
+                && url.path().starts_with("/api/webhooks/")
+        }
+        WebhookKind::Slack => url.scheme() == "https" && host == "hooks.slack.com",
+        WebhookKind::Json => matches!(url.scheme(), "http" | "https"),
+    }
+}
+
+/// Whether every address the host of the URL resolves to is public.
+pub async fn resolves_to_public(url: &str) -> bool {
+    let Ok(url) = Url::parse(url.trim()) else {
+        return false;
+    };
+
+    match (url.host(), url.port_or_known_default()) {
+        (Some(Host::Domain(domain)), Some(port)) => {
+            match tokio::net::lookup_host((domain, port)).await {
+                Ok(addrs) => {
+                    let addrs = addrs.collect::<Vec<_>>();
+                    !addrs.is_empty() && addrs.iter().all(|addr| is_public_ip(addr.ip()))
+                }
+                Err(_) => false,
+            }
+        }
+        (Some(Host::Ipv4(ip)), _) => is_public_ip(IpAddr::V4(ip)),
+        (Some(Host::Ipv6(ip)), _) => is_public_ip(IpAddr::V6(ip)),
+        _ => false,
+    }
+}
+
+/// Whether the host of the URL is neither a local name nor a literal address that is not public.
+fn has_public_host(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Domain(domain)) => domain != "localhost" && !domain.ends_with(".localhost"),
+        Some(Host::Ipv4(ip)) => is_public_ip(IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => is_public_ip(IpAddr::V6(ip)),
+        None => false,
+    }
+}
+
+/// Whether the address is reachable from the internet, i.e. neither a loopback, private,
+/// shared, link-local, unspecified nor broadcast address.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || first == 0
+                || (first == 100 && (64..128).contains(&second)))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ip(IpAddr::V4(ip)),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// Resolves the hosts of the webhooks to their public addresses only, so that a host
+/// cannot be pointed at the private network after the webhook was registered.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect::<Vec<_>>();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", name.as_str()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Posts the releases to every registered webhook.
+///
+/// Webhooks attached to a custom feed only receive its matching releases and
+/// webhooks without any release to post are skipped. A webhook failing after
+/// all retries is logged and does not prevent the others from being notified.
+pub async fn notify_webhooks<F, W>(
+    feed_repo: &F,
+    webhook_repo: &W,
+    notifier: &Notifier,
+    title: &str,
+    releases: &[(Release, Artist)],
+) -> Result<()>
+where
+    F: FeedRepository,
+    W: WebhookRepository,
+{
+    let Some(key) = &config().SECRET_KEY else {
+        warn!("Skipping the webhooks because the `SECRET_KEY` environment variable is not set.");
+        return Ok(());
+    };
+
+    if releases.is_empty() {
+        return Ok(());
+    }
+
+    for webhook in webhook_repo.all()? {
+        let matching = if webhook.custom_feed_id > -1 {
+            match feed_repo.get_custom_feed(webhook.custom_feed_id) {
+                Ok(custom_feed) => releases
+                    .iter()
+                    .filter(|(release, artist)| custom_feed.matches(release, artist))
+                    .collect::<Vec<_>>(),
+                Err(err) => {
+                    error!(
+                        "Error fetching custom feed of webhook {}: {err}",
+                        webhook.id
+                    );
+                    continue;
+                }
+            }
+        } else {
+            releases.iter().collect::<Vec<_>>()
+        };
+
+        if matching.is_empty() {
+            continue;
+        }
+
+        match notifier.notify(&webhook, key, title, &matching).await {
+            Ok(()) => webhook_repo.mark_sent(webhook.id, date_now().unix_timestamp())?,
+            Err(err) => error!("Error notifying webhook {}: {err}", webhook.id),
+        }
+    }
+
+    Ok(())
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::fixtures;
+
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use axum::{Router, extract::State, http::HeaderMap, routing::post};
+
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+    /// A local stand-in for a webhook endpoint that answers with the given
+    /// statuses in turn and records the requests it receives.
+    #[derive(Clone)]
+    struct StandIn {
+        statuses: Arc<Vec<StatusCode>>,
+        num_calls: Arc<AtomicUsize>,
+        requests: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+    }
+
+    impl StandIn {
+        async fn serve(statuses: Vec<StatusCode>) -> Result<(Self, String)> {
+            let stand_in = Self {
+                statuses: Arc::new(statuses),
+                num_calls: Arc::new(AtomicUsize::new(0)),
+                requests: Arc::new(Mutex::new(Vec::new())),
+            };
+
+            let router = Router::new()
+                .route("/hook", post(stand_in_handler))
+                .with_state(stand_in.clone());
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+            let url = format!("http://{}/hook", listener.local_addr()?);
+            tokio::spawn(async move { axum::serve(listener, router).await });
+
+            Ok((stand_in, url))
+        }
+    }
+
+    async fn stand_in_handler(
+        State(stand_in): State<StandIn>,
+        headers: HeaderMap,
+        body: String,
+    ) -> StatusCode {
+        let call = stand_in.num_calls.fetch_add(1, Ordering::SeqCst);
+        stand_in
+            .requests
+            .lock()
+            .expect("lock is not poisoned")
+            .push((headers, body));
+        stand_in
+            .statuses
+            .get(call)
+            .copied()
+            .unwrap_or(StatusCode::OK)
+    }
+
+    #[tokio::test]
+    async fn test_notify_json_retries_then_signs_ok() -> Result<()> {
+        let (stand_in, url) = StandIn::serve(vec![
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::TOO_MANY_REQUESTS,
+        ])
+        .await?;
+        let webhook = a_webhook(&url, WebhookKind::Json);
+        let notifier = Notifier::with_local_endpoints().with_retries(3, Duration::from_millis(1));
+
+        notifier
+            .notify(&webhook, "a secret key", "New releases", &[&a_release()])
+            .await?;
+
+        pretty_assertions::assert_eq!(stand_in.num_calls.load(Ordering::SeqCst), 3);
+        let requests = stand_in.requests.lock().expect("lock is not poisoned");
+        let (headers, body) = &requests[2];
+        let want = format!(
+            "sha256={}",
+            token::sign_bytes(&secret("a secret key", &webhook), body.as_bytes())
+        );
+        pretty_assertions::assert_eq!(
+            headers.get(SIGNATURE_HEADER).and_then(|v| v.to_str().ok()),
+            Some(want.as_str())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_notify_gives_up_after_max_attempts() -> Result<()> {
+        let (stand_in, url) = StandIn::serve(vec![StatusCode::BAD_GATEWAY; 5]).await?;
+        let notifier = Notifier::with_local_endpoints().with_retries(2, Duration::from_millis(1));
+
+        let got = notifier
+            .notify(
+                &a_webhook(&url, WebhookKind::Discord),
+                "a secret key",
+                "New releases",
+                &[&a_release()],
+            )
+            .await;
+
+        assert!(got.is_err());
+        pretty_assertions::assert_eq!(stand_in.num_calls.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_notify_does_not_retry_client_errors() -> Result<()> {
+        let (stand_in, url) = StandIn::serve(vec![StatusCode::NOT_FOUND; 5]).await?;
+        let notifier = Notifier::with_local_endpoints().with_retries(3, Duration::from_millis(1));
+
+        let got = notifier
+            .notify(
+                &a_webhook(&url, WebhookKind::Slack),
+                "a secret key",
+                "New releases",
+                &[&a_release()],
+            )
+            .await;
+
+        assert!(got.is_err());
+        pretty_assertions::assert_eq!(stand_in.num_calls.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_valid_url_ok() {
+        assert!(is_valid_url(
+            WebhookKind::Discord,
+            "https://discord.com/api/webhooks/123/abc"
+        ));
+        assert!(!is_valid_url(
+            WebhookKind::Discord,
+            "https://example.com/api/webhooks/123/abc"
+        ));
+        assert!(is_valid_url(
+            WebhookKind::Slack,
+            "https://hooks.slack.com/services/T000/B000/XXX"
+        ));
+        assert!(!is_valid_url(
+            WebhookKind::Slack,
+            "http://hooks.slack.com/services/T000/B000/XXX"
+        ));
+        assert!(is_valid_url(WebhookKind::Json, "https://example.com/hook"));
+        assert!(is_valid_url(
+            WebhookKind::Json,
+            "http://93.184.215.14:8080/hook"
+        ));
+        assert!(!is_valid_url(WebhookKind::Json, "ftp://example.com/hook"));
+        assert!(!is_valid_url(WebhookKind::Json, "not a url"));
+    }
+
+    #[test]
+    fn test_is_valid_url_local_hosts_fails() {
+        let urls = [
+            "http://localhost:8080/hook",
+            "http://api.localhost/hook",
+            "http://127.0.0.1:7125/hook",
+            "http://10.0.0.2/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ];
+
+        for url in urls {
+            assert!(!is_valid_url(WebhookKind::Json, url), "{url} is valid");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolves_to_public_ok() {
+        assert!(!resolves_to_public("http://localhost:8080/hook").await);
+        assert!(!resolves_to_public("http://169.254.169.254/latest/meta-data").await);
+        assert!(resolves_to_public("http://93.184.215.14/hook").await);
+    }
+
+    #[tokio::test]
+    async fn test_notify_refuses_local_endpoints() -> Result<()> {
+        let (stand_in, url) = StandIn::serve(vec![]).await?;
+        let notifier = Notifier::new().with_retries(1, Duration::from_millis(1));
+
+        let got = notifier
+            .notify(
+                &a_webhook(&url, WebhookKind::Json),
+                "a secret key",
+                "New releases",
+                &[&a_release()],
+            )
+            .await;
+
+        assert!(got.is_err());
+        pretty_assertions::assert_eq!(stand_in.num_calls.load(Ordering::SeqCst), 0);
+        Ok(())
+    }
+
+    fn a_webhook(url: &str, kind: WebhookKind) -> Webhook {
+        Webhook {
+            id: 1,
+            url: url.to_string(),
+            kind: kind.as_str().to_string(),
+            custom_feed_id: -1,
+            created_at: 0,
+            last_sent_at: None,
+        }
+    }
+
+    fn a_release() -> (Release, Artist) {
+        (
+            Release {
+                release_type: Some(String::from("Full-length")),
+                ..fixtures::a_release()
+            },
+            Artist {
+                genre: Some(String::from("Symphonic Melodic Death Metal")),
+                ..fixtures::an_artist()
+            },
+        )
+    }
+}
@@ -0,0 +1,240 @@
+//! Builders of the bodies posted to each kind of webhook.
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::{
+    model::{Artist, Release},
+    web::json_feed::MetalRelease,
+};
+
+/// The maximum number of embeds Discord accepts in a message.
+const DISCORD_MAX_EMBEDS: usize = 10;
+
+/// The number of releases per Slack message, to stay below its 50 blocks limit.
+const SLACK_MAX_RELEASES: usize = 40;
+
+/// The accent color of the Discord embeds.
+const COLOR: u32 = 0xD73737;
+
+/// The body posted to the generic JSON webhooks.
+#[derive(Debug, Serialize)]
+pub struct JsonPayload {
+    pub title: String,
+    pub releases: Vec<JsonRelease>,
+}
+
+/// A release of the generic JSON payload.
+#[derive(Debug, Serialize)]
+pub struct JsonRelease {
+    /// The release date formatted as YYYY-MM-DD.
+    pub date: String,
+    #[serde(flatten)]
+    pub release: MetalRelease,
+}
+
+/// Builds the Discord messages listing the releases as embeds.
+///
+/// Discord limits the number of embeds per message, so one message is built
+/// for every ten releases.
+pub fn discord(title: &str, releases: &[&(Release, Artist)]) -> Vec<Value> {
+    releases
+        .chunks(DISCORD_MAX_EMBEDS)
+        .map(|chunk| {
+            let embeds = chunk
+                .iter()
+                .map(|(release, artist)| {
+                    let mut description = details(release, artist).join(" · ");
+                    if !description.is_empty() {
+                        description.push('\n');
+                    }
+                    description.push_str(
+                        &links(release, artist)
+                            .iter()
+                            .map(|(name, url)| format!("[{name}]({url})"))
+                            .collect::<Vec<_>>()
+                            .join(" · "),
+                    );
+
+                    json!({
+                        "title": format!("{} - {}", artist.name, release.album),
+                        "url": release.url_metallum.as_ref().unwrap_or(&release.url_youtube),
+                        "description": description,
+                        "color": COLOR,
+                        "footer": { "text": release.date().to_string() },
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            json!({
+                "username": "Heavy Metal Releases",
+                "content": title,
+                "embeds": embeds,
+            })
+        })
+        .collect()
+}
+
+/// Builds the Slack Block Kit messages listing the releases as sections.
+pub fn slack(title: &str, releases: &[&(Release, Artist)]) -> Vec<Value> {
+    releases
+        .chunks(SLACK_MAX_RELEASES)
+        .map(|chunk| {
+            let mut blocks = vec![json!({
+                "type": "header",
+                "text": { "type": "plain_text", "text": title },
+            })];
+
+            blocks.extend(chunk.iter().map(|(release, artist)| {
+                let mut text = format!(
+                    "*{} - {}*",
+                    slack_escape(&artist.name),
+                    slack_escape(&release.album)
+                );
+
+                let mut details = details(release, artist);
+                details.push(release.date().to_string());
+                text.push('\n');
+                text.push_str(&slack_escape(&details.join(" · ")));
+
+                text.push('\n');
+                text.push_str(
+                    &links(release, artist)
+                        .iter()
+                        .map(|(name, url)| format!("<{url}|{name}>"))
+                        .collect::<Vec<_>>()
+                        .join(" · "),
+                );
+
+                json!({
+                    "type": "section",
+                    "text": { "type": "mrkdwn", "text": text },
+                })
+            }));
+
+            json!({
+                "text": title,
+                "blocks": blocks,
+            })
+        })
+        .collect()
+}
+
+/// Builds the body of the generic JSON webhooks.
+pub fn json(title: &str, releases: &[&(Release, Artist)]) -> JsonPayload {
+    JsonPayload {
+        title: title.to_string(),
+        releases: releases
+            .iter()
+            .map(|(release, artist)| JsonRelease {
+                date: release.date().to_string(),
+                release: MetalRelease::new(release, artist),
+            })
+            .collect(),
+    }
+}
+
+fn details(release: &Release, artist: &Artist) -> Vec<String> {
+    artist
+        .genre
+        .iter()
+        .chain(artist.country.iter())
+        .chain(release.release_type.iter())
+        .chain(release.label.iter())
+        .cloned()
+        .collect()
+}
+
+fn links<'a>(release: &'a Release, artist: &'a Artist) -> Vec<(&'static str, &'a str)> {
+    let mut links = vec![("YouTube", release.url_youtube.as_str())];
+    if let Some(url) = &artist.url_bandcamp {
+        links.push(("Bandcamp", url));
+    }
+    if let Some(url) = &release.url_metallum {
+        links.push(("Metallum", url));
+    }
+    links
+}
+
+/// Escapes the characters Slack's mrkdwn format reserves for links and mentions.
+fn slack_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::fixtures;
+
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[test]
+    fn test_discord_one_message_per_ten_releases_ok() {
+        let releases = (0..12).map(a_release).collect::<Vec<_>>();
+        let releases = releases.iter().collect::<Vec<_>>();
+
+        let got = discord("New releases", &releases);
+
+        pretty_assertions::assert_eq!(got.len(), 2);
+        pretty_assertions::assert_eq!(got[0]["embeds"].as_array().map(Vec::len), Some(10));
+        pretty_assertions::assert_eq!(got[1]["embeds"].as_array().map(Vec::len), Some(2));
+        pretty_assertions::assert_eq!(
+            got[0]["embeds"][0],
+            json!({
+                "title": "Wintersun - Time 0",
+                "url": "https://www.metal-archives.com/albums/Wintersun/Time_II/0",
+                "description": "Symphonic Melodic Death Metal · Full-length\n[YouTube](https://www.youtube.com) · [Metallum](https://www.metal-archives.com/albums/Wintersun/Time_II/0)",
+                "color": COLOR,
+                "footer": { "text": "2024-08-30" },
+            })
+        );
+    }
+
+    #[test]
+    fn test_slack_section_ok() {
+        let mut release = a_release(1);
+        release.1.name = String::from("Cult <Of> Luna & Co");
+
+        let got = slack("New releases", &[&release]);
+
+        pretty_assertions::assert_eq!(got.len(), 1);
+        pretty_assertions::assert_eq!(got[0]["blocks"][0]["text"]["text"], "New releases");
+        pretty_assertions::assert_eq!(
+            got[0]["blocks"][1]["text"]["text"],
+            "*Cult &lt;Of&gt; Luna &amp; Co - Time 1*\nSymphonic Melodic Death Metal · Full-length · 2024-08-30\n<https://www.youtube.com|YouTube> · <https://www.metal-archives.com/albums/Wintersun/Time_II/1|Metallum>"
+        );
+    }
+
+    #[test]
+    fn test_json_flattens_release_ok() -> Result<()> {
+        let release = a_release(1);
+
+        let got = serde_json::to_value(json("New releases", &[&release]))?;
+
+        pretty_assertions::assert_eq!(got["title"], "New releases");
+        pretty_assertions::assert_eq!(got["releases"][0]["date"], "2024-08-30");
+        pretty_assertions::assert_eq!(got["releases"][0]["artist"], "Wintersun");
+        pretty_assertions::assert_eq!(got["releases"][0]["album"], "Time 1");
+        Ok(())
+    }
+
+    fn a_release(id: i32) -> (Release, Artist) {
+        (
+            Release {
+                id,
+                album: format!("Time {id}"),
+                release_type: Some(String::from("Full-length")),
+                url_metallum: Some(format!(
+                    "https://www.metal-archives.com/albums/Wintersun/Time_II/{id}"
+                )),
+                ..fixtures::a_release()
+            },
+            Artist {
+                genre: Some(String::from("Symphonic Melodic Death Metal")),
+                ..fixtures::an_artist()
+            },
+        )
+    }
+}
@@ -0,0 +1,393 @@
+//! Finds the [Bandcamp](https://bandcamp.com) page of an artist.
+//!
+//! A candidate page is only accepted when its artist name, and ideally its albums,
+//! match the artist. The page at the subdomain guessed from the name is tried first,
+//! then the artists listed by the Bandcamp search.
+//!
+//! The album pages listed on an artist page are also searched for the album covers.
+
+use scraper::{Html, Selector};
+
+use super::client::BandcampClient;
+use crate::support::fuzzy;
+
+/// The confidence below which a candidate page is rejected.
+pub const MIN_CONFIDENCE: f32 = 0.5;
+
+/// The number of search results whose page is fetched.
+const MAX_SEARCH_CANDIDATES: usize = 3;
+
+/// The Bandcamp page found for an artist.
+#[derive(Debug, PartialEq)]
+pub struct BandcampMatch {
+    pub url: String,
+    /// How likely the page belongs to the artist, from 0 to 1.
+    pub confidence: f32,
+}
+
+/// An artist listed by the Bandcamp search.
+#[derive(Debug, PartialEq)]
+pub struct SearchResult {
+    pub name: String,
+    pub url: String,
+}
+
+/// Finds the Bandcamp page of the artist, given the titles of their albums.
+///
+/// Returns `None` when no candidate page reaches the `MIN_CONFIDENCE`.
+pub async fn find_artist_page(
+    client: &(dyn BandcampClient + Sync),
+    artist: &str,
+    albums: &[String],
+) -> Option<BandcampMatch> {
+    let guessed = guess_url(artist);
+    if let Some(found) = verify(client, &guessed, artist, albums).await {
+        return Some(found);
+    }
+
+    let mut query = String::new();
+    url_escape::encode_query_to_string(artist, &mut query);
+    let search = client
+        .fetch_bandcamp(&format!(
+            "https://bandcamp.com/search?q={query}&item_type=b"
+        ))
+        .await?;
+
+    let key = fuzzy::artist_key(artist);
+    let mut best: Option<BandcampMatch> = None;
+    for result in parse_search_results(&search.html)
+        .into_iter()
+        .filter(|result| result.url != guessed)
+        .filter(|result| fuzzy::is_similar(&fuzzy::artist_key(&result.name), &key))
+        .take(MAX_SEARCH_CANDIDATES)
+    {
+        if let Some(found) = verify(client, &result.url, artist, albums).await
+            && best
+                .as_ref()
+                .is_none_or(|best| found.confidence > best.confidence)
+        {
+            best = Some(found);
+        }
+    }
+    best
+}
+
+/// Fetches the candidate page and scores it, rejecting it below `MIN_CONFIDENCE`.
+///
+/// A candidate redirecting elsewhere, e.g. to the sign-up page of a free
+/// subdomain, is rejected too.
+async fn verify(
+    client: &(dyn BandcampClient + Sync),
+    url: &str,
+    artist: &str,
+    albums: &[String],
+) -> Option<BandcampMatch> {
+    let page = client.fetch_bandcamp(url).await?;
+    let expected_host = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .split(['/', '?'])
+        .next()?;
+    if page.url.host_str() != Some(expected_host) || page.url.path() == "/signup" {
+        return None;
+    }
+
+    let confidence = score_artist_page(&page.html, artist, albums);
+    (confidence >= MIN_CONFIDENCE).then(|| BandcampMatch {
+        url: format!("https://{expected_host}"),
+        confidence,
+    })
+}
+
+/// Finds the URL of the cover of the album on the Bandcamp page of the artist.
+///
+/// The album page is the one listed on the artist page under a title similar to the
+/// album, and its cover is the image of its `og:image` meta tag.
+pub async fn find_album_cover(
+    client: &(dyn BandcampClient + Sync),
+    artist_url: &str,
+    album: &str,
+) -> Option<String> {
+    let artist_url = artist_url.trim_end_matches('/');
+    let page = client.fetch_bandcamp(artist_url).await?;
+
+    let album = fuzzy::normalize(album);
+    let href = album_links(&Html::parse_document(&page.html))
+        .into_iter()
+        .find(|(title, _href)| fuzzy::is_similar(&fuzzy::normalize(title), &album))
+        .map(|(_title, href)| href)?;
+    let url = if href.starts_with("http") {
+        href
+    } else {
+        format!("{artist_url}/{}", href.trim_start_matches('/'))
+    };
+
+    let page = client.fetch_bandcamp(&url).await?;
+    let selector = Selector::parse(r#"meta[property="og:image"]"#).ok()?;
+    Html::parse_document(&page.html)
+        .select(&selector)
+        .find_map(|el| el.value().attr("content"))
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty())
+}
+
+/// Returns the subdomain guessed from the artist name, e.g. `https://darktranquillity.bandcamp.com`.
+pub fn guess_url(artist: &str) -> String {
+    let subdomain = fuzzy::normalize(artist)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>();
+    format!("https://{subdomain}.bandcamp.com")
+}
+
+/// Scores how likely the artist page belongs to the artist, from 0 to 1.
+///
+/// Half of the score comes from the artist name shown on the page, which counts
+/// less when it is only similar. The other half is the share of the albums
+/// listed on the page.
+pub fn score_artist_page(html: &str, artist: &str, albums: &[String]) -> f32 {
+    let document = Html::parse_document(html);
+
+    let key = fuzzy::artist_key(artist);
+    let name_score = page_names(&document)
+        .iter()
+        .map(|name| {
+            let name = fuzzy::artist_key(name);
+            if name == key {
+                1.0
+            } else if fuzzy::is_similar(&name, &key) {
+                0.7
+            } else {
+                0.0
+            }
+        })
+        .fold(0.0, f32::max);
+
+    let titles = page_albums(&document)
+        .iter()
+        .map(|title| fuzzy::normalize(title))
+        .collect::<Vec<_>>();
+    let album_score = if albums.is_empty() {
+        0.0
+    } else {
+        let num_found = albums
+            .iter()
+            .map(|album| fuzzy::normalize(album))
+            .filter(|album| titles.iter().any(|title| fuzzy::is_similar(title, album)))
+            .count();
+        num_found as f32 / albums.len() as f32
+    };
+
+    (name_score + album_score) / 2.0
+}
+
+/// Returns the artist names shown on an artist page.
+fn page_names(document: &Html) -> Vec<String> {
+    let mut names = Vec::new();
+
+    if let Ok(selector) = Selector::parse("#band-name-location .title") {
+        names.extend(
+            document
+                .select(&selector)
+                .map(|el| el.text().collect::<String>().trim().to_string()),
+        );
+    }
+
+    if let Ok(selector) = Selector::parse(r#"meta[property="og:site_name"]"#) {
+        names.extend(
+            document
+                .select(&selector)
+                .filter_map(|el| el.value().attr("content"))
+                .map(|name| name.trim().to_string()),
+        );
+    }
+
+    names.retain(|name| !name.is_empty());
+    names
+}
+
+/// Returns the album titles listed on an artist page.
+fn page_albums(document: &Html) -> Vec<String> {
+    let Ok(selector) = Selector::parse("#music-grid .title") else {
+        return Vec::new();
+    };
+
+    document
+        .select(&selector)
+        .filter_map(|el| el.text().map(str::trim).find(|text| !text.is_empty()))
+        .map(String::from)
+        .collect()
+}
+
+/// Returns the titles of the albums listed on an artist page along with the links
+/// to their pages.
+fn album_links(document: &Html) -> Vec<(String, String)> {
+    let (Ok(item), Ok(link), Ok(title)) = (
+        Selector::parse("#music-grid li"),
+        Selector::parse("a"),
+        Selector::parse(".title"),
+    ) else {
+        return Vec::new();
+    };
+
+    document
+        .select(&item)
+        .filter_map(|el| {
+            let href = el.select(&link).next()?.value().attr("href")?;
+            let title = el
+                .select(&title)
+                .next()?
+                .text()
+                .map(str::trim)
+                .find(|text| !text.is_empty())?;
+            Some((title.to_string(), href.to_string()))
+        })
+        .collect()
+}
+
+/// Parses the artists listed on a Bandcamp search page.
+pub fn parse_search_results(html: &str) -> Vec<SearchResult> {
+    let document = Html::parse_document(html);
+    let (Ok(result), Ok(kind), Ok(heading), Ok(url)) = (
+        Selector::parse("li.searchresult"),
+        Selector::parse(".itemtype"),
+        Selector::parse(".heading a"),
+        Selector::parse(".itemurl a"),
+    ) else {
+        return Vec::new();
+    };
+
+    document
+        .select(&result)
+        .filter(|el| {
+            el.select(&kind).next().is_none_or(|kind| {
+                kind.text()
+                    .collect::<String>()
+                    .trim()
+                    .eq_ignore_ascii_case("artist")
+            })
+        })
+        .filter_map(|el| {
+            let name = el.select(&heading).next()?.text().collect::<String>();
+            let url = el.select(&url).next()?.text().collect::<String>();
+            Some(SearchResult {
+                name: name.trim().to_string(),
+                url: url.trim().trim_end_matches('/').to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::scraper::client::tests::MockClient;
+
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[test]
+    fn test_guess_url_ok() {
+        pretty_assertions::assert_eq!(
+            guess_url("Dark Tranquillity"),
+            "https://darktranquillity.bandcamp.com"
+        );
+        pretty_assertions::assert_eq!(guess_url("Motörhead"), "https://motorhead.bandcamp.com");
+    }
+
+    #[test]
+    fn test_score_artist_page_ok() -> Result<()> {
+        let html = fs::read_to_string("./tests/testdata/bandcamp/wintersun.bandcamp.com.html")?;
+
+        let got_albums = score_artist_page(
+            &html,
+            "Wintersun",
+            &[String::from("Time II"), String::from("Time I")],
+        );
+        let got_name = score_artist_page(&html, "Wintersun", &[String::from("Time III")]);
+        let got_other = score_artist_page(
+            &html,
+            "Winterfylleth",
+            &[String::from("The Dark Hereafter")],
+        );
+
+        pretty_assertions::assert_eq!(got_albums, 1.0);
+        pretty_assertions::assert_eq!(got_name, 0.5);
+        pretty_assertions::assert_eq!(got_other, 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_search_results_ok() -> Result<()> {
+        let html = fs::read_to_string(
+            "./tests/testdata/bandcamp/bandcamp.com_search_q_Ghost_item_type_b.html",
+        )?;
+
+        let got = parse_search_results(&html);
+
+        pretty_assertions::assert_eq!(
+            got,
+            vec![
+                SearchResult {
+                    name: String::from("Ghost Town Collective"),
+                    url: String::from("https://ghost.bandcamp.com"),
+                },
+                SearchResult {
+                    name: String::from("Ghost"),
+                    url: String::from("https://ghostofficial.bandcamp.com"),
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_artist_page_guessed_ok() {
+        let got =
+            find_artist_page(&MockClient::new(), "Wintersun", &[String::from("Time II")]).await;
+
+        pretty_assertions::assert_eq!(
+            got,
+            Some(BandcampMatch {
+                url: String::from("https://wintersun.bandcamp.com"),
+                confidence: 1.0,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_artist_page_rejects_other_artist_ok() {
+        let got = find_artist_page(&MockClient::new(), "Ghost", &[String::from("Skeletá")]).await;
+
+        pretty_assertions::assert_eq!(
+            got,
+            Some(BandcampMatch {
+                url: String::from("https://ghostofficial.bandcamp.com"),
+                confidence: 1.0,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_artist_page_none_ok() {
+        let got = find_artist_page(&MockClient::new(), "Nobody Knows", &[]).await;
+
+        pretty_assertions::assert_eq!(got, None);
+    }
+
+    #[tokio::test]
+    async fn test_find_album_cover_ok() {
+        let client = MockClient::new();
+
+        let got = find_album_cover(&client, "https://wintersun.bandcamp.com", "Time II").await;
+        let got_unlisted =
+            find_album_cover(&client, "https://wintersun.bandcamp.com", "Time III").await;
+
+        pretty_assertions::assert_eq!(
+            got,
+            Some(String::from("https://f4.bcbits.com/img/a0000000001_5.jpg"))
+        );
+        pretty_assertions::assert_eq!(got_unlisted, None);
+    }
+}
@@ -5,8 +5,10 @@ use time::OffsetDateTime;
 use tracing::error;
 
 use super::metallum::MetallumReleases;
-use crate::error::Result;
+use crate::{config::config, error::Result};
 
+/// The HTTP client used to reach Wikipedia, The Metal Archives, Bandcamp and the
+/// hosts of the album covers.
 pub struct MainClient {
     http_client: reqwest::Client,
 }
@@ -17,51 +19,116 @@ impl MainClient {
     }
 }
 
+/// Fetches the yearly heavy metal releases pages of Wikipedia.
 #[async_trait]
-pub trait Client {
+pub trait WikiClient {
     async fn get_calendar(&self, year: i32) -> Result<Html>;
-    async fn get_bandcamp_link(&self, artist: String) -> Option<Url>;
+}
+
+/// Pages through the upcoming releases of The Metal Archives and fetches
+/// their band and album pages.
+#[async_trait]
+pub trait MetallumClient {
     async fn fetch_metallum(&self, page: u16) -> Option<MetallumReleases>;
+
+    /// Fetches the HTML of a band or album page, or `None` when it cannot be reached.
+    async fn fetch_metallum_page(&self, url: &str) -> Option<String>;
 }
 
+/// A page fetched from Bandcamp.
+pub struct BandcampPage {
+    /// The URL the page was served from, after the redirects.
+    pub url: Url,
+    pub html: String,
+}
+
+/// Fetches the artist and search pages of Bandcamp.
 #[async_trait]
-impl Client for MainClient {
+pub trait BandcampClient {
+    /// Fetches the page at the URL, or `None` when it cannot be reached.
+    async fn fetch_bandcamp(&self, url: &str) -> Option<BandcampPage>;
+}
+
+/// Downloads the images of the album covers.
+#[async_trait]
+pub trait ImageClient {
+    /// Fetches the bytes of the image at the URL, or `None` when it cannot be reached
+    /// or is not an image.
+    async fn fetch_image(&self, url: &str) -> Option<Vec<u8>>;
+}
+
+/// The largest image downloaded, in bytes.
+const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+
+#[async_trait]
+impl WikiClient for MainClient {
     async fn get_calendar(&self, year: i32) -> Result<Html> {
         let url = format!("https://en.wikipedia.org/wiki/{year}_in_heavy_metal_music");
         let res = self.http_client.get(url).send().await?;
         let text = res.text().await?;
         Ok(Html::parse_document(text.as_str()))
     }
+}
 
-    async fn get_bandcamp_link(&self, artist: String) -> Option<Url> {
-        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+#[async_trait]
+impl BandcampClient for MainClient {
+    async fn fetch_bandcamp(&self, url: &str) -> Option<BandcampPage> {
+        tokio::time::sleep(config().BANDCAMP_DELAY).await;
 
-        let artist = artist
-            .to_lowercase()
-            .chars()
-            .filter(|c| c.is_alphanumeric())
-            .collect::<String>();
+        match self.http_client.get(url).send().await {
+            Ok(res) if res.status().is_success() => {
+                let url = res.url().clone();
+                match res.text().await {
+                    Ok(html) => Some(BandcampPage { url, html }),
+                    Err(err) => {
+                        error!("url = {url}; err = {err}");
+                        None
+                    }
+                }
+            }
+            Ok(_) => None,
+            Err(err) => {
+                error!("url = {url}; err = {err}");
+                None
+            }
+        }
+    }
+}
 
-        let url = format!("https://{artist}.bandcamp.com");
+#[async_trait]
+impl ImageClient for MainClient {
+    async fn fetch_image(&self, url: &str) -> Option<Vec<u8>> {
+        match self.http_client.get(url).send().await {
+            Ok(res) if res.status().is_success() => {
+                let is_image = res
+                    .headers()
+                    .get(reqwest::header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.starts_with("image/"));
+                if !is_image {
+                    return None;
+                }
 
-        match self.http_client.get(&url).send().await {
-            Ok(res) => {
-                let is_valid = res
-                    .url()
-                    .host_str()
-                    .map(|host| host == format!("{}.bandcamp.com", artist))
-                    .unwrap_or(false)
-                    && res.url().path() != "/signup";
-
-                is_valid.then(|| Url::parse(&url).ok()).flatten()
+                match res.bytes().await {
+                    Ok(bytes) if bytes.len() <= MAX_IMAGE_BYTES => Some(bytes.to_vec()),
+                    Ok(_) => None,
+                    Err(err) => {
+                        error!("url = {url}; err = {err}");
+                        None
+                    }
+                }
             }
+            Ok(_) => None,
             Err(err) => {
-                error!("artist = {artist}; url = {url}; err = {err}");
+                error!("url = {url}; err = {err}");
                 None
             }
         }
     }
+}
 
+#[async_trait]
+impl MetallumClient for MainClient {
     async fn fetch_metallum(&self, page: u16) -> Option<MetallumReleases> {
         let offset = page * 100;
         let now = OffsetDateTime::now_local().unwrap_or(OffsetDateTime::now_utc());
@@ -95,10 +162,30 @@ impl Client for MainClient {
             }
         }
     }
+
+    async fn fetch_metallum_page(&self, url: &str) -> Option<String> {
+        tokio::time::sleep(config().METALLUM_DELAY).await;
+
+        match self.http_client.get(url).send().await {
+            Ok(res) if res.status().is_success() => res
+                .text()
+                .await
+                .map_err(|err| error!("url = {url}; err = {err}"))
+                .ok(),
+            Ok(res) => {
+                error!("url = {url}; status = {}", res.status());
+                None
+            }
+            Err(err) => {
+                error!("url = {url}; err = {err}");
+                None
+            }
+        }
+    }
 }
 
 #[cfg(test)]
-pub mod tests {
+pub(crate) mod tests {
     use super::*;
 
     use std::{fs, io::Write, path::PathBuf};
@@ -120,7 +207,7 @@ pub mod tests {
     }
 
     #[async_trait]
-    impl Client for MockClient {
+    impl WikiClient for MockClient {
         async fn get_calendar(&self, year: i32) -> Result<Html> {
             let path = PathBuf::from(format!("./tests/testdata/wiki/test_{year}.html"));
 
@@ -144,17 +231,41 @@ pub mod tests {
 
             Ok(Html::parse_document(&content))
         }
+    }
+
+    #[async_trait]
+    impl BandcampClient for MockClient {
+        /// Serves the page saved under `tests/testdata/bandcamp`, named after the URL
+        /// without its scheme, e.g. `wintersun.bandcamp.com.html`. A page without a
+        /// file cannot be reached.
+        async fn fetch_bandcamp(&self, url: &str) -> Option<BandcampPage> {
+            let name = url
+                .trim_start_matches("https://")
+                .trim_end_matches('/')
+                .replace(['/', '?', '&', '='], "_");
+            let html = fs::read_to_string(format!("./tests/testdata/bandcamp/{name}.html")).ok()?;
+
+            Some(BandcampPage {
+                url: Url::parse(url).ok()?,
+                html,
+            })
+        }
+    }
 
-        async fn get_bandcamp_link(&self, artist: String) -> Option<Url> {
-            let artist = artist
-                .to_lowercase()
-                .replace(":", "")
-                .split_whitespace()
-                .collect::<String>();
-            let url = format!("https://{artist}.bandcamp.com");
-            Url::parse(&url).ok()
+    #[async_trait]
+    impl ImageClient for MockClient {
+        /// Serves the image saved under `tests/testdata/covers`, named after the URL
+        /// without its scheme, e.g. `coverartarchive.org_release-group_<id>_front-250`.
+        async fn fetch_image(&self, url: &str) -> Option<Vec<u8>> {
+            let name = url
+                .trim_start_matches("https://")
+                .replace(['/', '?', '&', '='], "_");
+            fs::read(format!("./tests/testdata/covers/{name}")).ok()
         }
+    }
 
+    #[async_trait]
+    impl MetallumClient for MockClient {
         async fn fetch_metallum(&self, page: u16) -> Option<MetallumReleases> {
             let page = page * 100 + 100;
             let path_str = format!("./tests/testdata/metallum/{page}.json");
@@ -190,5 +301,14 @@ pub mod tests {
                 }
             }
         }
+
+        /// Serves the page saved under `tests/testdata/metallum/pages`, named after the
+        /// path of the URL, e.g. `bands_Wintersun_11023.html`.
+        async fn fetch_metallum_page(&self, url: &str) -> Option<String> {
+            let name = url
+                .trim_start_matches("https://www.metal-archives.com/")
+                .replace('/', "_");
+            fs::read_to_string(format!("./tests/testdata/metallum/pages/{name}.html")).ok()
+        }
     }
 }
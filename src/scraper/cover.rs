@@ -0,0 +1,207 @@
+//! Finds the album covers of the releases and stores their thumbnails.
+//!
+//! A cover is looked for at the [Cover Art Archive](https://coverartarchive.org) when
+//! the MusicBrainz release group is known, then on the album page of The Metal Archives,
+//! then on the album page of the artist's Bandcamp. Its thumbnail is saved as a JPEG
+//! named after the hash of its content, so that an image is stored once and can be
+//! cached forever by the browsers.
+
+use std::{fs, io::Cursor, path::Path};
+
+use image::ImageFormat;
+use sha2::{Digest, Sha256};
+
+use super::{
+    bandcamp,
+    client::{BandcampClient, ImageClient, MetallumClient},
+    metallum,
+};
+use crate::error::Result;
+
+/// The largest width and height of a thumbnail, in pixels.
+pub const THUMBNAIL_SIZE: u32 = 300;
+
+/// A client able to reach every host a cover can be found on.
+pub trait CoverClient: MetallumClient + BandcampClient + ImageClient {}
+
+impl<T> CoverClient for T where T: MetallumClient + BandcampClient + ImageClient {}
+
+/// The pages of a release where its cover can be found.
+pub struct CoverQuery<'a> {
+    pub album: &'a str,
+    /// The MusicBrainz identifier of the release group.
+    pub musicbrainz_id: Option<&'a str>,
+    /// The album page on The Metal Archives.
+    pub url_metallum: Option<&'a str>,
+    /// The artist page on Bandcamp.
+    pub url_bandcamp: Option<&'a str>,
+}
+
+/// Downloads the cover of the release from the first source that has one.
+pub async fn find_cover(
+    client: &(dyn CoverClient + Sync),
+    query: &CoverQuery<'_>,
+) -> Option<Vec<u8>> {
+    if let Some(id) = query.musicbrainz_id
+        && let Some(image) = client
+            .fetch_image(&format!(
+                "https://coverartarchive.org/release-group/{id}/front-500"
+            ))
+            .await
+    {
+        return Some(image);
+    }
+
+    if let Some(url) = query.url_metallum
+        && let Some(cover) = metallum::scrape_album_cover(client, url).await
+        && let Some(image) = client.fetch_image(&cover).await
+    {
+        return Some(image);
+    }
+
+    if let Some(url) = query.url_bandcamp
+        && let Some(cover) = bandcamp::find_album_cover(client, url, query.album).await
+    {
+        return client.fetch_image(&cover).await;
+    }
+
+    None
+}
+
+/// Shrinks the image to fit in `THUMBNAIL_SIZE` pixels and saves it as a JPEG in the folder.
+///
+/// Returns the name of the file, which is the SHA-256 of the thumbnail, e.g. `3f2a…9c.jpg`,
+/// along with its size in bytes.
+/// The file is left as it is when the same thumbnail was already stored.
+///
+/// # Errors
+///
+/// This function returns an error if the image cannot be decoded or the file cannot be written.
+pub fn store_thumbnail(image: &[u8], dir: &Path) -> Result<(String, usize)> {
+    let image = image::load_from_memory(image)?;
+    let image = if image.width() > THUMBNAIL_SIZE || image.height() > THUMBNAIL_SIZE {
+        image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+    } else {
+        image
+    };
+
+    let mut bytes = Vec::new();
+    image
+        .to_rgb8()
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Jpeg)?;
+
+    let name = format!("{}.jpg", hex::encode(Sha256::digest(&bytes)));
+    let path = dir.join(&name);
+    if !path.exists() {
+        fs::create_dir_all(dir)?;
+        fs::write(path, &bytes)?;
+    }
+    Ok((name, bytes.len()))
+}
+
+/// Whether the name is one given to a thumbnail by `store_thumbnail`, which makes it
+/// safe to join to the covers folder.
+pub fn is_cover_name(name: &str) -> bool {
+    name.strip_suffix(".jpg").is_some_and(|hash| {
+        hash.len() == 64
+            && hash
+                .chars()
+                .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scraper::client::tests::MockClient;
+
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[tokio::test]
+    async fn test_find_cover_ok() {
+        let client = MockClient::new();
+        let caa = fs::read("./tests/testdata/covers/coverartarchive.org_release-group_6d8d1f0c-4f3a-4d2c-8f0b-1b2c3d4e5f60_front-500").ok();
+        let metallum =
+            fs::read("./tests/testdata/covers/www.metal-archives.com_images_1_2_3_3_1233034.jpg")
+                .ok();
+        let bandcamp = fs::read("./tests/testdata/covers/f4.bcbits.com_img_a0000000001_5.jpg").ok();
+
+        let query = CoverQuery {
+            album: "Time II",
+            musicbrainz_id: Some("6d8d1f0c-4f3a-4d2c-8f0b-1b2c3d4e5f60"),
+            url_metallum: Some("https://www.metal-archives.com/albums/Wintersun/Time_II/1233034"),
+            url_bandcamp: Some("https://wintersun.bandcamp.com"),
+        };
+        let got_caa = find_cover(&client, &query).await;
+        let got_metallum = find_cover(
+            &client,
+            &CoverQuery {
+                musicbrainz_id: None,
+                ..query
+            },
+        )
+        .await;
+        let got_bandcamp = find_cover(
+            &client,
+            &CoverQuery {
+                musicbrainz_id: None,
+                url_metallum: None,
+                ..query
+            },
+        )
+        .await;
+        let got_none = find_cover(
+            &client,
+            &CoverQuery {
+                album: "Time III",
+                musicbrainz_id: None,
+                url_metallum: None,
+                url_bandcamp: Some("https://wintersun.bandcamp.com"),
+            },
+        )
+        .await;
+
+        pretty_assertions::assert_eq!(got_caa, caa);
+        pretty_assertions::assert_eq!(got_metallum, metallum);
+        pretty_assertions::assert_eq!(got_bandcamp, bandcamp);
+        pretty_assertions::assert_eq!(got_none, None);
+    }
+
+    #[test]
+    fn test_store_thumbnail_ok() -> Result<()> {
+        let dir = std::env::temp_dir().join("heavy-metal-notifier-test-covers");
+        let image = fs::read(
+            "./tests/testdata/covers/coverartarchive.org_release-group_6d8d1f0c-4f3a-4d2c-8f0b-1b2c3d4e5f60_front-500",
+        )?;
+
+        let (got, size) = store_thumbnail(&image, &dir)?;
+        let got_again = store_thumbnail(&image, &dir)?;
+
+        let thumbnail = image::open(dir.join(&got))?;
+        let size_on_disk = fs::metadata(dir.join(&got))?.len() as usize;
+        fs::remove_dir_all(&dir)?;
+        assert!(is_cover_name(&got));
+        pretty_assertions::assert_eq!(got_again, (got, size));
+        pretty_assertions::assert_eq!(size_on_disk, size);
+        pretty_assertions::assert_eq!((thumbnail.width(), thumbnail.height()), (300, 240));
+        Ok(())
+    }
+
+    #[test]
+    fn test_store_thumbnail_not_an_image_fails() {
+        let dir = std::env::temp_dir().join("heavy-metal-notifier-test-covers-invalid");
+
+        assert!(store_thumbnail(b"<html></html>", &dir).is_err());
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_is_cover_name_ok() {
+        let name = format!("{}.jpg", "0123456789abcdef".repeat(4));
+
+        assert!(is_cover_name(&name));
+        assert!(!is_cover_name(&name.replace('a', "A")));
+        assert!(!is_cover_name("../metal.db"));
+        assert!(!is_cover_name(&format!("{}.jpg", "0".repeat(63))));
+    }
+}
@@ -1,12 +1,13 @@
 use std::str::FromStr;
 
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
 use serde::Deserialize;
 use tracing::info;
 
-use super::client::Client;
+use super::client::MetallumClient;
 use crate::{
     calendar::{Calendar, Release},
+    config::config,
     error::{Error, Result},
 };
 
@@ -99,9 +100,15 @@ impl MetallumReleaseParts {
     }
 }
 
-pub async fn scrape(client: &impl Client, year: i32) -> Result<Calendar> {
+/// Scrapes the upcoming releases once and sorts them into one calendar per year.
+///
+/// The calendars are returned in the same order as the years.
+pub async fn scrape_years(client: &impl MetallumClient, years: &[i32]) -> Result<Vec<Calendar>> {
     info!("Scraping The Metal Archives");
-    let mut calendar = Calendar::new(year);
+    let mut calendars = years
+        .iter()
+        .map(|&year| Calendar::new(year))
+        .collect::<Vec<_>>();
     let mut page = 0;
 
     loop {
@@ -113,8 +120,14 @@ pub async fn scrape(client: &impl Client, year: i32) -> Result<Calendar> {
                     .data
                     .iter()
                     .filter_map(|release| MetallumReleaseParts::from_release(release.to_vec()).ok())
-                    .filter(|release| release.release_date.year() == year)
                     .for_each(|parts| {
+                        let Some(calendar) = calendars
+                            .iter_mut()
+                            .find(|calendar| calendar.year == parts.release_date.year())
+                        else {
+                            return;
+                        };
+
                         calendar.add_release(
                             parts.release_date.month(),
                             parts.release_date.day(),
@@ -131,11 +144,195 @@ pub async fn scrape(client: &impl Client, year: i32) -> Result<Calendar> {
         }
 
         page += 1;
-        tokio::time::sleep(std::time::Duration::from_secs(3)).await; // From robots.txt: `Crawl-delay: 3`
+        tokio::time::sleep(config().METALLUM_DELAY).await;
     }
 
     info!("Calendar created");
-    Ok(calendar)
+    Ok(calendars)
+}
+
+/// Scrapes the country of origin from the band page at the URL, if the page lists one.
+///
+/// # Errors
+///
+/// This function returns `Error::ScraperFail` if the page cannot be fetched.
+pub async fn scrape_band_country(
+    client: &(dyn MetallumClient + Sync),
+    url: &str,
+) -> Result<Option<String>> {
+    let html = client
+        .fetch_metallum_page(url)
+        .await
+        .ok_or(Error::ScraperFail)?;
+    Ok(definition(
+        &Html::parse_document(&html),
+        "#band_stats",
+        "Country of origin:",
+    ))
+}
+
+/// Scrapes the record label from the album page at the URL, if the page lists one.
+///
+/// # Errors
+///
+/// This function returns `Error::ScraperFail` if the page cannot be fetched.
+pub async fn scrape_album_label(
+    client: &(dyn MetallumClient + Sync),
+    url: &str,
+) -> Result<Option<String>> {
+    let html = client
+        .fetch_metallum_page(url)
+        .await
+        .ok_or(Error::ScraperFail)?;
+    Ok(definition(
+        &Html::parse_document(&html),
+        "#album_info",
+        "Label:",
+    ))
+}
+
+/// The details listed on an album page of The Metal Archives.
+#[derive(Debug, PartialEq)]
+pub struct AlbumDetails {
+    /// The format of the release, e.g. `CD` or `Digital`.
+    pub format: Option<String>,
+    pub label: Option<String>,
+    pub tracks: Vec<AlbumTrack>,
+    /// The total running time in seconds, if the page lists it or every track has a duration.
+    pub runtime: Option<i32>,
+}
+
+/// A track of the tracklist of an album page.
+#[derive(Debug, PartialEq)]
+pub struct AlbumTrack {
+    /// The number of the disc, starting at 1.
+    pub disc: i32,
+    /// The number of the track on its disc, starting at 1.
+    pub position: i32,
+    pub title: String,
+    /// The duration in seconds, if known.
+    pub duration: Option<i32>,
+}
+
+/// Scrapes the format, label and tracklist from the album page at the URL.
+pub async fn scrape_album_details(
+    client: &(dyn MetallumClient + Sync),
+    url: &str,
+) -> Option<AlbumDetails> {
+    let html = client.fetch_metallum_page(url).await?;
+    Some(parse_album_details(&html))
+}
+
+/// Parses the format, label and tracklist of an album page.
+///
+/// The tracks are numbered per disc, the discs being introduced by a `Disc N` row
+/// on the releases with many of them.
+pub fn parse_album_details(html: &str) -> AlbumDetails {
+    let document = Html::parse_document(html);
+
+    let mut tracks = Vec::new();
+    let mut listed_runtime = None;
+    if let (Ok(row), Ok(cell)) = (
+        Selector::parse("#album_tabs_tracklist table.table_lyrics tr"),
+        Selector::parse("td"),
+    ) {
+        let mut disc = 1;
+        for tr in document.select(&row) {
+            let cells = tr
+                .select(&cell)
+                .map(|td| {
+                    td.text()
+                        .collect::<String>()
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>();
+            let classes = tr.value().attr("class").unwrap_or_default();
+
+            if classes.contains("discRow") {
+                disc = cells
+                    .first()
+                    .and_then(|text| text.trim_start_matches("Disc").trim().parse().ok())
+                    .unwrap_or(disc + 1);
+            } else if classes.contains("even") || classes.contains("odd") {
+                let (Some(position), Some(title)) = (
+                    cells
+                        .first()
+                        .and_then(|text| text.trim_end_matches('.').parse().ok()),
+                    cells.get(1).filter(|title| !title.is_empty()),
+                ) else {
+                    continue;
+                };
+
+                tracks.push(AlbumTrack {
+                    disc,
+                    position,
+                    title: title.clone(),
+                    duration: cells.get(2).and_then(|text| parse_duration(text)),
+                });
+            } else if let Some(runtime) = cells.get(2).and_then(|text| parse_duration(text)) {
+                listed_runtime = Some(runtime);
+            }
+        }
+    }
+
+    let runtime = listed_runtime.or_else(|| {
+        if tracks.is_empty() {
+            None
+        } else {
+            tracks.iter().map(|track| track.duration).sum()
+        }
+    });
+
+    AlbumDetails {
+        format: definition(&document, "#album_info", "Format:"),
+        label: definition(&document, "#album_info", "Label:"),
+        tracks,
+        runtime,
+    }
+}
+
+/// Parses a duration written as `MM:SS` or `HH:MM:SS` into seconds.
+fn parse_duration(text: &str) -> Option<i32> {
+    let parts = text.trim().split(':').collect::<Vec<_>>();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+
+    parts.iter().try_fold(0, |total, part| {
+        Some(total * 60 + part.parse::<i32>().ok()?)
+    })
+}
+
+/// Scrapes the URL of the cover image from the album page at the URL.
+pub async fn scrape_album_cover(client: &(dyn MetallumClient + Sync), url: &str) -> Option<String> {
+    let html = client.fetch_metallum_page(url).await?;
+    let selector = Selector::parse("#cover").ok()?;
+
+    Html::parse_document(&html)
+        .select(&selector)
+        .find_map(|el| el.value().attr("href"))
+        .map(|href| href.trim().to_string())
+        .filter(|href| !href.is_empty())
+}
+
+/// Returns the description of the term listed in the `<dl>` of the container,
+/// e.g. `Finland` for the `Country of origin:` of a band.
+fn definition(document: &Html, container: &str, term: &str) -> Option<String> {
+    let selector = Selector::parse(&format!("{container} dt")).ok()?;
+
+    let dt = document
+        .select(&selector)
+        .find(|dt| dt.text().collect::<String>().trim() == term)?;
+    let dd = dt
+        .next_siblings()
+        .filter_map(ElementRef::wrap)
+        .find(|el| el.value().name() == "dd")?;
+
+    let text = dd.text().collect::<String>();
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
 }
 
 #[cfg(test)]
@@ -155,7 +352,7 @@ mod tests {
     async fn test_2024_calendar_ok() -> Result<()> {
         let client = MockClient::new();
 
-        let got = scrape(&client, 2024).await?;
+        let got = scrape_years(&client, &[2024]).await?.remove(0);
 
         let want = Calendar {
             year: 2024,
@@ -168,7 +365,7 @@ mod tests {
 						Release::new("Morbid Invocation", "Opus I").with_metallum("https://www.metal-archives.com/bands/Morbid_Invocation/3540552419", "https://www.metal-archives.com/albums/Morbid_Invocation/Opus_I/1280396", "Full-length", "Black Metal"),
 						Release::new("Phyllomedusa", "Hope Floats").with_metallum("https://www.metal-archives.com/bands/Phyllomedusa/3540529653", "https://www.metal-archives.com/albums/Phyllomedusa/Hope_Floats/1280408", "EP", "Gorenoise, Various")						,
 						Release::new("Hazzerd", "Deathbringer").with_metallum("https://www.metal-archives.com/bands/Hazzerd/3540393393", "https://www.metal-archives.com/albums/Hazzerd/Deathbringer/1280442", "Single", "Thrash Metal")						,
-						Release::new("Död Sol", "På drift i v​ä​st").with_metallum("https://www.metal-archives.com/bands/D%C3%B6d_Sol/3540503122", "https://www.metal-archives.com/albums/D%C3%B6d_Sol/P%C3%A5_drift_i_v%E2%80%8B%C3%A4%E2%80%8Bst/1280508", "Single", "Doom/Stoner Metal/Rock"),
+						Release::new("Död Sol", "På drift i v\u{200B}ä\u{200B}st").with_metallum("https://www.metal-archives.com/bands/D%C3%B6d_Sol/3540503122", "https://www.metal-archives.com/albums/D%C3%B6d_Sol/P%C3%A5_drift_i_v%E2%80%8B%C3%A4%E2%80%8Bst/1280508", "Single", "Doom/Stoner Metal/Rock"),
 					]),
                     (10, vec![
 						Release::new("Rise of Kronos", "Imperium").with_metallum("https://www.metal-archives.com/bands/Rise_of_Kronos/3540504118", "https://www.metal-archives.com/albums/Rise_of_Kronos/Imperium/1266381", "Full-length", "Death/Thrash Metal"),
@@ -226,7 +423,7 @@ mod tests {
 						Release::new("Cytotoxin", "Hope Terminator").with_metallum("https://www.metal-archives.com/bands/Cytotoxin/3540325917", "https://www.metal-archives.com/albums/Cytotoxin/Hope_Terminator/1278462", "Single", "Technical/Brutal Death Metal"),
 						Release::new("Oda", "Bloodstained").with_metallum("https://www.metal-archives.com/bands/Oda/3540550714", "https://www.metal-archives.com/albums/Oda/Bloodstained/1279409", "Full-length", "Psychedelic Doom Metal"),
 						Release::new("Konatus", "Psikoz").with_metallum("https://www.metal-archives.com/bands/Konatus/3540545254", "https://www.metal-archives.com/albums/Konatus/Psikoz/1279432", "Full-length", "Death Metal"),
-						Release::new("Epiklesis", "La Santa Iglesia Cat​ó​lica").with_metallum("https://www.metal-archives.com/bands/Epiklesis/3540551498", "https://www.metal-archives.com/albums/Epiklesis/La_Santa_Iglesia_Cat%E2%80%8B%C3%B3%E2%80%8Blica/1279523", "Full-length", "Symphonic Black Metal"),
+						Release::new("Epiklesis", "La Santa Iglesia Cat\u{200B}ó\u{200B}lica").with_metallum("https://www.metal-archives.com/bands/Epiklesis/3540551498", "https://www.metal-archives.com/albums/Epiklesis/La_Santa_Iglesia_Cat%E2%80%8B%C3%B3%E2%80%8Blica/1279523", "Full-length", "Symphonic Black Metal"),
 						Release::new("Klynt", "Thunderous").with_metallum("https://www.metal-archives.com/bands/Klynt/3540337071", "https://www.metal-archives.com/albums/Klynt/Thunderous/1280205", "Full-length", "Power/Thrash Metal"),
 						Release::new("Druid Stone", "\"Missing Girl\" b/w \"Satellite\"").with_metallum("https://www.metal-archives.com/bands/Druid_Stone/3540495933", "https://www.metal-archives.com/albums/Druid_Stone/%22Missing_Girl%22_b-w_%22Satellite%22/1280343", "Single", "Blackened Doom Metal"),
 						Release::new("Timo Tolkki", "Stratovarius: 4th Dimension Demos").with_metallum("https://www.metal-archives.com/bands/Timo_Tolkki/2564", "https://www.metal-archives.com/albums/Timo_Tolkki/Stratovarius%3A_4th_Dimension_Demos/1280356", "Compilation", "Neoclassical Heavy Metal/Shred (early); Melodic Rock/Ambient (later)"),
@@ -688,7 +885,7 @@ mod tests {
 						Release::new("Alex Nunziati", "Impending Catastrophe").with_metallum("https://www.metal-archives.com/bands/Alex_Nunziati/3540506323", "https://www.metal-archives.com/albums/Alex_Nunziati/Impending_Catastrophe/1266673", "Full-length", "Heavy Metal, Thrash Metal"),
 						Release::new("Vokonis", "Transitions").with_metallum("https://www.metal-archives.com/bands/Vokonis/3540411114", "https://www.metal-archives.com/albums/Vokonis/Transitions/1267264", "Full-length", "Stoner/Doom Metal"),
 						Release::new("Mercyless", "Those Who Reign Below").with_metallum("https://www.metal-archives.com/bands/Mercyless/7544", "https://www.metal-archives.com/albums/Mercyless/Those_Who_Reign_Below/1267629", "Full-length", "Death/Thrash Metal"),
-						Release::new("Sedimentum", "Derri​è​re les portes d'une arcane transcendante").with_metallum("https://www.metal-archives.com/bands/Sedimentum/3540455227", "https://www.metal-archives.com/albums/Sedimentum/Derri%E2%80%8B%C3%A8%E2%80%8Bre_les_portes_d%27une_arcane_transcendante/1267941", "EP", "Death Metal"),
+						Release::new("Sedimentum", "Derri\u{200B}è\u{200B}re les portes d'une arcane transcendante").with_metallum("https://www.metal-archives.com/bands/Sedimentum/3540455227", "https://www.metal-archives.com/albums/Sedimentum/Derri%E2%80%8B%C3%A8%E2%80%8Bre_les_portes_d%27une_arcane_transcendante/1267941", "EP", "Death Metal"),
 						Release::new("Adamantra", "Act III: Pareidolia of Depravity").with_metallum("https://www.metal-archives.com/bands/Adamantra/84533", "https://www.metal-archives.com/albums/Adamantra/Act_III%3A_Pareidolia_of_Depravity/1268265", "Full-length", "Progressive/Power Metal"),
 						Release::new("Stilverlight", "Dead Souls").with_metallum("https://www.metal-archives.com/bands/Stilverlight/3540389416", "https://www.metal-archives.com/albums/Stilverlight/Dead_Souls/1268317", "Full-length", "Melodic Power Metal"),
 						Release::new("Perfidious", "Savouring His Flesh").with_metallum("https://www.metal-archives.com/bands/Perfidious/3540395457", "https://www.metal-archives.com/albums/Perfidious/Savouring_His_Flesh/1268454", "Full-length", "Death Metal"),
@@ -720,7 +917,7 @@ mod tests {
 						Release::new("Extermination Dismemberment", "Butcher Basement (Revamp)").with_metallum("https://www.metal-archives.com/bands/Extermination_Dismemberment/3540318825", "https://www.metal-archives.com/albums/Extermination_Dismemberment/Butcher_Basement_%28Revamp%29/1276476", "Full-length", "Slam/Brutal Death Metal")						,
 						Release::new("Sallow Moth", "Vial").with_metallum("https://www.metal-archives.com/bands/Sallow_Moth/3540438444", "https://www.metal-archives.com/albums/Sallow_Moth/Vial/1276786", "EP", "Death Metal"),
 						Release::new("Draconicon", "A Symphony of Pestilence").with_metallum("https://www.metal-archives.com/bands/Draconicon/3540486854", "https://www.metal-archives.com/albums/Draconicon/A_Symphony_of_Pestilence/1277969", "Full-length", "Power Metal"),
-						Release::new("Mordran", "One​-​and​-​Ninety Years of Darkness").with_metallum("https://www.metal-archives.com/bands/Mordran/3540496459", "https://www.metal-archives.com/albums/Mordran/One%E2%80%8B-%E2%80%8Band%E2%80%8B-%E2%80%8BNinety_Years_of_Darkness/1278163", "EP", "Depressive/Raw Atmospheric Black Metal/Dark Ambient"),
+						Release::new("Mordran", "One\u{200B}-\u{200B}and\u{200B}-\u{200B}Ninety Years of Darkness").with_metallum("https://www.metal-archives.com/bands/Mordran/3540496459", "https://www.metal-archives.com/albums/Mordran/One%E2%80%8B-%E2%80%8Band%E2%80%8B-%E2%80%8BNinety_Years_of_Darkness/1278163", "EP", "Depressive/Raw Atmospheric Black Metal/Dark Ambient"),
 						Release::new("The Holy Flesh", "Advocate, Martyr and Redeemer").with_metallum("https://www.metal-archives.com/bands/The_Holy_Flesh/3540461827", "https://www.metal-archives.com/albums/The_Holy_Flesh/Advocate%2C_Martyr_and_Redeemer/1278257", "Full-length", "Atmospheric Black Metal")						,
 						Release::new("Intöxicated", "Under the Sign of the Red Light").with_metallum("https://www.metal-archives.com/bands/Int%C3%B6xicated/3540299709", "https://www.metal-archives.com/albums/Int%C3%B6xicated/Under_the_Sign_of_the_Red_Light/1278465", "EP", "Speed/Thrash Metal"),
 						Release::new("Lóstregos", "Nai").with_metallum("https://www.metal-archives.com/bands/L%C3%B3stregos/3540411010", "https://www.metal-archives.com/albums/L%C3%B3stregos/Nai/1279093", "Full-length", "Melodic/Pagan Black Metal"),
@@ -783,7 +980,7 @@ mod tests {
 						Release::new("Asgrauw", "Oorsprong").with_metallum("https://www.metal-archives.com/bands/Asgrauw/3540344621", "https://www.metal-archives.com/albums/Asgrauw/Oorsprong/1267751", "Full-length", "Black Metal"),
 						Release::new("Sleepless", "Through Endless Black").with_metallum("https://www.metal-archives.com/bands/Sleepless/3540484422", "https://www.metal-archives.com/albums/Sleepless/Through_Endless_Black/1268093", "Full-length", "Technical Thrash Metal")						,
 						Release::new("Summoning Death", "Tombs of the Blind Dead").with_metallum("https://www.metal-archives.com/bands/Summoning_Death/3540390270", "https://www.metal-archives.com/albums/Summoning_Death/Tombs_of_the_Blind_Dead/1268471", "Full-length", "Death Metal")						,
-						Release::new("Goreatorium", "Vile​-​Lence").with_metallum("https://www.metal-archives.com/bands/Goreatorium/3540414223", "https://www.metal-archives.com/albums/Goreatorium/Vile%E2%80%8B-%E2%80%8BLence/1269092", "Full-length", "Death Metal/Goregrind")						,
+						Release::new("Goreatorium", "Vile\u{200B}-\u{200B}Lence").with_metallum("https://www.metal-archives.com/bands/Goreatorium/3540414223", "https://www.metal-archives.com/albums/Goreatorium/Vile%E2%80%8B-%E2%80%8BLence/1269092", "Full-length", "Death Metal/Goregrind")						,
 						Release::new("Alien Carcass", "Entropic Visions of a Celestial Heaven").with_metallum("https://www.metal-archives.com/bands/Alien_Carcass/3540496967", "https://www.metal-archives.com/albums/Alien_Carcass/Entropic_Visions_of_a_Celestial_Heaven/1269102", "Full-length", "Black/Death Metal")						,
 						Release::new("Slechtvalk", "At Death's Gate").with_metallum("https://www.metal-archives.com/bands/Slechtvalk/5957", "https://www.metal-archives.com/albums/Slechtvalk/At_Death%27s_Gate/1270255", "Full-length", "Melodic/Epic Black Metal")						,
 						Release::new("Sorry...", "Drowned in Misery").with_metallum("https://www.metal-archives.com/bands/Sorry.../3540452576", "https://www.metal-archives.com/albums/Sorry.../Drowned_in_Misery/1271019", "Full-length", "Depressive Black Metal/Post-Punk")						,
@@ -882,7 +1079,7 @@ mod tests {
 						Release::new("Klone", "The Unseen").with_metallum("https://www.metal-archives.com/bands/Klone/18519", "https://www.metal-archives.com/albums/Klone/The_Unseen/1259421", "Full-length", "Progressive Groove Metal (early); Progressive Metal/Rock (later)")						,
 						Release::new("Molder", "Catastrophic Reconfiguration").with_metallum("https://www.metal-archives.com/bands/Molder/3540437246", "https://www.metal-archives.com/albums/Molder/Catastrophic_Reconfiguration/1260154", "Full-length", "Death/Thrash Metal"),
 						Release::new("Make Them Suffer", "Make Them Suffer").with_metallum("https://www.metal-archives.com/bands/Make_Them_Suffer/3540328594", "https://www.metal-archives.com/albums/Make_Them_Suffer/Make_Them_Suffer/1262589", "Full-length", "Symphonic Deathcore (early); Deathcore/Metalcore (later)")						,
-						Release::new("Sólstafir", "Hin helga kv​ö​l").with_metallum("https://www.metal-archives.com/bands/S%C3%B3lstafir/3213", "https://www.metal-archives.com/albums/S%C3%B3lstafir/Hin_helga_kv%E2%80%8B%C3%B6%E2%80%8Bl/1263912", "Full-length", "Viking/Black Metal (early); Post-Metal/Rock (later)")						,
+						Release::new("Sólstafir", "Hin helga kv\u{200B}ö\u{200B}l").with_metallum("https://www.metal-archives.com/bands/S%C3%B3lstafir/3213", "https://www.metal-archives.com/albums/S%C3%B3lstafir/Hin_helga_kv%E2%80%8B%C3%B6%E2%80%8Bl/1263912", "Full-length", "Viking/Black Metal (early); Post-Metal/Rock (later)")						,
 						Release::new("Yoth Iria", "Blazing Inferno").with_metallum("https://www.metal-archives.com/bands/Yoth_Iria/3540451390", "https://www.metal-archives.com/albums/Yoth_Iria/Blazing_Inferno/1266395", "Full-length", "Black Metal")						,
 						Release::new("Valontuoja", "Luonnon armoilla").with_metallum("https://www.metal-archives.com/bands/Valontuoja/3540549976", "https://www.metal-archives.com/albums/Valontuoja/Luonnon_armoilla/1266689", "Full-length", "Black Metal"),
 						Release::new("Ad Vitam Infernal", "Le ballet des anges").with_metallum("https://www.metal-archives.com/bands/Ad_Vitam_Infernal/3540461752", "https://www.metal-archives.com/albums/Ad_Vitam_Infernal/Le_ballet_des_anges/1269921", "Full-length", "Death Metal")						,
@@ -894,7 +1091,7 @@ mod tests {
 						Release::new("Massacre", "Necrolution").with_metallum("https://www.metal-archives.com/bands/Massacre/281", "https://www.metal-archives.com/albums/Massacre/Necrolution/1270551", "Full-length", "Death Metal"),
 						Release::new("Witnesses", "Joy").with_metallum("https://www.metal-archives.com/bands/Witnesses/3540450514", "https://www.metal-archives.com/albums/Witnesses/Joy/1270803", "Full-length", "Ambient/Electronic, Melodic Doom Metal"),
 						Release::new("Impellitteri", "War Machine").with_metallum("https://www.metal-archives.com/bands/Impellitteri/320", "https://www.metal-archives.com/albums/Impellitteri/War_Machine/1270894", "Full-length", "Heavy/Power Metal/Shred")						,
-						Release::new("Stranger Vision", "Faust - Act​​ I Prelude to Darkness").with_metallum("https://www.metal-archives.com/bands/Stranger_Vision/3540485212", "https://www.metal-archives.com/albums/Stranger_Vision/Faust_-_Act%E2%80%8B%E2%80%8B_I_Prelude_to_Darkness/1272179", "Full-length", "Melodic Heavy Metal")						,
+						Release::new("Stranger Vision", "Faust - Act\u{200B}\u{200B} I Prelude to Darkness").with_metallum("https://www.metal-archives.com/bands/Stranger_Vision/3540485212", "https://www.metal-archives.com/albums/Stranger_Vision/Faust_-_Act%E2%80%8B%E2%80%8B_I_Prelude_to_Darkness/1272179", "Full-length", "Melodic Heavy Metal")						,
 						Release::new("Codespeaker", "Scavenger").with_metallum("https://www.metal-archives.com/bands/Codespeaker/3540514945", "https://www.metal-archives.com/albums/Codespeaker/Scavenger/1272427", "Full-length", "Sludge/Post-Metal")						,
 						Release::new("Alarum", "Recontinue").with_metallum("https://www.metal-archives.com/bands/Alarum/2352", "https://www.metal-archives.com/albums/Alarum/Recontinue/1272934", "Full-length", "Progressive/Thrash Metal/Fusion")						,
 						Release::new("Ershetu", "Yomi").with_metallum("https://www.metal-archives.com/bands/Ershetu/3540532792", "https://www.metal-archives.com/albums/Ershetu/Yomi/1274010", "Full-length", "Progressive Black Metal")						,
@@ -1139,7 +1336,7 @@ mod tests {
 							"Full-length",
 							"Power Metal"
 						),
-						Release::new("Misanthropy", "The Ever​-​Crushing Weight of Stagnance").with_metallum(
+						Release::new("Misanthropy", "The Ever\u{200B}-\u{200B}Crushing Weight of Stagnance").with_metallum(
 							"https://www.metal-archives.com/bands/Misanthropy/3540372393",
 							"https://www.metal-archives.com/albums/Misanthropy/The_Ever%E2%80%8B-%E2%80%8BCrushing_Weight_of_Stagnance/1279340",
 							"Full-length",
@@ -1152,7 +1349,7 @@ mod tests {
 					(20, vec![
 						Release::new("Vinodium", "¿En que mundo vivimos?").with_metallum("https://www.metal-archives.com/bands/Vinodium/3540460500", "https://www.metal-archives.com/albums/Vinodium/%C2%BFEn_que_mundo_vivimos%3F/1275758", "Full-length", "Heavy/Thrash Metal")						,
 						Release::new("Lights to Remain", "Damnation").with_metallum("https://www.metal-archives.com/bands/Lights_to_Remain/3540527030", "https://www.metal-archives.com/albums/Lights_to_Remain/Damnation/1278979", "Full-length", "Melodic Death Metal"),
-						Release::new("Hexenbrett", "Dritte Beschw​ö​rung: Dem Teufel eine Tochter").with_metallum("https://www.metal-archives.com/bands/Hexenbrett/3540449256", "https://www.metal-archives.com/albums/Hexenbrett/Dritte_Beschw%E2%80%8B%C3%B6%E2%80%8Brung%3A_Dem_Teufel_eine_Tochter/1280236", "Full-length", "Black/Heavy Metal")						,
+						Release::new("Hexenbrett", "Dritte Beschw\u{200B}ö\u{200B}rung: Dem Teufel eine Tochter").with_metallum("https://www.metal-archives.com/bands/Hexenbrett/3540449256", "https://www.metal-archives.com/albums/Hexenbrett/Dritte_Beschw%E2%80%8B%C3%B6%E2%80%8Brung%3A_Dem_Teufel_eine_Tochter/1280236", "Full-length", "Black/Heavy Metal")						,
 					]),
 					(27, vec![
 						Release::new("Bolvag", "Sad Dark Descent into the Dungeon Dream").with_metallum("https://www.metal-archives.com/bands/Bolvag/3540518676", "https://www.metal-archives.com/albums/Bolvag/Sad_Dark_Descent_into_the_Dungeon_Dream/1212539", "Demo", "Raw Black Metal/Ambient"),
@@ -1177,7 +1374,7 @@ mod tests {
     async fn test_2025_calendar_ok() -> Result<()> {
         let client = MockClient::new();
 
-        let got = scrape(&client, 2025).await?;
+        let got = scrape_years(&client, &[2025]).await?.remove(0);
 
         let want = Calendar {
             year: 2025,
@@ -1260,4 +1457,163 @@ mod tests {
         compare_calendars(got, want);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_scrape_band_country_ok() {
+        let got = scrape_band_country(
+            &MockClient::new(),
+            "https://www.metal-archives.com/bands/Wintersun/11023",
+        )
+        .await;
+
+        pretty_assertions::assert_eq!(got.ok(), Some(Some(String::from("Finland"))));
+    }
+
+    #[tokio::test]
+    async fn test_scrape_album_label_ok() {
+        let client = MockClient::new();
+
+        let got_signed = scrape_album_label(
+            &client,
+            "https://www.metal-archives.com/albums/Wintersun/Time_II/1233034",
+        )
+        .await;
+        let got_independent = scrape_album_label(
+            &client,
+            "https://www.metal-archives.com/albums/Dead_Rejoice/Demo/1280001",
+        )
+        .await;
+        let got_missing = scrape_album_label(
+            &client,
+            "https://www.metal-archives.com/albums/Nobody/Nothing/1",
+        )
+        .await;
+
+        pretty_assertions::assert_eq!(got_signed.ok(), Some(Some(String::from("Nuclear Blast"))));
+        pretty_assertions::assert_eq!(
+            got_independent.ok(),
+            Some(Some(String::from("Independent")))
+        );
+        assert!(got_missing.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scrape_album_cover_ok() {
+        let client = MockClient::new();
+
+        let got = scrape_album_cover(
+            &client,
+            "https://www.metal-archives.com/albums/Wintersun/Time_II/1233034",
+        )
+        .await;
+        let got_without = scrape_album_cover(
+            &client,
+            "https://www.metal-archives.com/albums/Dead_Rejoice/Demo/1280001",
+        )
+        .await;
+
+        pretty_assertions::assert_eq!(
+            got,
+            Some(String::from(
+                "https://www.metal-archives.com/images/1/2/3/3/1233034.jpg"
+            ))
+        );
+        pretty_assertions::assert_eq!(got_without, None);
+    }
+
+    #[test]
+    fn test_parse_album_details_ok() -> Result<()> {
+        let html = std::fs::read_to_string(
+            "./tests/testdata/metallum/pages/albums_Wintersun_Time_II_1233034.html",
+        )?;
+
+        let got = parse_album_details(&html);
+
+        let track = |position: i32, title: &str, duration: i32| AlbumTrack {
+            disc: 1,
+            position,
+            title: String::from(title),
+            duration: Some(duration),
+        };
+        pretty_assertions::assert_eq!(
+            got,
+            AlbumDetails {
+                format: Some(String::from("CD")),
+                label: Some(String::from("Nuclear Blast")),
+                tracks: vec![
+                    track(1, "Fields of Snow", 112),
+                    track(2, "The Way of the Fire", 740),
+                    track(3, "One with the Shadows", 496),
+                    track(4, "Ominous Clouds", 87),
+                    track(5, "Storm", 664),
+                    track(6, "Silver Leaves", 610),
+                ],
+                runtime: Some(2709),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_album_details_many_discs_ok() -> Result<()> {
+        let html = std::fs::read_to_string(
+            "./tests/testdata/metallum/pages/albums_Dark_Tranquillity_Projector_Remastered_1300001.html",
+        )?;
+
+        let got = parse_album_details(&html);
+
+        pretty_assertions::assert_eq!(
+            got,
+            AlbumDetails {
+                format: Some(String::from("2CD")),
+                label: Some(String::from("Century Media Records")),
+                tracks: vec![
+                    AlbumTrack {
+                        disc: 1,
+                        position: 1,
+                        title: String::from("FreeCard"),
+                        duration: Some(297),
+                    },
+                    AlbumTrack {
+                        disc: 1,
+                        position: 2,
+                        title: String::from("ThereIn"),
+                        duration: Some(378),
+                    },
+                    AlbumTrack {
+                        disc: 2,
+                        position: 1,
+                        title: String::from("Exposure (demo)"),
+                        duration: None,
+                    },
+                ],
+                runtime: None,
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_album_details_without_tracklist_ok() {
+        let got = parse_album_details("<html><body><div id=\"album_info\"></div></body></html>");
+
+        pretty_assertions::assert_eq!(
+            got,
+            AlbumDetails {
+                format: None,
+                label: None,
+                tracks: Vec::new(),
+                runtime: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_ok() {
+        pretty_assertions::assert_eq!(parse_duration("01:52"), Some(112));
+        pretty_assertions::assert_eq!(parse_duration("01:02:03"), Some(3723));
+        pretty_assertions::assert_eq!(parse_duration(""), None);
+        pretty_assertions::assert_eq!(parse_duration("52"), None);
+        pretty_assertions::assert_eq!(parse_duration("ab:cd"), None);
+    }
 }
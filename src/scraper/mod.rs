@@ -1,5 +1,9 @@
+pub mod bandcamp;
 pub mod client;
+pub mod cover;
 pub mod metallum;
+pub mod musicbrainz;
+pub mod source;
 pub mod wiki;
 
 #[cfg(test)]
@@ -0,0 +1,566 @@
+//! Reads the heavy metal releases of [MusicBrainz](https://musicbrainz.org).
+//!
+//! The releases are read from JSON, either from the files of a
+//! [JSON data dump](https://musicbrainz.org/doc/MusicBrainz_Database/Download) and web service
+//! responses saved to disk, or from a [web service](https://musicbrainz.org/doc/MusicBrainz_API/Search)
+//! such as a local mirror. Only the releases tagged with a metal genre are kept.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use reqwest::Url;
+use serde::Deserialize;
+use time::Date;
+use tracing::{error, info, warn};
+
+use crate::{
+    calendar::{Calendar, Release},
+    error::{Error, Result},
+};
+
+/// The tags a release, its release group or its artist must have one of, as
+/// part of the tag, e.g. `melodic death metal`.
+const METAL_TAGS: [&str; 7] = [
+    "metal",
+    "grindcore",
+    "deathcore",
+    "djent",
+    "doom",
+    "sludge",
+    "thrash",
+];
+
+/// Where the MusicBrainz JSON is read from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Location {
+    /// A JSON file or a directory of `.json` and `.jsonl` files.
+    Path(PathBuf),
+    /// The base URL of a web service, e.g. `https://musicbrainz.org/ws/2`.
+    Url(Url),
+}
+
+impl Location {
+    /// Parses a location, which is a URL when it starts with `http://` or `https://`.
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim();
+        match Url::parse(value) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => Location::Url(url),
+            _ => Location::Path(PathBuf::from(value)),
+        }
+    }
+}
+
+/// A page of releases returned by the search of the web service.
+#[derive(Deserialize)]
+struct ReleaseList {
+    #[serde(default)]
+    count: usize,
+    releases: Vec<MbRelease>,
+}
+
+/// A release as serialized by the web service and in the JSON data dumps.
+#[derive(Clone, Debug, Deserialize)]
+pub struct MbRelease {
+    pub title: String,
+    pub date: Option<String>,
+    pub status: Option<String>,
+    #[serde(rename = "release-group")]
+    pub release_group: Option<MbReleaseGroup>,
+    #[serde(rename = "artist-credit", default)]
+    pub artist_credit: Vec<MbArtistCredit>,
+    #[serde(default)]
+    pub tags: Vec<MbTag>,
+    #[serde(default)]
+    pub genres: Vec<MbTag>,
+    #[serde(rename = "label-info", default)]
+    pub label_info: Vec<MbLabelInfo>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MbReleaseGroup {
+    pub id: String,
+    #[serde(rename = "primary-type")]
+    pub primary_type: Option<String>,
+    #[serde(rename = "secondary-types", default)]
+    pub secondary_types: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<MbTag>,
+    #[serde(default)]
+    pub genres: Vec<MbTag>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MbArtistCredit {
+    pub name: String,
+    #[serde(default)]
+    pub joinphrase: String,
+    pub artist: MbArtist,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MbArtist {
+    pub id: String,
+    #[serde(default)]
+    pub tags: Vec<MbTag>,
+    #[serde(default)]
+    pub genres: Vec<MbTag>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MbTag {
+    pub name: String,
+    #[serde(default)]
+    pub count: i32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MbLabelInfo {
+    pub label: Option<MbLabel>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MbLabel {
+    pub name: String,
+}
+
+impl MbRelease {
+    /// Returns the release date when it is complete, i.e. `YYYY-MM-DD`.
+    fn release_date(&self) -> Option<Date> {
+        let mut parts = self.date.as_deref()?.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = time::Month::try_from(parts.next()?.parse::<u8>().ok()?).ok()?;
+        Date::from_calendar_date(year, month, parts.next()?.parse().ok()?).ok()
+    }
+
+    /// Returns the tags of the release, of its release group and of its artists.
+    fn all_tags(&self) -> impl Iterator<Item = &MbTag> {
+        let group = self
+            .release_group
+            .iter()
+            .flat_map(|group| group.tags.iter().chain(&group.genres));
+        let artists = self
+            .artist_credit
+            .iter()
+            .flat_map(|credit| credit.artist.tags.iter().chain(&credit.artist.genres));
+
+        self.tags
+            .iter()
+            .chain(&self.genres)
+            .chain(group)
+            .chain(artists)
+    }
+
+    /// Returns the most voted metal tag in title case, e.g. `Melodic Death Metal`.
+    fn metal_genre(&self) -> Option<String> {
+        self.all_tags()
+            .filter(|tag| is_metal(&tag.name))
+            .max_by_key(|tag| tag.count)
+            .map(|tag| title_case(&tag.name))
+    }
+
+    /// Returns the first record label releasing the release.
+    fn label(&self) -> Option<&str> {
+        self.label_info
+            .iter()
+            .filter_map(|info| info.label.as_ref())
+            .map(|label| label.name.as_str())
+            .find(|name| !name.is_empty() && *name != "[no label]")
+    }
+
+    /// Whether the release was officially released, bootlegs and the like excluded.
+    fn is_official(&self) -> bool {
+        self.status
+            .as_deref()
+            .is_none_or(|status| status.eq_ignore_ascii_case("official"))
+    }
+
+    /// Returns the credited artists as written on the release, e.g. `Amorphis / The Halo Effect`.
+    fn artist_name(&self) -> String {
+        self.artist_credit
+            .iter()
+            .map(|credit| format!("{}{}", credit.name, credit.joinphrase))
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+
+    /// Returns the type of release with the names used by The Metal Archives, e.g. `Full-length`.
+    fn release_type(&self) -> String {
+        let Some(group) = &self.release_group else {
+            return String::new();
+        };
+
+        let secondary = |name: &str| {
+            group
+                .secondary_types
+                .iter()
+                .any(|kind| kind.eq_ignore_ascii_case(name))
+        };
+
+        if secondary("Live") {
+            String::from("Live album")
+        } else if secondary("Compilation") {
+            String::from("Compilation")
+        } else if secondary("Demo") {
+            String::from("Demo")
+        } else {
+            match group.primary_type.as_deref() {
+                Some("Album") => String::from("Full-length"),
+                Some(other) => other.to_string(),
+                None => String::new(),
+            }
+        }
+    }
+}
+
+/// Whether the tag names a metal genre.
+fn is_metal(tag: &str) -> bool {
+    let tag = tag.to_lowercase();
+    METAL_TAGS.iter().any(|metal| tag.contains(metal))
+}
+
+fn title_case(value: &str) -> String {
+    value
+        .split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads the releases of the years from a JSON file or from every `.json`
+/// and `.jsonl` file of a directory.
+///
+/// A `.json` file holds a response of the web service, i.e. an object with a `releases`
+/// array, or a single release. A `.jsonl` file holds one release per line, like the
+/// data dumps, and is streamed so that large dumps are not loaded in memory.
+pub fn read_path(path: &Path, years: &[i32]) -> Result<Vec<MbRelease>> {
+    let files = if path.is_dir() {
+        let mut files = fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "json" || extension == "jsonl")
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut releases = Vec::new();
+    for file in files {
+        info!("Reading MusicBrainz releases from {}", file.display());
+
+        if file
+            .extension()
+            .is_some_and(|extension| extension == "jsonl")
+        {
+            for line in BufReader::new(fs::File::open(&file)?).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                match serde_json::from_str::<MbRelease>(&line) {
+                    Ok(release) if is_in(&release, years) => releases.push(release),
+                    Ok(_) => {}
+                    Err(err) => warn!("Skipping a release of {}: {err}", file.display()),
+                }
+            }
+        } else {
+            releases.extend(
+                parse_json(&fs::read(&file)?)?
+                    .into_iter()
+                    .filter(|release| is_in(release, years)),
+            );
+        }
+    }
+
+    Ok(releases)
+}
+
+/// Whether the release is dated in one of the years.
+fn is_in(release: &MbRelease, years: &[i32]) -> bool {
+    release
+        .release_date()
+        .is_some_and(|date| years.contains(&date.year()))
+}
+
+/// Parses a response of the web service or a single release.
+fn parse_json(bytes: &[u8]) -> Result<Vec<MbRelease>> {
+    if let Ok(list) = serde_json::from_slice::<ReleaseList>(bytes) {
+        return Ok(list.releases);
+    }
+
+    serde_json::from_slice::<MbRelease>(bytes)
+        .map(|release| vec![release])
+        .map_err(|err| {
+            error!("Failed to decode MusicBrainz JSON: {err}");
+            Error::ParseFail
+        })
+}
+
+/// Searches the web service for the metal releases of the year, page by page.
+///
+/// The pages are fetched a second apart to respect the
+/// [rate limit](https://musicbrainz.org/doc/MusicBrainz_API/Rate_Limiting) of MusicBrainz.
+pub async fn fetch_year(
+    http_client: &reqwest::Client,
+    base_url: &Url,
+    year: i32,
+) -> Result<Vec<MbRelease>> {
+    let endpoint = format!("{}/release", base_url.as_str().trim_end_matches('/'));
+    let query = format!("date:{year}* AND tag:metal");
+
+    let mut releases = Vec::new();
+    loop {
+        let offset = releases.len();
+        info!("Fetching MusicBrainz releases {offset} to {}", offset + 100);
+
+        let bytes = http_client
+            .get(&endpoint)
+            .header(
+                reqwest::header::USER_AGENT,
+                concat!("heavy-metal-notifier/", env!("CARGO_PKG_VERSION")),
+            )
+            .query(&[
+                ("query", query.as_str()),
+                ("fmt", "json"),
+                ("limit", "100"),
+                ("offset", &offset.to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        let page = serde_json::from_slice::<ReleaseList>(&bytes).map_err(|err| {
+            error!("Failed to decode MusicBrainz response: {err}; offset={offset}");
+            Error::ParseFail
+        })?;
+
+        let is_last = page.releases.is_empty() || offset + page.releases.len() >= page.count;
+        releases.extend(page.releases);
+        if is_last {
+            break;
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+
+    Ok(releases)
+}
+
+/// Sorts the official metal releases with a complete date into one calendar per year.
+///
+/// The editions of an album share a release group, of which only the earliest is kept.
+/// The calendars are returned in the same order as the years.
+pub fn to_calendars(releases: Vec<MbRelease>, years: &[i32]) -> Vec<Calendar> {
+    let mut earliest: HashMap<String, (Date, MbRelease)> = HashMap::new();
+
+    for release in releases {
+        let (Some(date), Some(group)) = (release.release_date(), &release.release_group) else {
+            continue;
+        };
+        if !years.contains(&date.year())
+            || !release.is_official()
+            || release.metal_genre().is_none()
+        {
+            continue;
+        }
+
+        let group_id = group.id.clone();
+        match earliest.get(&group_id) {
+            Some((kept, _)) if *kept <= date => {}
+            _ => {
+                earliest.insert(group_id, (date, release));
+            }
+        }
+    }
+
+    let mut earliest = earliest.into_iter().collect::<Vec<_>>();
+    earliest.sort_by(|(a_id, (a_date, _)), (b_id, (b_date, _))| {
+        a_date.cmp(b_date).then_with(|| a_id.cmp(b_id))
+    });
+
+    let mut calendars = years
+        .iter()
+        .map(|&year| Calendar::new(year))
+        .collect::<Vec<_>>();
+
+    for (group_id, (date, release)) in earliest {
+        let Some(calendar) = calendars
+            .iter_mut()
+            .find(|calendar| calendar.year == date.year())
+        else {
+            continue;
+        };
+
+        let artist_id = match release.artist_credit.as_slice() {
+            [credit] => Some(credit.artist.id.clone()),
+            _ => None,
+        };
+
+        let mut new_release = Release::new(release.artist_name(), &release.title).with_musicbrainz(
+            artist_id,
+            group_id,
+            release.release_type(),
+            release.metal_genre().unwrap_or_default(),
+        );
+        if let Some(label) = release.label() {
+            new_release = new_release.with_label(label);
+        }
+
+        calendar.add_release(date.month(), date.day(), new_release);
+    }
+
+    calendars
+}
+
+#[cfg(test)]
+mod tests {
+    use time::Month;
+
+    use super::*;
+
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[test]
+    fn test_location_parse_ok() -> Result<()> {
+        pretty_assertions::assert_eq!(
+            Location::parse("https://musicbrainz.org/ws/2"),
+            Location::Url(Url::parse("https://musicbrainz.org/ws/2")?)
+        );
+        pretty_assertions::assert_eq!(
+            Location::parse("./data/musicbrainz"),
+            Location::Path(PathBuf::from("./data/musicbrainz"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_path_file_ok() -> Result<()> {
+        let releases = read_path(
+            Path::new("./tests/testdata/musicbrainz/releases.json"),
+            &[2024],
+        )?;
+
+        let got = to_calendars(releases, &[2024]).remove(0);
+
+        let mut want = Calendar::new(2024);
+        want.add_release(
+            Month::August,
+            30,
+            Release::new("Wintersun", "Time II")
+                .with_musicbrainz(
+                    Some(String::from("9f6e5b9d-2f9a-4b0e-9d59-3e5a3c8f1a11")),
+                    "6d8d1f0c-4f3a-4d2c-8f0b-1b2c3d4e5f60",
+                    "Full-length",
+                    "Melodic Death Metal",
+                )
+                .with_label("Nuclear Blast"),
+        );
+        want.add_release(
+            Month::October,
+            4,
+            Release::new("Blood Incantation", "Live at Roadburn").with_musicbrainz(
+                Some(String::from("3c2f1e0d-9b8a-4c7d-8e6f-5a4b3c2d1e0f")),
+                "a1b2c3d4-e5f6-4a7b-8c9d-0e1f2a3b4c5d",
+                "Live album",
+                "Death Metal",
+            ),
+        );
+        pretty_assertions::assert_eq!(got, want);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_path_directory_ok() -> Result<()> {
+        let releases = read_path(Path::new("./tests/testdata/musicbrainz"), &[2024, 2025])?;
+
+        let got = to_calendars(releases, &[2024, 2025]);
+
+        pretty_assertions::assert_eq!(got[0].len(), 2);
+        pretty_assertions::assert_eq!(
+            got[1].get_releases(Month::May, 9),
+            Some(&vec![
+                Release::new("Amorphis / The Halo Effect", "Northern Lights").with_musicbrainz(
+                    None,
+                    "f0e1d2c3-b4a5-4968-8776-655443322110",
+                    "EP",
+                    "Melodic Death Metal",
+                )
+            ])
+        );
+        pretty_assertions::assert_eq!(got[1].len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_year_ok() -> Result<()> {
+        let base_url = serve_testdata().await?;
+
+        let releases = fetch_year(&reqwest::Client::new(), &base_url, 2024).await?;
+
+        pretty_assertions::assert_eq!(releases.len(), 7);
+        pretty_assertions::assert_eq!(to_calendars(releases, &[2024])[0].len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_release_type_ok() {
+        let release = |primary: &str, secondary: &[&str]| MbRelease {
+            title: String::from("Album"),
+            date: None,
+            status: None,
+            release_group: Some(MbReleaseGroup {
+                id: String::from("1"),
+                primary_type: Some(primary.to_string()),
+                secondary_types: secondary.iter().map(|s| s.to_string()).collect(),
+                tags: Vec::new(),
+                genres: Vec::new(),
+            }),
+            artist_credit: Vec::new(),
+            tags: Vec::new(),
+            genres: Vec::new(),
+            label_info: Vec::new(),
+        };
+
+        pretty_assertions::assert_eq!(release("Album", &[]).release_type(), "Full-length");
+        pretty_assertions::assert_eq!(release("EP", &[]).release_type(), "EP");
+        pretty_assertions::assert_eq!(release("Album", &["Live"]).release_type(), "Live album");
+        pretty_assertions::assert_eq!(
+            release("Album", &["Compilation"]).release_type(),
+            "Compilation"
+        );
+    }
+
+    /// Serves the saved web service response on a local port, standing in for MusicBrainz.
+    async fn serve_testdata() -> Result<Url> {
+        let body = fs::read_to_string("./tests/testdata/musicbrainz/releases.json")?;
+        let app = axum::Router::new().route(
+            "/ws/2/release",
+            axum::routing::get(move || async move {
+                ([(reqwest::header::CONTENT_TYPE, "application/json")], body)
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        Ok(Url::parse(&format!("http://{addr}/ws/2"))?)
+    }
+}
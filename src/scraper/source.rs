@@ -0,0 +1,318 @@
+//! The sources of heavy metal releases the calendar is built from.
+//!
+//! Each source implements [`ReleaseSource`] and is registered in the [`SourceRegistry`].
+//! A new source is added by implementing the trait and registering it in
+//! [`SourceRegistry::with_defaults`].
+
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use super::{
+    client::{MainClient, MetallumClient, WikiClient},
+    metallum,
+    musicbrainz::{self, Location},
+    wiki,
+};
+use crate::{
+    calendar::Calendar,
+    config::config,
+    error::{Error, Result},
+};
+
+/// What a release source can be asked for.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capabilities {
+    /// Whether the source lists the releases of the current and next years.
+    pub upcoming: bool,
+    /// Whether the source lists the releases of past years, to backfill the archive.
+    pub past_years: bool,
+}
+
+/// A source of heavy metal releases, e.g. Wikipedia or The Metal Archives.
+#[async_trait]
+pub trait ReleaseSource: Send + Sync {
+    /// Returns the unique name of the source, e.g. `wikipedia`.
+    ///
+    /// The name identifies the source in the configuration and in the database.
+    fn name(&self) -> &'static str;
+
+    /// Returns what the source can be asked for.
+    fn capabilities(&self) -> Capabilities;
+
+    /// Fetches the calendar of each of the given years.
+    ///
+    /// The years are fetched together because some sources list the releases of
+    /// every year at once.
+    async fn fetch(&self, years: &[i32]) -> Result<Vec<Calendar>>;
+}
+
+/// Holds the release sources in the order their calendars are merged.
+///
+/// When two sources list the same release with as much metadata, the one of the
+/// source registered first is kept.
+#[derive(Default)]
+pub struct SourceRegistry {
+    sources: Vec<Arc<dyn ReleaseSource>>,
+}
+
+impl SourceRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with every source the application supports.
+    ///
+    /// The MusicBrainz source is only registered when `MUSICBRAINZ_LOCATION` is set.
+    pub fn with_defaults() -> Self {
+        let client = Arc::new(MainClient::new(reqwest::Client::new()));
+
+        let registry = Self::new()
+            .register(MetallumSource::new(client.clone()))
+            .register(WikipediaSource::new(client));
+
+        match &config().MUSICBRAINZ_LOCATION {
+            Some(location) => registry.register(MusicBrainzSource::new(Location::parse(location))),
+            None => registry,
+        }
+    }
+
+    /// Adds a source to the registry.
+    pub fn register(mut self, source: impl ReleaseSource + 'static) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    /// Returns the names of every registered source.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.sources.iter().map(|source| source.name()).collect()
+    }
+
+    /// Returns the sources with the given names, ignoring the case, even if they are
+    /// disabled in the configuration.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a name is not the one of a registered source.
+    pub fn named(&self, names: &[String]) -> Result<Vec<Arc<dyn ReleaseSource>>> {
+        names
+            .iter()
+            .map(|name| {
+                self.sources
+                    .iter()
+                    .find(|source| source.name().eq_ignore_ascii_case(name))
+                    .cloned()
+                    .ok_or_else(|| Error::EntityNotFoundByName {
+                        entity: "source",
+                        name: name.clone(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the sources that are not disabled in the configuration.
+    pub fn enabled(&self) -> Vec<Arc<dyn ReleaseSource>> {
+        self.sources
+            .iter()
+            .filter(|source| is_enabled(source.name(), &config().DISABLED_SOURCES))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Whether the source is absent from the list of disabled sources, ignoring the case.
+fn is_enabled(name: &str, disabled: &[String]) -> bool {
+    !disabled
+        .iter()
+        .any(|disabled| disabled.eq_ignore_ascii_case(name))
+}
+
+/// Lists the upcoming releases of [The Metal Archives](https://www.metal-archives.com/release/upcoming).
+pub struct MetallumSource<C> {
+    client: Arc<C>,
+}
+
+impl<C> MetallumSource<C> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl<C> ReleaseSource for MetallumSource<C>
+where
+    C: MetallumClient + Send + Sync,
+{
+    fn name(&self) -> &'static str {
+        "metallum"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            upcoming: true,
+            past_years: false,
+        }
+    }
+
+    async fn fetch(&self, years: &[i32]) -> Result<Vec<Calendar>> {
+        metallum::scrape_years(self.client.as_ref(), years).await
+    }
+}
+
+/// Lists the releases of the yearly [Wikipedia](https://en.wikipedia.org/wiki/2024_in_heavy_metal_music) pages.
+pub struct WikipediaSource<C> {
+    client: Arc<C>,
+}
+
+impl<C> WikipediaSource<C> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl<C> ReleaseSource for WikipediaSource<C>
+where
+    C: WikiClient + Send + Sync,
+{
+    fn name(&self) -> &'static str {
+        "wikipedia"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            upcoming: true,
+            past_years: true,
+        }
+    }
+
+    async fn fetch(&self, years: &[i32]) -> Result<Vec<Calendar>> {
+        let mut calendars = Vec::with_capacity(years.len());
+        for &year in years {
+            calendars.push(wiki::scrape(self.client.as_ref(), year).await?);
+        }
+        Ok(calendars)
+    }
+}
+
+/// Lists the metal releases of [MusicBrainz](https://musicbrainz.org), read from JSON files
+/// or from a web service.
+pub struct MusicBrainzSource {
+    location: Location,
+    http_client: reqwest::Client,
+}
+
+impl MusicBrainzSource {
+    pub fn new(location: Location) -> Self {
+        Self {
+            location,
+            http_client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl ReleaseSource for MusicBrainzSource {
+    fn name(&self) -> &'static str {
+        "musicbrainz"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            upcoming: true,
+            past_years: true,
+        }
+    }
+
+    async fn fetch(&self, years: &[i32]) -> Result<Vec<Calendar>> {
+        let releases = match &self.location {
+            Location::Path(path) => {
+                let path = path.clone();
+                let years = years.to_vec();
+                tokio::task::spawn_blocking(move || musicbrainz::read_path(&path, &years))
+                    .await
+                    .map_err(|_| Error::ScraperFail)??
+            }
+            Location::Url(url) => {
+                let mut releases = Vec::new();
+                for &year in years {
+                    releases.extend(musicbrainz::fetch_year(&self.http_client, url, year).await?);
+                }
+                releases
+            }
+        };
+
+        Ok(musicbrainz::to_calendars(releases, years))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::scraper::client::tests::MockClient;
+
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[test]
+    fn test_is_enabled_ok() {
+        let disabled = vec![String::from("Wikipedia")];
+
+        assert!(!is_enabled("wikipedia", &disabled));
+        assert!(is_enabled("metallum", &disabled));
+        assert!(is_enabled("wikipedia", &[]));
+    }
+
+    #[test]
+    fn test_registry_keeps_order_ok() {
+        let client = Arc::new(MockClient::new());
+
+        let registry = SourceRegistry::new()
+            .register(MetallumSource::new(client.clone()))
+            .register(WikipediaSource::new(client));
+
+        pretty_assertions::assert_eq!(registry.names(), vec!["metallum", "wikipedia"]);
+    }
+
+    #[test]
+    fn test_registry_named_ok() -> Result<()> {
+        let client = Arc::new(MockClient::new());
+        let registry = SourceRegistry::new()
+            .register(MetallumSource::new(client.clone()))
+            .register(WikipediaSource::new(client));
+
+        let got = registry.named(&[String::from("Wikipedia")])?;
+
+        pretty_assertions::assert_eq!(
+            got.iter().map(|source| source.name()).collect::<Vec<_>>(),
+            vec!["wikipedia"]
+        );
+        assert!(registry.named(&[String::from("discogs")]).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_metallum_source_fetch_ok() -> Result<()> {
+        let source = MetallumSource::new(Arc::new(MockClient::new()));
+
+        let got = source.fetch(&[2024, 2025]).await?;
+
+        pretty_assertions::assert_eq!(got.len(), 2);
+        pretty_assertions::assert_eq!(got[0].year, 2024);
+        pretty_assertions::assert_eq!(got[1].year, 2025);
+        assert!(!got[0].is_empty() || !got[1].is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_musicbrainz_source_fetch_ok() -> Result<()> {
+        let source = MusicBrainzSource::new(Location::parse("./tests/testdata/musicbrainz"));
+
+        let got = source.fetch(&[2025]).await?;
+
+        pretty_assertions::assert_eq!(got.len(), 1);
+        pretty_assertions::assert_eq!(got[0].len(), 2);
+        Ok(())
+    }
+}
@@ -9,9 +9,9 @@ use crate::{
     error::Result,
 };
 
-use super::client::Client;
+use super::client::WikiClient;
 
-pub async fn scrape(client: &impl Client, year: i32) -> Result<Calendar> {
+pub async fn scrape(client: &impl WikiClient, year: i32) -> Result<Calendar> {
     info!("Scraping Wikipedia");
     let doc = client.get_calendar(year).await?;
     Ok(extract_calendar(doc, year))
@@ -73,6 +73,11 @@ fn extract_calendar(doc: Html, year: i32) -> Calendar {
     calendar
 }
 
+/// Adds the releases listed in a month's table to the calendar.
+///
+/// The day and artist cells span the rows of the releases they share, so a row
+/// only holds the rightmost cells of the table. The columns following the album,
+/// e.g. the record label, are thus read from the end of the row.
 fn process_table(
     table: ElementRef,
     calendar: &mut Calendar,
@@ -80,16 +85,32 @@ fn process_table(
     current_day: &mut u8,
     current_artist: &mut String,
 ) {
+    let columns = trailing_columns(table);
+    let label_column = columns
+        .iter()
+        .position(|column| column.to_lowercase().starts_with("label"));
+
     let selector = &Selector::parse("tbody tr").unwrap();
     table.select(selector).for_each(|row| {
-        let cells = row.child_elements().collect::<Vec<_>>();
+        let mut cells = row.child_elements().collect::<Vec<_>>();
+        if cells.len() <= columns.len() {
+            return;
+        }
+        let trailing = cells.split_off(cells.len() - columns.len());
+
+        let label = label_column.map(|index| clean_text(&trailing[index]));
+        let new_release = |artist: &str, album: &str| match &label {
+            Some(label) => Release::new(artist, album).with_label(label),
+            None => Release::new(artist, album),
+        };
+
         match cells.len() {
             1 => {
                 let album = cells[0].text().collect::<String>();
                 calendar.add_release(
                     month,
                     *current_day,
-                    Release::new(current_artist.clone(), album.trim()),
+                    new_release(current_artist, album.trim()),
                 )
             }
             2 => {
@@ -100,7 +121,7 @@ fn process_table(
                 let album = cells[1].text().collect::<String>();
                 let album = album.trim();
 
-                calendar.add_release(month, *current_day, Release::new(artist, album))
+                calendar.add_release(month, *current_day, new_release(artist, album))
             }
             3 => {
                 let day: core::result::Result<u8, _> =
@@ -117,7 +138,7 @@ fn process_table(
                 let album = album.trim();
 
                 if artist != "Artist" {
-                    calendar.add_release(month, *current_day, Release::new(artist, album.trim()));
+                    calendar.add_release(month, *current_day, new_release(artist, album.trim()));
                 }
             }
             _ => {}
@@ -125,6 +146,43 @@ fn process_table(
     })
 }
 
+/// Returns the names of the header columns following the album column, if any.
+fn trailing_columns(table: ElementRef) -> Vec<String> {
+    let selector = &Selector::parse("tr").unwrap();
+    let Some(header) = table.select(selector).find(|row| {
+        let mut cells = row.child_elements().peekable();
+        cells.peek().is_some() && cells.all(|cell| cell.value().name() == "th")
+    }) else {
+        return Vec::new();
+    };
+    let headers = header
+        .child_elements()
+        .map(|th| clean_text(&th))
+        .collect::<Vec<_>>();
+
+    match headers.iter().position(|header| header == "Album") {
+        Some(index) => headers[index + 1..].to_vec(),
+        None => Vec::new(),
+    }
+}
+
+/// Returns the text of a cell without its footnote references, e.g. `[12]`.
+fn clean_text(cell: &ElementRef) -> String {
+    let text = cell.text().collect::<String>();
+
+    let mut cleaned = String::new();
+    let mut depth = 0;
+    for c in text.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth > 0 => depth -= 1,
+            _ if depth == 0 => cleaned.push(c),
+            _ => {}
+        }
+    }
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,6 +194,29 @@ mod tests {
 
     type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
 
+    #[test]
+    fn test_extract_calendar_with_labels_ok() {
+        let doc = Html::parse_document(
+            r#"<table id="table_March"><tbody>
+                <tr><th>Day</th><th>Artist</th><th>Album</th><th>Label</th></tr>
+                <tr><td rowspan="3">28</td><td rowspan="2">Arch Enemy</td><td>Blood Dynasty</td><td>Century Media<sup>[12]</sup></td></tr>
+                <tr><td>Blood Dynasty (Live)</td><td>Century Media</td></tr>
+                <tr><td>Wintersun</td><td>Time II</td><td>Nuclear Blast</td></tr>
+            </tbody></table>"#,
+        );
+
+        let got = extract_calendar(doc, 2025);
+
+        pretty_assertions::assert_eq!(
+            got.get_releases(Month::March, 28),
+            Some(&vec![
+                Release::new("Arch Enemy", "Blood Dynasty").with_label("Century Media"),
+                Release::new("Arch Enemy", "Blood Dynasty (Live)").with_label("Century Media"),
+                Release::new("Wintersun", "Time II").with_label("Nuclear Blast"),
+            ])
+        );
+    }
+
     #[tokio::test]
     async fn test_2022_calendar_ok() -> Result<()> {
         let client = MockClient::new();
@@ -1,7 +1,13 @@
-use crate::config::SmtpConfig;
+use crate::{
+    config::SmtpConfig,
+    error::{Error, Result},
+};
 use lettre::{
     Message, SmtpTransport, Transport,
-    message::{Mailbox, header::ContentType},
+    message::{
+        Mailbox,
+        header::{ContentType, Header, HeaderName, HeaderValue},
+    },
     transport::smtp::authentication::Credentials,
 };
 use tracing::error;
@@ -64,3 +70,98 @@ pub fn send_email(smtp_config: &SmtpConfig, from: String, body: impl Into<String
         error!("Send email failed: {:?}", err);
     }
 }
+
+/// Sends an HTML email from the SMTP user to the recipient.
+///
+/// When an unsubscribe URL is given, the `List-Unsubscribe` and `List-Unsubscribe-Post`
+/// headers are set so that email clients can offer one-click unsubscription (RFC 8058).
+pub fn send_html_email(
+    smtp_config: &SmtpConfig,
+    to: &str,
+    subject: &str,
+    html: String,
+    unsubscribe_url: Option<&str>,
+) -> Result<()> {
+    let from: Mailbox = format!("Heavy Metal Releases <{}>", smtp_config.username)
+        .parse()
+        .map_err(|err| {
+            error!("Could not parse the SMTP username as the sender: {:?}", err);
+            Error::EmailFail
+        })?;
+
+    let to: Mailbox = to.parse().map_err(|err| {
+        error!("Could not parse the recipient {to}: {:?}", err);
+        Error::EmailFail
+    })?;
+
+    let mut builder = Message::builder()
+        .from(from)
+        .to(to)
+        .subject(subject)
+        .header(ContentType::TEXT_HTML);
+
+    if let Some(url) = unsubscribe_url {
+        builder = builder
+            .header(ListUnsubscribe(format!("<{url}>")))
+            .header(ListUnsubscribePost);
+    }
+
+    let email = builder.body(html).map_err(|err| {
+        error!("Build email failed: {:?}", err);
+        Error::EmailFail
+    })?;
+
+    let mailer = SmtpTransport::relay(&smtp_config.relay)
+        .map_err(|err| {
+            error!("Failed to set up relay {}: {:?}", smtp_config.relay, err);
+            Error::EmailFail
+        })?
+        .credentials(Credentials::new(
+            smtp_config.username.clone(),
+            smtp_config.password.clone(),
+        ))
+        .build();
+
+    mailer.send(&email).map_err(|err| {
+        error!("Send email failed: {:?}", err);
+        Error::EmailFail
+    })?;
+
+    Ok(())
+}
+
+/// The `List-Unsubscribe` header holding the unsubscribe URL between angle brackets.
+#[derive(Clone)]
+struct ListUnsubscribe(String);
+
+impl Header for ListUnsubscribe {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("List-Unsubscribe")
+    }
+
+    fn parse(s: &str) -> core::result::Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self(s.to_string()))
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), self.0.clone())
+    }
+}
+
+/// The `List-Unsubscribe-Post` header signaling one-click unsubscription support.
+#[derive(Clone)]
+struct ListUnsubscribePost;
+
+impl Header for ListUnsubscribePost {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("List-Unsubscribe-Post")
+    }
+
+    fn parse(_s: &str) -> core::result::Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self)
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), String::from("List-Unsubscribe=One-Click"))
+    }
+}
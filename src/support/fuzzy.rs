@@ -0,0 +1,121 @@
+//! Helpers to recognize the same artist or album spelled differently by the sources.
+
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+/// Normalizes a name for comparison.
+///
+/// The name is lowercased, its diacritics and ligatures are folded, `&` becomes `and`
+/// and any other punctuation becomes a space, e.g. `motorhead and friends` for
+/// "Motörhead & Friends!".
+pub fn normalize(name: &str) -> String {
+    let mut folded = String::with_capacity(name.len());
+
+    for c in name
+        .nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+    {
+        match c {
+            '&' => folded.push_str(" and "),
+            'æ' => folded.push_str("ae"),
+            'œ' => folded.push_str("oe"),
+            'ß' => folded.push_str("ss"),
+            'þ' => folded.push_str("th"),
+            'ø' => folded.push('o'),
+            'ł' => folded.push('l'),
+            'đ' | 'ð' => folded.push('d'),
+            c if c.is_alphanumeric() => folded.push(c),
+            _ => folded.push(' '),
+        }
+    }
+
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Normalizes an artist name for comparison.
+///
+/// On top of `normalize`, the leading "The" is dropped and the artists of a
+/// split release, separated by " / ", are sorted, so that "The Halo Effect / Amorphis"
+/// and "Amorphis / Halo Effect" give the same key.
+pub fn artist_key(artist: &str) -> String {
+    let mut artists = artist
+        .split(" / ")
+        .map(|part| {
+            let part = normalize(part);
+            match part.strip_prefix("the ") {
+                Some(rest) => rest.to_string(),
+                None => part,
+            }
+        })
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>();
+
+    artists.sort_unstable();
+    artists.join(" / ")
+}
+
+/// Whether two normalized names are close enough to be the same one.
+///
+/// The number of edits allowed grows with the length of the names: none up
+/// to 5 characters, one up to 12 and two beyond. Names whose numbers differ,
+/// e.g. "Time I" and "Time II", are never similar.
+pub fn is_similar(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+
+    let (len_a, len_b) = (a.chars().count(), b.chars().count());
+    let max_edits = match len_a.max(len_b) {
+        0..=5 => return false,
+        6..=12 => 1,
+        _ => 2,
+    };
+
+    len_a.abs_diff(len_b) <= max_edits
+        && numbers(a).eq(numbers(b))
+        && strsim::levenshtein(a, b) <= max_edits
+}
+
+/// Iterates over the words of a normalized name that are numbers or roman numerals.
+fn numbers(name: &str) -> impl Iterator<Item = &str> {
+    const ROMAN_NUMERALS: [&str; 10] =
+        ["i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x"];
+
+    name.split(' ')
+        .filter(|word| word.chars().all(|c| c.is_ascii_digit()) || ROMAN_NUMERALS.contains(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_ok() {
+        pretty_assertions::assert_eq!(normalize("Motörhead & Friends!"), "motorhead and friends");
+        pretty_assertions::assert_eq!(normalize("  Blood   Dynasty "), "blood dynasty");
+        pretty_assertions::assert_eq!(
+            normalize("Sólstafir – Hin helga kvöl"),
+            "solstafir hin helga kvol"
+        );
+    }
+
+    #[test]
+    fn test_artist_key_ok() {
+        pretty_assertions::assert_eq!(artist_key("The Halo Effect"), "halo effect");
+        pretty_assertions::assert_eq!(
+            artist_key("The Halo Effect / Amorphis"),
+            artist_key("Amorphis / Halo Effect")
+        );
+        pretty_assertions::assert_eq!(artist_key("Theory in Practice"), "theory in practice");
+    }
+
+    #[test]
+    fn test_is_similar_ok() {
+        assert!(is_similar("scorched earth", "scorched erth"));
+        assert!(is_similar("harakiri for the sky", "harakiri for teh sky"));
+        assert!(!is_similar("birna", "birka"));
+        assert!(!is_similar("time i", "time ii"));
+        assert!(!is_similar("blood dynasty", "blood dynasty ii"));
+        assert!(!is_similar("part 1", "part 2"));
+    }
+}
@@ -0,0 +1,74 @@
+//! Helpers to write [RFC 5545](https://datatracker.ietf.org/doc/html/rfc5545) iCalendar content.
+
+/// Escapes a TEXT property value.
+///
+/// Backslashes, semicolons and commas are escaped and newlines are replaced
+/// by the `\n` sequence.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Folds a content line so that no line is longer than 75 octets, then
+/// terminates it with CRLF.
+///
+/// Continuation lines start with a single space. Lines are never split in the
+/// middle of a multi-byte character.
+pub fn fold(line: &str) -> String {
+    const MAX_OCTETS: usize = 75;
+
+    let mut folded = String::with_capacity(line.len() + line.len() / MAX_OCTETS * 3 + 2);
+    let mut num_octets = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if num_octets + len > MAX_OCTETS {
+            folded.push_str("\r\n ");
+            num_octets = 1;
+        }
+        folded.push(c);
+        num_octets += len;
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_ok() {
+        let got = escape("Death, Doom; Black\\Thrash\nMetal");
+
+        pretty_assertions::assert_eq!(got, "Death\\, Doom\\; Black\\\\Thrash\\nMetal");
+    }
+
+    #[test]
+    fn test_fold_short_line_ok() {
+        let got = fold("SUMMARY:Wintersun - Time II");
+
+        pretty_assertions::assert_eq!(got, "SUMMARY:Wintersun - Time II\r\n");
+    }
+
+    #[test]
+    fn test_fold_long_line_ok() {
+        let line = format!("DESCRIPTION:{}", "ö".repeat(80));
+
+        let got = fold(&line);
+
+        let lines = got.split("\r\n").collect::<Vec<_>>();
+        pretty_assertions::assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|line| line.len() <= 75));
+        pretty_assertions::assert_eq!(got.replace("\r\n ", ""), format!("{line}\r\n"));
+    }
+}
@@ -1,2 +1,5 @@
 pub mod email;
 pub mod env;
+pub mod fuzzy;
+pub mod ical;
+pub mod token;
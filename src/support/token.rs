@@ -0,0 +1,132 @@
+//! Signed tokens embedded in the links sent by email.
+//!
+//! A token is the hex-encoded HMAC-SHA256 of the purpose of the link, the ID
+//! of the subscriber or webhook and its address, i.e. the email or the URL.
+//! Changing any of them invalidates it.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The purpose of a link sent by email.
+#[derive(Clone, Copy, Debug)]
+pub enum Purpose {
+    Confirm,
+    Unsubscribe,
+    /// The link removing a webhook.
+    RemoveWebhook,
+    /// The secret with which the payloads of a generic JSON webhook are signed.
+    WebhookSecret,
+}
+
+impl Purpose {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Purpose::Confirm => "confirm",
+            Purpose::Unsubscribe => "unsubscribe",
+            Purpose::RemoveWebhook => "remove-webhook",
+            Purpose::WebhookSecret => "webhook-secret",
+        }
+    }
+}
+
+/// Signs the ID and address for the given purpose.
+pub fn sign(key: &str, purpose: Purpose, id: i32, address: &str) -> String {
+    sign_bytes(key, message(purpose, id, address).as_bytes())
+}
+
+/// Verifies, in constant time, that the token was signed with the key for the
+/// given purpose, ID and address.
+pub fn verify(key: &str, purpose: Purpose, id: i32, address: &str, token: &str) -> bool {
+    match hex::decode(token) {
+        Ok(bytes) => mac(key, message(purpose, id, address).as_bytes())
+            .verify_slice(&bytes)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Returns the hex-encoded HMAC-SHA256 of the data.
+pub fn sign_bytes(key: &str, data: &[u8]) -> String {
+    hex::encode(mac(key, data).finalize().into_bytes())
+}
+
+/// Compares a secret, e.g. a password, with the expected one in constant time.
+pub fn secrets_match(expected: &str, given: &str) -> bool {
+    let given = mac(expected, given.as_bytes()).finalize().into_bytes();
+    mac(expected, expected.as_bytes())
+        .verify_slice(&given)
+        .is_ok()
+}
+
+fn message(purpose: Purpose, id: i32, address: &str) -> String {
+    format!("{}:{id}:{address}", purpose.as_str())
+}
+
+fn mac(key: &str, data: &[u8]) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(key.as_bytes()).expect("HMAC can take a key of any size");
+    mac.update(data);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "a secret key";
+
+    #[test]
+    fn test_secrets_match_ok() {
+        assert!(secrets_match("hunter2", "hunter2"));
+        assert!(!secrets_match("hunter2", "hunter3"));
+        assert!(!secrets_match("hunter2", ""));
+    }
+
+    #[test]
+    fn test_sign_and_verify_ok() {
+        let token = sign(KEY, Purpose::Confirm, 1, "metal@head.com");
+
+        assert!(verify(KEY, Purpose::Confirm, 1, "metal@head.com", &token));
+    }
+
+    #[test]
+    fn test_verify_tampered_token_fails() {
+        let token = sign(KEY, Purpose::Confirm, 1, "metal@head.com");
+
+        assert!(!verify(
+            KEY,
+            Purpose::Unsubscribe,
+            1,
+            "metal@head.com",
+            &token
+        ));
+        assert!(!verify(KEY, Purpose::Confirm, 2, "metal@head.com", &token));
+        assert!(!verify(KEY, Purpose::Confirm, 1, "other@head.com", &token));
+        assert!(!verify(
+            "another key",
+            Purpose::Confirm,
+            1,
+            "metal@head.com",
+            &token
+        ));
+        assert!(!verify(
+            KEY,
+            Purpose::Confirm,
+            1,
+            "metal@head.com",
+            "not hex"
+        ));
+    }
+
+    #[test]
+    fn test_sign_bytes_ok() {
+        let got = sign_bytes("Jefe", b"what do ya want for nothing?");
+
+        pretty_assertions::assert_eq!(
+            got,
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -0,0 +1,441 @@
+use axum::{
+    Form, Router,
+    extract::{Path, Query, Request, State},
+    http::{HeaderMap, Method, StatusCode, header::WWW_AUTHENTICATE},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use axum_extra::{
+    TypedHeader,
+    headers::{Authorization, authorization::Basic},
+};
+use maud::html;
+use serde::Deserialize;
+use tracing::{error, info};
+
+use super::templates::admin::{
+    artist, dashboard, jobs, message, new_release, release, search_results,
+};
+use crate::{
+    config::config,
+    jobs::{self as scheduled, ScheduledJob},
+    model::{ArtistEdit, NewRelease},
+    parse_date,
+    support::token,
+    web::{AppState, valid_link},
+};
+
+/// The number of runs listed by the jobs page.
+const MAX_JOB_RUNS: i64 = 100;
+
+/// The number of releases listed by the search of the dashboard.
+const MAX_SEARCH_RESULTS: i64 = 25;
+
+/// Defines the routes of the administration pages, which require the administrator password.
+pub fn routes_admin() -> Router<AppState> {
+    Router::new()
+        .route("/", get(dashboard_handler))
+        .route("/jobs", get(jobs_handler))
+        .route("/jobs/:name/run", post(run_job_handler))
+        .route("/search", get(search_handler))
+        .route(
+            "/artists/:id",
+            get(artist_handler).post(edit_artist_handler),
+        )
+        .route(
+            "/releases/new",
+            get(new_release_handler).post(add_release_handler),
+        )
+        .route("/releases/:id", get(release_handler))
+        .route("/releases/:id/hide", post(hide_release_handler))
+        .route("/releases/:id/show", post(show_release_handler))
+        .route("/releases/:id/merge", post(merge_release_handler))
+        .route_layer(middleware::from_fn(require_admin))
+}
+
+/// Lets the administrators through with their password given by HTTP basic authentication,
+/// under any user name.
+///
+/// The pages are not found when no administrator password is set. The forms are sent by
+/// HTMX, so the other requests than `GET` must carry its `HX-Request` header, which another
+/// website cannot add to the requests of the browser it makes on behalf of an administrator.
+async fn require_admin(
+    authorization: Option<TypedHeader<Authorization<Basic>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(password) = config().ADMIN_PASSWORD.as_deref() else {
+        return (StatusCode::NOT_FOUND, "404 Not Found").into_response();
+    };
+
+    match authorization {
+        Some(TypedHeader(Authorization(credentials)))
+            if token::secrets_match(password, credentials.password()) =>
+        {
+            if request.method() != Method::GET && !request.headers().contains_key("HX-Request") {
+                return (StatusCode::FORBIDDEN, "403 Forbidden").into_response();
+            }
+            next.run(request).await
+        }
+        _ => (
+            StatusCode::UNAUTHORIZED,
+            [(
+                WWW_AUTHENTICATE,
+                r#"Basic realm="Administration", charset="UTF-8""#,
+            )],
+            "401 Unauthorized",
+        )
+            .into_response(),
+    }
+}
+
+/// Represents the search of the dashboard.
+#[derive(Deserialize)]
+struct SearchQuery {
+    #[serde(default)]
+    q: String,
+}
+
+/// Represents the form correcting an artist, where empty fields clear the values.
+#[derive(Deserialize)]
+struct ArtistForm {
+    genre: String,
+    url_bandcamp: String,
+    url_metallum: String,
+}
+
+/// Represents the release kept when merging a duplicate into it.
+#[derive(Deserialize)]
+struct MergeForm {
+    into: i32,
+}
+
+/// Represents the form adding a release by hand, with its date as `YYYY-MM-DD`.
+#[derive(Deserialize)]
+struct NewReleaseForm {
+    artist: String,
+    #[serde(default)]
+    genre: String,
+    album: String,
+    date: String,
+    #[serde(default)]
+    release_type: String,
+    #[serde(default)]
+    label: String,
+}
+
+async fn dashboard_handler(headers: HeaderMap, State(state): State<AppState>) -> impl IntoResponse {
+    let runs = state.job_run_repo.recent(MAX_JOB_RUNS);
+    let health = state.source_health_repo.all();
+    let corrections = state.entities_repo.corrected_releases();
+
+    match (runs, health, corrections) {
+        (Ok(runs), Ok(health), Ok(corrections)) => {
+            dashboard(&runs, &health, &corrections, headers).into_response()
+        }
+        (Err(err), _, _) | (_, Err(err), _) | (_, _, Err(err)) => {
+            error!("Error fetching the dashboard: {err}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn jobs_handler(headers: HeaderMap, State(state): State<AppState>) -> impl IntoResponse {
+    match state.job_run_repo.recent(MAX_JOB_RUNS) {
+        Ok(runs) => jobs(&runs, headers).into_response(),
+        Err(err) => {
+            error!("Error fetching the job runs: {err}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Starts the job in the background, unless it is already running.
+async fn run_job_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    let job = match name.parse::<ScheduledJob>() {
+        Ok(job) if job.can_run_on_demand() => job,
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                message(html!("This job cannot be run on demand.")),
+            );
+        }
+    };
+
+    if job.is_running() {
+        return (
+            StatusCode::CONFLICT,
+            message(html!("The " (job.name()) " job is already running.")),
+        );
+    }
+
+    info!("An administrator started the {} job", job.name());
+    let runs_repo = state.job_run_repo.clone();
+    tokio::spawn(async move {
+        if let Err(err) = scheduled::run_job(job, &*runs_repo).await {
+            error!("Error running the {} job: {err}", job.name());
+        }
+    });
+
+    (
+        StatusCode::OK,
+        message(html!(
+            "The " (job.name()) " job started. Its run is listed in the "
+            a href="/admin/jobs" class="link link-primary visited:link-secondary focus:link-accent" { "jobs" }
+            " page."
+        )),
+    )
+}
+
+async fn search_handler(
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+) -> impl IntoResponse {
+    if query.q.trim().is_empty() {
+        return search_results("", &[]).into_response();
+    }
+
+    match state.entities_repo.search(&query.q, 0, MAX_SEARCH_RESULTS) {
+        Ok(results) => search_results(&query.q, &results).into_response(),
+        Err(err) => {
+            error!("Error searching for '{}': {err}", query.q);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn artist_handler(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    match state.entities_repo.artist(id) {
+        Ok(found) => {
+            let genres = state.entities_repo.genres().unwrap_or_default();
+            artist(&found, &genres, headers).into_response()
+        }
+        Err(_) => (StatusCode::NOT_FOUND, "404 Not Found").into_response(),
+    }
+}
+
+/// Corrects the genre and the links of the artist.
+///
+/// A Bandcamp link set here is never searched again by the Bandcamp job.
+async fn edit_artist_handler(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Form(form): Form<ArtistForm>,
+) -> impl IntoResponse {
+    let link = |url: &str| -> Result<Option<String>, ()> {
+        match url.trim() {
+            "" => Ok(None),
+            url => valid_link(url).map(|url| Some(url.to_string())).ok_or(()),
+        }
+    };
+
+    let (Ok(url_bandcamp), Ok(url_metallum)) = (link(&form.url_bandcamp), link(&form.url_metallum))
+    else {
+        return (
+            StatusCode::BAD_REQUEST,
+            message(html!(
+                "Please enter links starting with http:// or https://."
+            )),
+        );
+    };
+
+    let edit = ArtistEdit {
+        genre: non_empty(&form.genre),
+        url_bandcamp,
+        url_metallum,
+    };
+
+    match state.entities_repo.edit_artist(id, &edit) {
+        Ok(artist) => {
+            info!("An administrator corrected the artist {}", artist.name);
+            (StatusCode::OK, message(html!("The artist was saved.")))
+        }
+        Err(err) => {
+            error!("Error editing the artist {id}: {err}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                message(html!("Could not save the artist. Please try again.")),
+            )
+        }
+    }
+}
+
+async fn release_handler(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    let Ok((found, found_artist)) = state.entities_repo.release(id) else {
+        return (StatusCode::NOT_FOUND, "404 Not Found").into_response();
+    };
+
+    let others = state
+        .entities_repo
+        .artist_releases(found_artist.id)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|other| other.id != found.id && !other.is_hidden)
+        .collect::<Vec<_>>();
+
+    release(&found, &found_artist, &others, headers).into_response()
+}
+
+async fn hide_release_handler(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    set_hidden(&state, id, true)
+}
+
+async fn show_release_handler(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    set_hidden(&state, id, false)
+}
+
+/// Hides the duplicate release and points its page to the release kept.
+async fn merge_release_handler(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Form(form): Form<MergeForm>,
+) -> Response {
+    match state.entities_repo.merge_releases(form.into, id) {
+        Ok(()) => {
+            info!(
+                "An administrator merged the release {id} into {}",
+                form.into
+            );
+            corrected(&state)
+        }
+        Err(err) => {
+            error!("Error merging the release {id} into {}: {err}", form.into);
+            (
+                StatusCode::BAD_REQUEST,
+                message(html!(
+                    "Could not merge the release. Please check that the release to keep exists and was not merged itself."
+                )),
+            )
+                .into_response()
+        }
+    }
+}
+
+async fn new_release_handler(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    let genres = state.entities_repo.genres().unwrap_or_default();
+    new_release(&state.bands, &genres, headers)
+}
+
+/// Adds a release that no source lists, along with its artist when it is new.
+async fn add_release_handler(
+    State(state): State<AppState>,
+    Form(form): Form<NewReleaseForm>,
+) -> impl IntoResponse {
+    let (Some(artist_name), Some(album)) = (non_empty(&form.artist), non_empty(&form.album)) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            message(html!("Please enter the artist and the album.")),
+        );
+    };
+
+    let Some(date) = parse_date(&form.date) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            message(html!("Please enter a valid release date.")),
+        );
+    };
+
+    let new = NewRelease {
+        artist: artist_name,
+        genre: non_empty(&form.genre),
+        album,
+        date,
+        release_type: non_empty(&form.release_type),
+        label: non_empty(&form.label),
+    };
+
+    match state.entities_repo.add_release(&new) {
+        Ok((added, added_artist)) => {
+            info!(
+                "An administrator added the release {} - {}",
+                added_artist.name, added.album
+            );
+            refresh_feeds(&state);
+
+            let permalink = added.permalink(&added_artist, "");
+            (
+                StatusCode::OK,
+                message(html!(
+                    "The release was added: "
+                    a href=(permalink) class="link link-primary visited:link-secondary focus:link-accent" { (added_artist.name) " - " (added.album) }
+                )),
+            )
+        }
+        Err(err) => {
+            error!("Error adding the release: {err}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                message(html!("Could not add the release. Please try again.")),
+            )
+        }
+    }
+}
+
+fn set_hidden(state: &AppState, id: i32, is_hidden: bool) -> Response {
+    match state.entities_repo.hide_release(id, is_hidden) {
+        Ok(()) => {
+            info!(
+                "An administrator {} the release {id}",
+                if is_hidden { "hid" } else { "showed" }
+            );
+            corrected(state)
+        }
+        Err(err) => {
+            error!("Error hiding the release {id}: {err}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                message(html!("Could not save the release. Please try again.")),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Refreshes the feeds and reloads the page of the corrected release, so it shows
+/// its new status.
+fn corrected(state: &AppState) -> Response {
+    refresh_feeds(state);
+    (
+        StatusCode::OK,
+        [("HX-Refresh", "true")],
+        message(html!("Saved.")),
+    )
+        .into_response()
+}
+
+/// Regenerates today's feeds so that they reflect the corrections of the releases.
+fn refresh_feeds(state: &AppState) {
+    if let Err(err) =
+        scheduled::regenerate_feeds(state.calendar_repo.clone(), state.feed_repo.clone())
+    {
+        error!("Error regenerating today's feeds: {err}");
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    match value.trim() {
+        "" => None,
+        value => Some(value.to_string()),
+    }
+}
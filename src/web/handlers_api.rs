@@ -0,0 +1,837 @@
+use axum::{
+    Json, Router, async_trait,
+    extract::rejection::JsonRejection,
+    extract::{FromRequestParts, Path, Query, State},
+    http::request::Parts,
+    http::{HeaderMap, header::AUTHORIZATION},
+    response::{IntoResponse, Response},
+    routing::{get, put},
+};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use time::{Date, Month, util::days_in_month};
+use tracing::error;
+
+use crate::{
+    config::config,
+    error::{Error, Result},
+    model::{Artist, CustomFeed, Release, ReleaseDetails, Track},
+    parse_date,
+    support::token,
+    web::{AppState, valid_link},
+};
+
+const DEFAULT_PER_PAGE: usize = 50;
+const MAX_PER_PAGE: usize = 500;
+
+/// Defines the routes for the versioned JSON API.
+///
+/// The API is read-only, except for the corrections made by the administrators.
+pub fn routes_api() -> Router<AppState> {
+    Router::new()
+        .route("/openapi.json", get(openapi_handler))
+        .route("/releases", get(releases_handler))
+        .route("/releases/:id", get(release_handler))
+        .route("/releases/:year/:month", get(releases_month_handler))
+        .route("/releases/:year/:month/:day", get(releases_date_handler))
+        .route("/artists", get(artists_handler))
+        .route("/artists/:id", get(artist_handler))
+        .route("/artists/:id/bandcamp", put(artist_bandcamp_handler))
+        .route("/genres", get(genres_handler))
+        .route("/countries", get(countries_handler))
+        .route("/labels", get(labels_handler))
+        .route("/search", get(search_handler))
+        .route("/feeds", get(feeds_handler))
+        .route("/feeds/:id", get(feed_handler))
+}
+
+/// The error returned by the API, serialized as `{"error": {"code": ..., "message": ...}}`.
+pub struct ApiError(Error);
+
+impl From<Error> for ApiError {
+    fn from(err: Error) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, code, message) = match &self.0 {
+            Error::EntityNotFound { entity, id } => (
+                StatusCode::NOT_FOUND,
+                "not_found",
+                format!("The {entity} with ID {id} does not exist."),
+            ),
+            Error::EntityNotFoundByName { entity, name } => (
+                StatusCode::NOT_FOUND,
+                "not_found",
+                format!("The {entity} named '{name}' does not exist."),
+            ),
+            Error::Diesel(diesel::result::Error::NotFound) | Error::NoItem => (
+                StatusCode::NOT_FOUND,
+                "not_found",
+                String::from("The requested resource does not exist."),
+            ),
+            Error::InvalidParam(param) => (
+                StatusCode::BAD_REQUEST,
+                "invalid_parameter",
+                format!("The '{param}' parameter is invalid."),
+            ),
+            Error::Unauthorized => (
+                StatusCode::UNAUTHORIZED,
+                "unauthorized",
+                String::from("The administrator password is missing or wrong."),
+            ),
+            err => {
+                error!("API error: {err}");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal_error",
+                    String::from("An unexpected error occurred."),
+                )
+            }
+        };
+
+        (
+            status,
+            Json(json!({ "error": { "code": code, "message": message } })),
+        )
+            .into_response()
+    }
+}
+
+type ApiResult<T> = core::result::Result<Json<T>, ApiError>;
+
+/// A `Query` extractor whose rejection is an `ApiError`.
+struct ApiQuery<T>(T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> core::result::Result<Self, Self::Rejection> {
+        Query::<T>::from_request_parts(parts, state)
+            .await
+            .map(|Query(query)| Self(query))
+            .map_err(|_| Error::InvalidParam("query").into())
+    }
+}
+
+/// A `Path` extractor whose rejection is an `ApiError`.
+struct ApiPath<T>(T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> core::result::Result<Self, Self::Rejection> {
+        Path::<T>::from_request_parts(parts, state)
+            .await
+            .map(|Path(path)| Self(path))
+            .map_err(|_| Error::InvalidParam("path").into())
+    }
+}
+
+/// Represents the pagination query parameters.
+#[derive(Deserialize)]
+struct PageQuery {
+    page: Option<usize>,
+    per_page: Option<usize>,
+}
+
+/// Represents a page of results.
+#[derive(Serialize)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    pub pagination: Pagination,
+}
+
+/// Describes the position of a page within the results.
+#[derive(Serialize)]
+pub struct Pagination {
+    pub page: usize,
+    pub per_page: usize,
+    pub total: usize,
+    pub total_pages: usize,
+}
+
+impl PageQuery {
+    /// Validates the parameters and returns the page, its size and the number
+    /// of items before it.
+    fn window(&self) -> Result<(usize, usize, usize)> {
+        let page = self.page.unwrap_or(1);
+        let per_page = self.per_page.unwrap_or(DEFAULT_PER_PAGE);
+
+        if per_page == 0 || per_page > MAX_PER_PAGE {
+            return Err(Error::InvalidParam("per_page"));
+        }
+
+        let skipped = page
+            .checked_sub(1)
+            .and_then(|page| page.checked_mul(per_page))
+            .ok_or(Error::InvalidParam("page"))?;
+
+        Ok((page, per_page, skipped))
+    }
+}
+
+impl<T> Page<T> {
+    fn new(items: Vec<T>, query: &PageQuery) -> Result<Self> {
+        let (page, per_page, skipped) = query.window()?;
+        let total = items.len();
+        let data = items.into_iter().skip(skipped).take(per_page).collect();
+        Ok(Self::of(data, page, per_page, total))
+    }
+
+    /// Wraps the items of a page already cut out of `total` results.
+    fn of(data: Vec<T>, page: usize, per_page: usize, total: usize) -> Self {
+        Self {
+            data,
+            pagination: Pagination {
+                page,
+                per_page,
+                total,
+                total_pages: total.div_ceil(per_page),
+            },
+        }
+    }
+}
+
+/// A release along with its artist.
+#[derive(Serialize)]
+pub struct ReleaseWithArtist {
+    #[serde(flatten)]
+    pub release: Release,
+    pub artist: Artist,
+}
+
+impl From<(Release, Artist)> for ReleaseWithArtist {
+    fn from((release, artist): (Release, Artist)) -> Self {
+        Self { release, artist }
+    }
+}
+
+/// A release along with its artist and the details scraped from its album page.
+#[derive(Serialize)]
+pub struct ReleaseWithTracklist {
+    #[serde(flatten)]
+    pub release: ReleaseWithArtist,
+    pub details: Option<ReleaseDetails>,
+    pub tracks: Vec<Track>,
+}
+
+/// A custom feed definition.
+#[derive(Serialize)]
+pub struct CustomFeedDefinition {
+    pub id: i32,
+    pub bands: Vec<String>,
+    pub genres: Vec<String>,
+    pub countries: Vec<String>,
+    pub labels: Vec<String>,
+    pub url: String,
+}
+
+impl From<CustomFeed> for CustomFeedDefinition {
+    fn from(feed: CustomFeed) -> Self {
+        Self {
+            id: feed.id,
+            bands: feed.bands_list(),
+            genres: feed.genres_list(),
+            countries: feed.countries_list(),
+            labels: feed.labels_list(),
+            url: format!("{}/calendar/feed.xml?id={}", config().HOST_URL, feed.id),
+        }
+    }
+}
+
+async fn openapi_handler() -> Json<Value> {
+    Json(openapi())
+}
+
+#[derive(Deserialize)]
+struct RangeQuery {
+    from: Option<String>,
+    to: Option<String>,
+    page: Option<usize>,
+    per_page: Option<usize>,
+}
+
+async fn releases_handler(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<RangeQuery>,
+) -> ApiResult<Page<ReleaseWithArtist>> {
+    let from = parse_date(query.from.as_deref().ok_or(Error::InvalidParam("from"))?)
+        .ok_or(Error::InvalidParam("from"))?;
+    let to = match query.to.as_deref() {
+        Some(to) => parse_date(to).ok_or(Error::InvalidParam("to"))?,
+        None => from,
+    };
+    if to < from {
+        return Err(Error::InvalidParam("to").into());
+    }
+
+    let page = PageQuery {
+        page: query.page,
+        per_page: query.per_page,
+    };
+    releases_page(&state, from, to, &page)
+}
+
+async fn release_handler(
+    State(state): State<AppState>,
+    ApiPath(id): ApiPath<i32>,
+) -> ApiResult<ReleaseWithTracklist> {
+    let release = state.entities_repo.release(id)?;
+    if release.0.is_hidden {
+        return Err(Error::EntityNotFound {
+            entity: "release",
+            id: id as i64,
+        }
+        .into());
+    }
+    let (details, tracks) = state.entities_repo.release_tracklist(id)?;
+
+    Ok(Json(ReleaseWithTracklist {
+        release: release.into(),
+        details,
+        tracks,
+    }))
+}
+
+async fn releases_month_handler(
+    State(state): State<AppState>,
+    ApiPath((year, month)): ApiPath<(i32, u8)>,
+    ApiQuery(query): ApiQuery<PageQuery>,
+) -> ApiResult<Page<ReleaseWithArtist>> {
+    let month = Month::try_from(month).map_err(|_| Error::InvalidParam("month"))?;
+    let from = Date::from_calendar_date(year, month, 1).map_err(|_| Error::InvalidParam("year"))?;
+    let to = Date::from_calendar_date(year, month, days_in_month(month, year))
+        .map_err(|_| Error::InvalidParam("year"))?;
+
+    releases_page(&state, from, to, &query)
+}
+
+async fn releases_date_handler(
+    State(state): State<AppState>,
+    ApiPath((year, month, day)): ApiPath<(i32, u8, u8)>,
+    ApiQuery(query): ApiQuery<PageQuery>,
+) -> ApiResult<Page<ReleaseWithArtist>> {
+    let month = Month::try_from(month).map_err(|_| Error::InvalidParam("month"))?;
+    let date =
+        Date::from_calendar_date(year, month, day).map_err(|_| Error::InvalidParam("day"))?;
+
+    releases_page(&state, date, date, &query)
+}
+
+fn releases_page(
+    state: &AppState,
+    from: Date,
+    to: Date,
+    query: &PageQuery,
+) -> ApiResult<Page<ReleaseWithArtist>> {
+    let releases = state.calendar_repo.get_between(from, to)?;
+    let releases = releases.into_iter().map(ReleaseWithArtist::from).collect();
+    Ok(Json(Page::new(releases, query)?))
+}
+
+#[derive(Deserialize)]
+struct ArtistQuery {
+    name: Option<String>,
+}
+
+async fn artists_handler(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<ArtistQuery>,
+) -> ApiResult<Artist> {
+    let name = query.name.ok_or(Error::InvalidParam("name"))?;
+    Ok(Json(state.entities_repo.artist_by_name(&name)?))
+}
+
+async fn artist_handler(
+    State(state): State<AppState>,
+    ApiPath(id): ApiPath<i32>,
+) -> ApiResult<Artist> {
+    Ok(Json(state.entities_repo.artist(id)?))
+}
+
+#[derive(Deserialize)]
+struct BandcampOverride {
+    url: Option<String>,
+}
+
+/// Sets the Bandcamp link of an artist by hand, or removes a wrong one.
+///
+/// Only the administrators may correct the links, with their password as a bearer token.
+async fn artist_bandcamp_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ApiPath(id): ApiPath<i32>,
+    payload: core::result::Result<Json<BandcampOverride>, JsonRejection>,
+) -> ApiResult<Artist> {
+    let password = config()
+        .ADMIN_PASSWORD
+        .as_deref()
+        .ok_or(Error::Unauthorized)?;
+    let given = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(Error::Unauthorized)?;
+    if !token::secrets_match(password, given) {
+        return Err(Error::Unauthorized.into());
+    }
+
+    let Json(payload) = payload.map_err(|_| Error::InvalidParam("url"))?;
+    let url = match payload.url.as_deref() {
+        Some(url) => Some(valid_link(url).ok_or(Error::InvalidParam("url"))?),
+        None => None,
+    };
+
+    Ok(Json(state.entities_repo.override_bandcamp(id, url)?))
+}
+
+async fn genres_handler(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<PageQuery>,
+) -> ApiResult<Page<String>> {
+    let genres = state.entities_repo.genres()?;
+    Ok(Json(Page::new(genres, &query)?))
+}
+
+async fn countries_handler(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<PageQuery>,
+) -> ApiResult<Page<String>> {
+    Ok(Json(Page::new(state.entities_repo.countries(), &query)?))
+}
+
+async fn labels_handler(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<PageQuery>,
+) -> ApiResult<Page<String>> {
+    Ok(Json(Page::new(state.entities_repo.labels(), &query)?))
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: Option<String>,
+    page: Option<usize>,
+    per_page: Option<usize>,
+}
+
+async fn search_handler(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<SearchQuery>,
+) -> ApiResult<Page<ReleaseWithArtist>> {
+    let q = query.q.ok_or(Error::InvalidParam("q"))?;
+    let (page, per_page, skipped) = PageQuery {
+        page: query.page,
+        per_page: query.per_page,
+    }
+    .window()?;
+    let offset = i64::try_from(skipped).map_err(|_| Error::InvalidParam("page"))?;
+
+    let total = state.entities_repo.count_search(&q)?;
+    let releases = state
+        .entities_repo
+        .search(&q, offset, per_page as i64)?
+        .into_iter()
+        .map(ReleaseWithArtist::from)
+        .collect();
+
+    Ok(Json(Page::of(releases, page, per_page, total as usize)))
+}
+
+async fn feeds_handler(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<PageQuery>,
+) -> ApiResult<Page<CustomFeedDefinition>> {
+    let feeds = state.feed_repo.custom_feeds()?;
+    let feeds = feeds.into_iter().map(CustomFeedDefinition::from).collect();
+    Ok(Json(Page::new(feeds, &query)?))
+}
+
+async fn feed_handler(
+    State(state): State<AppState>,
+    ApiPath(id): ApiPath<i32>,
+) -> ApiResult<CustomFeedDefinition> {
+    if id < 0 {
+        return Err(Error::EntityNotFound {
+            entity: "custom feed",
+            id: id as i64,
+        }
+        .into());
+    }
+
+    let feed = state
+        .feed_repo
+        .get_custom_feed(id)
+        .map_err(|err| match err {
+            Error::Diesel(diesel::result::Error::NotFound) => Error::EntityNotFound {
+                entity: "custom feed",
+                id: id as i64,
+            },
+            err => err,
+        })?;
+    Ok(Json(feed.into()))
+}
+
+/// Generates the OpenAPI 3.1 document describing the API.
+pub fn openapi() -> Value {
+    let page_params = json!([
+        { "name": "page", "in": "query", "required": false, "schema": { "type": "integer", "minimum": 1, "default": 1 } },
+        { "name": "per_page", "in": "query", "required": false, "schema": { "type": "integer", "minimum": 1, "maximum": MAX_PER_PAGE, "default": DEFAULT_PER_PAGE } }
+    ]);
+
+    let path_param = |name: &str| json!({ "name": name, "in": "path", "required": true, "schema": { "type": "integer" } });
+
+    let with_page_params = |params: Vec<Value>| {
+        let mut params = params;
+        params.extend(page_params.as_array().cloned().unwrap_or_default());
+        Value::Array(params)
+    };
+
+    let ok = |schema: Value| {
+        json!({
+            "200": { "description": "OK", "content": { "application/json": { "schema": schema } } },
+            "400": { "$ref": "#/components/responses/BadRequest" },
+            "404": { "$ref": "#/components/responses/NotFound" },
+            "500": { "$ref": "#/components/responses/InternalError" }
+        })
+    };
+
+    let page_of = |schema: Value| {
+        json!({
+            "type": "object",
+            "required": ["data", "pagination"],
+            "properties": {
+                "data": { "type": "array", "items": schema },
+                "pagination": { "$ref": "#/components/schemas/Pagination" }
+            }
+        })
+    };
+
+    let error_response = |description: &str| {
+        json!({
+            "description": description,
+            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } }
+        })
+    };
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "Heavy Metal Releases API",
+            "description": "Read-only access to the heavy metal releases calendar, and corrections by the administrators.",
+            "version": "1.0.0"
+        },
+        "servers": [{ "url": format!("{}/api/v1", config().HOST_URL) }],
+        "paths": {
+            "/releases": {
+                "get": {
+                    "summary": "List the releases between two dates, both inclusive.",
+                    "parameters": with_page_params(vec![
+                        json!({ "name": "from", "in": "query", "required": true, "schema": { "type": "string", "format": "date" } }),
+                        json!({ "name": "to", "in": "query", "required": false, "schema": { "type": "string", "format": "date" } }),
+                    ]),
+                    "responses": ok(page_of(json!({ "$ref": "#/components/schemas/Release" })))
+                }
+            },
+            "/releases/{id}": {
+                "get": {
+                    "summary": "Look up a release by ID, along with its format, running time and tracklist when they were scraped.",
+                    "parameters": [path_param("id")],
+                    "responses": ok(json!({ "$ref": "#/components/schemas/ReleaseWithTracklist" }))
+                }
+            },
+            "/releases/{year}/{month}": {
+                "get": {
+                    "summary": "List the releases of a month.",
+                    "parameters": with_page_params(vec![path_param("year"), path_param("month")]),
+                    "responses": ok(page_of(json!({ "$ref": "#/components/schemas/Release" })))
+                }
+            },
+            "/releases/{year}/{month}/{day}": {
+                "get": {
+                    "summary": "List the releases of a date.",
+                    "parameters": with_page_params(vec![path_param("year"), path_param("month"), path_param("day")]),
+                    "responses": ok(page_of(json!({ "$ref": "#/components/schemas/Release" })))
+                }
+            },
+            "/artists": {
+                "get": {
+                    "summary": "Look up an artist by name, ignoring the case.",
+                    "parameters": [
+                        { "name": "name", "in": "query", "required": true, "schema": { "type": "string" } }
+                    ],
+                    "responses": ok(json!({ "$ref": "#/components/schemas/Artist" }))
+                }
+            },
+            "/artists/{id}": {
+                "get": {
+                    "summary": "Look up an artist by ID.",
+                    "parameters": [path_param("id")],
+                    "responses": ok(json!({ "$ref": "#/components/schemas/Artist" }))
+                }
+            },
+            "/artists/{id}/bandcamp": {
+                "put": {
+                    "summary": "Set the Bandcamp link of an artist by hand, or remove it with a null URL. The link is never searched again.",
+                    "security": [{ "admin": [] }],
+                    "parameters": [path_param("id")],
+                    "requestBody": {
+                        "required": true,
+                        "content": { "application/json": { "schema": {
+                            "type": "object",
+                            "required": ["url"],
+                            "properties": { "url": { "type": ["string", "null"], "format": "uri" } }
+                        } } }
+                    },
+                    "responses": {
+                        "200": { "description": "OK", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Artist" } } } },
+                        "400": { "$ref": "#/components/responses/BadRequest" },
+                        "401": { "$ref": "#/components/responses/Unauthorized" },
+                        "404": { "$ref": "#/components/responses/NotFound" },
+                        "500": { "$ref": "#/components/responses/InternalError" }
+                    }
+                }
+            },
+            "/genres": {
+                "get": {
+                    "summary": "List the distinct genres of the artists.",
+                    "parameters": page_params,
+                    "responses": ok(page_of(json!({ "type": "string" })))
+                }
+            },
+            "/countries": {
+                "get": {
+                    "summary": "List the distinct countries of origin of the artists.",
+                    "parameters": page_params,
+                    "responses": ok(page_of(json!({ "type": "string" })))
+                }
+            },
+            "/labels": {
+                "get": {
+                    "summary": "List the distinct record labels of the releases.",
+                    "parameters": page_params,
+                    "responses": ok(page_of(json!({ "type": "string" })))
+                }
+            },
+            "/search": {
+                "get": {
+                    "summary": "Search the releases by artist and album, best matches first. Every word matches as a prefix, ignoring the case and the diacritics.",
+                    "parameters": with_page_params(vec![
+                        json!({ "name": "q", "in": "query", "required": true, "schema": { "type": "string" } }),
+                    ]),
+                    "responses": ok(page_of(json!({ "$ref": "#/components/schemas/Release" })))
+                }
+            },
+            "/feeds": {
+                "get": {
+                    "summary": "List the custom feed definitions.",
+                    "parameters": page_params,
+                    "responses": ok(page_of(json!({ "$ref": "#/components/schemas/CustomFeed" })))
+                }
+            },
+            "/feeds/{id}": {
+                "get": {
+                    "summary": "Look up a custom feed definition by ID.",
+                    "parameters": [path_param("id")],
+                    "responses": ok(json!({ "$ref": "#/components/schemas/CustomFeed" }))
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Artist": {
+                    "type": "object",
+                    "required": ["id", "name"],
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "name": { "type": "string" },
+                        "genre": { "type": ["string", "null"] },
+                        "url_bandcamp": { "type": ["string", "null"] },
+                        "url_metallum": { "type": ["string", "null"] },
+                        "musicbrainz_id": { "type": ["string", "null"] },
+                        "bandcamp_confidence": { "type": ["number", "null"], "minimum": 0, "maximum": 1 },
+                        "country": { "type": ["string", "null"] }
+                    }
+                },
+                "Release": {
+                    "type": "object",
+                    "required": ["id", "year", "month", "day", "artist_id", "album", "url_youtube", "artist"],
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "year": { "type": "integer" },
+                        "month": { "type": "integer" },
+                        "day": { "type": "integer" },
+                        "artist_id": { "type": "integer" },
+                        "album": { "type": "string" },
+                        "release_type": { "type": ["string", "null"] },
+                        "url_youtube": { "type": "string" },
+                        "url_metallum": { "type": ["string", "null"] },
+                        "musicbrainz_id": { "type": ["string", "null"] },
+                        "label": { "type": ["string", "null"] },
+                        "cover": { "type": ["string", "null"], "description": "The file name of the cover thumbnail, served at `/covers/{cover}`." },
+                        "artist": { "$ref": "#/components/schemas/Artist" }
+                    }
+                },
+                "ReleaseWithTracklist": {
+                    "allOf": [
+                        { "$ref": "#/components/schemas/Release" },
+                        {
+                            "type": "object",
+                            "required": ["details", "tracks"],
+                            "properties": {
+                                "details": {
+                                    "type": ["object", "null"],
+                                    "properties": {
+                                        "format": { "type": ["string", "null"] },
+                                        "label": { "type": ["string", "null"] },
+                                        "runtime": { "type": ["integer", "null"], "description": "The total running time in seconds." }
+                                    }
+                                },
+                                "tracks": { "type": "array", "items": { "$ref": "#/components/schemas/Track" } }
+                            }
+                        }
+                    ]
+                },
+                "Track": {
+                    "type": "object",
+                    "required": ["disc", "position", "title"],
+                    "properties": {
+                        "disc": { "type": "integer" },
+                        "position": { "type": "integer" },
+                        "title": { "type": "string" },
+                        "duration": { "type": ["integer", "null"], "description": "The duration in seconds." }
+                    }
+                },
+                "CustomFeed": {
+                    "type": "object",
+                    "required": ["id", "bands", "genres", "countries", "labels", "url"],
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "bands": { "type": "array", "items": { "type": "string" } },
+                        "genres": { "type": "array", "items": { "type": "string" } },
+                        "countries": { "type": "array", "items": { "type": "string" } },
+                        "labels": { "type": "array", "items": { "type": "string" } },
+                        "url": { "type": "string" }
+                    }
+                },
+                "Pagination": {
+                    "type": "object",
+                    "required": ["page", "per_page", "total", "total_pages"],
+                    "properties": {
+                        "page": { "type": "integer" },
+                        "per_page": { "type": "integer" },
+                        "total": { "type": "integer" },
+                        "total_pages": { "type": "integer" }
+                    }
+                },
+                "Error": {
+                    "type": "object",
+                    "required": ["error"],
+                    "properties": {
+                        "error": {
+                            "type": "object",
+                            "required": ["code", "message"],
+                            "properties": {
+                                "code": { "type": "string", "enum": ["not_found", "invalid_parameter", "unauthorized", "internal_error"] },
+                                "message": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            },
+            "securitySchemes": {
+                "admin": { "type": "http", "scheme": "bearer", "description": "The `ADMIN_PASSWORD` of the server." }
+            },
+            "responses": {
+                "BadRequest": error_response("A parameter is invalid."),
+                "NotFound": error_response("The resource does not exist."),
+                "Unauthorized": error_response("The administrator password is missing or wrong."),
+                "InternalError": error_response("An unexpected error occurred.")
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[test]
+    fn test_page_ok() -> Result<()> {
+        let items = (1..=7).collect::<Vec<_>>();
+
+        let got = Page::new(
+            items,
+            &PageQuery {
+                page: Some(2),
+                per_page: Some(3),
+            },
+        )?;
+
+        pretty_assertions::assert_eq!(got.data, vec![4, 5, 6]);
+        pretty_assertions::assert_eq!(got.pagination.total, 7);
+        pretty_assertions::assert_eq!(got.pagination.total_pages, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_page_of_ok() {
+        let got = Page::of(vec![4, 5, 6], 2, 3, 1_000);
+
+        pretty_assertions::assert_eq!(got.data, vec![4, 5, 6]);
+        pretty_assertions::assert_eq!(got.pagination.page, 2);
+        pretty_assertions::assert_eq!(got.pagination.total, 1_000);
+        pretty_assertions::assert_eq!(got.pagination.total_pages, 334);
+    }
+
+    #[test]
+    fn test_page_invalid_params() {
+        let query = PageQuery {
+            page: Some(0),
+            per_page: None,
+        };
+        assert!(matches!(
+            Page::new(vec![1], &query),
+            Err(Error::InvalidParam("page"))
+        ));
+
+        let query = PageQuery {
+            page: Some(usize::MAX),
+            per_page: Some(MAX_PER_PAGE),
+        };
+        assert!(matches!(
+            Page::new(vec![1], &query),
+            Err(Error::InvalidParam("page"))
+        ));
+
+        let query = PageQuery {
+            page: None,
+            per_page: Some(MAX_PER_PAGE + 1),
+        };
+        assert!(matches!(
+            Page::new(vec![1], &query),
+            Err(Error::InvalidParam("per_page"))
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::HeaderMap,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use reqwest::StatusCode;
+use tracing::error;
+
+use super::templates::artist::{artist, artist_feed_links};
+use crate::{config::config, date_now, error::Error, web::AppState};
+
+/// Defines the routes for the artist pages.
+pub fn routes_artists() -> Router<AppState> {
+    Router::new()
+        .route("/:id", get(artist_handler))
+        .route("/:id/subscribe", post(subscribe_handler))
+}
+
+async fn artist_handler(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    let artist_row = match state.entities_repo.artist(id) {
+        Ok(artist) => artist,
+        Err(Error::EntityNotFound { .. }) => {
+            return (StatusCode::NOT_FOUND, "404 Not Found").into_response();
+        }
+        Err(err) => {
+            error!("Error fetching artist {id}: {err}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let releases = state
+        .entities_repo
+        .artist_releases(id)
+        .unwrap_or_else(|err| {
+            error!("Error fetching releases of artist {id}: {err}");
+            Vec::new()
+        });
+
+    artist(&artist_row, &releases, date_now().date(), headers).into_response()
+}
+
+/// Creates or reuses the custom feed following only this artist.
+async fn subscribe_handler(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    let artist_row = match state.entities_repo.artist(id) {
+        Ok(artist) => artist,
+        Err(_) => return (StatusCode::NOT_FOUND, "404 Not Found").into_response(),
+    };
+
+    match state.feed_repo.get_or_create_custom_feed(
+        vec![artist_row.name],
+        vec![String::from("None")],
+        Vec::new(),
+        Vec::new(),
+    ) {
+        Some(custom_feed_id) => {
+            artist_feed_links(&config().HOST_URL, custom_feed_id).into_response()
+        }
+        None => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Could not create the feed. Please try again later.",
+        )
+            .into_response(),
+    }
+}
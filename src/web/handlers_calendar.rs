@@ -1,3 +1,7 @@
+use atom_syndication::{
+    ContentBuilder, Entry, EntryBuilder, Feed as AtomFeed, FeedBuilder, FixedDateTime, LinkBuilder,
+    PersonBuilder,
+};
 use axum::{
     Router,
     extract::{Path, Query, State},
@@ -7,23 +11,33 @@ use axum::{
 };
 use axum_extra::extract::Form;
 use maud::Markup;
-use reqwest::{StatusCode, header::CONTENT_TYPE};
-use rss::{Channel, ChannelBuilder, Guid, Image, Item, ItemBuilder};
+use reqwest::{
+    StatusCode,
+    header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+};
+use rss::{
+    CategoryBuilder, Channel, ChannelBuilder, EnclosureBuilder, Guid, Image, Item, ItemBuilder,
+    extension::ExtensionBuilder,
+};
 use serde::Deserialize;
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 use time::{
     Date, Duration, Month, OffsetDateTime, Time, UtcOffset,
-    format_description::well_known::Rfc2822, util::days_in_month,
+    format_description::well_known::{Rfc2822, Rfc3339},
+    util::days_in_month,
 };
 use tracing::error;
 
-use super::templates::calendar::{calendar, feeds, render_calendar};
-use crate::model::FeedRepository;
+use super::{
+    json_feed::{self, JsonFeed, JsonFeedAuthor, JsonFeedItem, MetalExtension, MetalRelease},
+    templates::calendar::{calendar, feeds, render_calendar},
+};
 use crate::{
     config::config,
     date_now,
-    error::Result,
-    model::{Artist, CalendarRepository, Feed, Release},
+    error::{Error, Result},
+    model::{Artist, CalendarRepository, Feed, FeedFormat, FeedRepository, Release, ReleaseChange},
+    support::ical,
     web::AppState,
 };
 
@@ -32,7 +46,11 @@ pub fn routes_calendar() -> Router<AppState> {
     Router::new()
         .route("/", get(calendar_handler))
         .route("/:year/:month/:day/releases", get(calendar_month_handler))
+        .route("/archive", get(calendar_archive_handler))
         .route("/feed.xml", get(feed_handler).post(feed_post_handler))
+        .route("/feed.atom", get(feed_atom_handler))
+        .route("/feed.json", get(feed_json_handler))
+        .route("/releases.ics", get(ics_handler))
         .route("/:year/:month/:day", get(releases_handler))
 }
 
@@ -51,8 +69,9 @@ pub struct CalendarDay {
 
 async fn calendar_handler(State(state): State<AppState>, headers: HeaderMap) -> Markup {
     let now = date_now();
+    let years = state.calendar_repo.years().unwrap_or_default();
     let (days, releases) = calculate_calendar(state.calendar_repo, now);
-    calendar(now, days, releases, headers)
+    calendar(now, days, releases, &years, headers)
 }
 
 async fn calendar_month_handler(
@@ -68,9 +87,33 @@ async fn calendar_month_handler(
 
     let primitive_date_time = date.with_time(Time::MIDNIGHT);
     let date = primitive_date_time.assume_offset(UtcOffset::UTC);
+    let years = state.calendar_repo.years().unwrap_or_default();
     let (days, releases) = calculate_calendar(state.calendar_repo, date);
 
-    render_calendar(date, days, releases)
+    render_calendar(date, days, releases, &years)
+}
+
+#[derive(Deserialize)]
+struct ArchiveParams {
+    year: i32,
+    month: u8,
+}
+
+/// Renders the calendar of the given month of any archived year.
+async fn calendar_archive_handler(
+    State(state): State<AppState>,
+    Query(params): Query<ArchiveParams>,
+) -> impl IntoResponse {
+    let month = Month::try_from(params.month).unwrap_or(Month::January);
+    let Ok(date) = Date::from_calendar_date(params.year, month, 1) else {
+        return (StatusCode::BAD_REQUEST, "Invalid year").into_response();
+    };
+
+    let date = date.with_time(Time::MIDNIGHT).assume_offset(UtcOffset::UTC);
+    let years = state.calendar_repo.years().unwrap_or_default();
+    let (days, releases) = calculate_calendar(state.calendar_repo, date);
+
+    render_calendar(date, days, releases, &years).into_response()
 }
 
 fn calculate_calendar(
@@ -133,55 +176,107 @@ fn calculate_calendar(
 #[derive(Deserialize)]
 struct FeedQuery {
     id: Option<i32>,
+    #[serde(default)]
+    mode: FeedMode,
+}
+
+/// How the releases are grouped into the items of the RSS feed.
+#[derive(Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum FeedMode {
+    /// One item per day listing all of its releases.
+    #[default]
+    Daily,
+    /// One item per release.
+    Items,
+}
+
+/// Holds what describes today's feed, regardless of its syndication format.
+struct FeedContext {
+    /// The human-readable date, e.g. `October 17, 2026`.
+    date: String,
+    /// The date as an integer, e.g. `20261017`.
+    date_int: i32,
+    /// The publication date in the RFC 2822 format used by RSS.
+    pub_date: String,
+    /// The publication date in the RFC 3339 format used by Atom.
+    updated: String,
+    /// The link to the feed itself.
+    link_feed: String,
+    /// The link to today's releases on the calendar.
+    link_item: String,
+    custom_feed_id: i32,
+}
+
+impl FeedContext {
+    fn new(now: OffsetDateTime, feed_path: &str, custom_feed_id: i32) -> Option<Self> {
+        let date_int = format!("{}{:02}{:02}", now.year(), now.month() as u8, now.day())
+            .parse::<i32>()
+            .ok()?;
+
+        let base_url = &config().HOST_URL;
+        let link_feed = if custom_feed_id > -1 {
+            format!("{base_url}/calendar/{feed_path}?id={custom_feed_id}")
+        } else {
+            format!("{base_url}/calendar/{feed_path}")
+        };
+
+        Some(Self {
+            date: format!("{} {}, {}", now.month(), now.day(), now.year()),
+            date_int,
+            pub_date: now.format(&Rfc2822).unwrap_or_default(),
+            updated: now.format(&Rfc3339).unwrap_or_default(),
+            link_feed,
+            link_item: format!(
+                "{}/calendar/{}/{}/{}",
+                base_url,
+                now.year(),
+                now.month() as u8,
+                now.day()
+            ),
+            custom_feed_id,
+        })
+    }
 }
 
 async fn feed_handler(
     State(state): State<AppState>,
     feed_query: Query<FeedQuery>,
 ) -> impl IntoResponse {
-    let now = date_now();
-    let date_int =
-        match format!("{}{:02}{:02}", now.year(), now.month() as u8, now.day()).parse::<i32>() {
-            Ok(n) => n,
-            Err(_) => {
-                return (
+    let custom_feed_id = feed_query.id.unwrap_or(-1);
+    let Some(ctx) = FeedContext::new(date_now(), "feed.xml", custom_feed_id) else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Could not parse today's date.",
+        )
+            .into_response();
+    };
+
+    if feed_query.mode == FeedMode::Items {
+        return match create_items_channel(ctx, &state.calendar_repo, &state.feed_repo) {
+            Ok(channel) => (
+                [(CONTENT_TYPE, "text/xml;charset=UTF-8")],
+                channel.to_string(),
+            )
+                .into_response(),
+            Err(err) => {
+                error!("getting releases per item {}: {err}", date_now());
+                (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    "Could not parse today's date.",
+                    "Could not fetch the releases.",
                 )
-                    .into_response();
+                    .into_response()
             }
         };
+    }
 
-    let pub_date = now.format(&Rfc2822).unwrap_or_default();
-    let date = format!("{} {}, {}", now.month(), now.day(), now.year());
-    let base_url = &config().HOST_URL;
-
-    let link_feed = format!("{}/calendar/feed.xml", base_url);
-    let link_item: String = format!(
-        "{}/calendar/{}/{}/{}",
-        base_url,
-        now.year(),
-        now.month() as u8,
-        now.day()
-    );
-
-    let custom_feed_id = feed_query.id.unwrap_or(-1);
-
-    match state.feed_repo.get(12, custom_feed_id) {
+    match state
+        .feed_repo
+        .get(config().FEED_MAX_ITEMS, custom_feed_id, FeedFormat::Rss)
+    {
         Ok(feeds) => (
             [(CONTENT_TYPE, "text/xml;charset=UTF-8")],
-            create_channel(
-                feeds,
-                date,
-                date_int,
-                pub_date,
-                link_feed,
-                link_item,
-                custom_feed_id,
-                state.calendar_repo,
-                state.feed_repo,
-            )
-            .to_string(),
+            create_channel(feeds, &ctx, &state.calendar_repo, &state.feed_repo).to_string(),
         )
             .into_response(),
         Err(err) => {
@@ -197,14 +292,9 @@ async fn feed_handler(
 
 fn create_channel(
     feeds: Vec<Feed>,
-    date: String,
-    date_int: i32,
-    pub_date: String,
-    link_feed: String,
-    link_item: String,
-    custom_feed_id: i32,
-    calendar_repo: Arc<dyn CalendarRepository + Send + Sync>,
-    feed_repo: Arc<dyn FeedRepository + Send + Sync>,
+    ctx: &FeedContext,
+    calendar_repo: &Arc<dyn CalendarRepository + Send + Sync>,
+    feed_repo: &Arc<dyn FeedRepository + Send + Sync>,
 ) -> Channel {
     let items = feeds
         .iter()
@@ -215,149 +305,72 @@ fn create_channel(
         })
         .collect::<Vec<_>>();
 
-    let image_url = format!("{}/public/favicon.png", config().HOST_URL);
-    let image = rss::ImageBuilder::default()
-        .link(&image_url)
-        .url(image_url)
-        .build();
+    let image = channel_image();
 
     feeds
         .first()
         .and_then(|feed| {
-            if feed.date == date_int {
+            if feed.date == ctx.date_int {
                 Some(build_channel_with_items(
-                    &pub_date,
-                    &link_feed,
+                    &ctx.pub_date,
+                    &ctx.link_feed,
                     image.clone(),
-                    items,
+                    items.clone(),
                 ))
             } else {
-                create_new_feed(
-                    pub_date.clone(),
-                    date.clone(),
-                    date_int,
-                    link_feed.clone(),
-                    link_item.clone(),
-                    image.clone(),
-                    custom_feed_id,
-                    &calendar_repo,
-                    &feed_repo,
-                )
-                .ok()
-                .map(|channel| {
-                    if let Some(item) = channel.items.first() {
-                        let mut items_with_new = items.clone();
-                        items_with_new.insert(0, item.clone());
-                        build_channel_from_existing(channel, items)
-                    } else {
-                        build_channel_from_existing(channel, items)
-                    }
-                })
+                create_new_feed(ctx, image.clone(), calendar_repo, feed_repo)
+                    .ok()
+                    .map(|channel| {
+                        if let Some(item) = channel.items.first() {
+                            let mut items_with_new = items.clone();
+                            items_with_new.insert(0, item.clone());
+                            build_channel_from_existing(channel, items)
+                        } else {
+                            build_channel_from_existing(channel, items)
+                        }
+                    })
             }
         })
         .unwrap_or_else(|| {
-            create_new_feed(
-                pub_date.clone(),
-                date,
-                date_int,
-                link_feed.clone(),
-                link_item,
-                image.clone(),
-                custom_feed_id,
-                &calendar_repo,
-                &feed_repo,
-            )
-            .unwrap_or_else(|err| {
+            create_new_feed(ctx, image.clone(), calendar_repo, feed_repo).unwrap_or_else(|err| {
                 error!("Error creating new channel: {err}");
-                build_channel(pub_date, link_feed, image)
+                build_channel(ctx.pub_date.clone(), ctx.link_feed.clone(), image)
             })
         })
 }
 
 fn create_new_feed(
-    pub_date: String,
-    date: String,
-    date_int: i32,
-    link_feed: impl Into<String>,
-    link_item: impl Into<String>,
+    ctx: &FeedContext,
     image: Image,
-    custom_feed_id: i32,
     calendar_repo: &Arc<dyn CalendarRepository + Send + Sync>,
     feed_repo: &Arc<dyn FeedRepository + Send + Sync>,
 ) -> Result<Channel> {
-    let releases = calendar_repo.get().map_err(|err| {
-        error!("Error fetching calendar: {}", err);
-        err
-    })?;
-
-    let releases = if custom_feed_id > -1 {
-        let custom_feed = feed_repo.get_custom_feed(custom_feed_id)?;
-        let custom_feed_genres = custom_feed.genres;
-        let custom_feed_bands = custom_feed.bands;
-
-        if custom_feed_genres == "none" {
-            releases
-                .into_iter()
-                .filter(|(_releases, artist)| {
-                    custom_feed_bands.contains(&artist.name.to_lowercase())
-                })
-                .collect::<Vec<_>>()
-        } else if custom_feed_bands == "none" {
-            releases
-                .into_iter()
-                .filter(|(_releases, artist)| {
-                    let mut is_genre_in_want = artist.genre.is_some();
-
-                    if let Some(ref genre) = artist.genre {
-                        is_genre_in_want = contains_any_keywords(
-                            &genre.to_lowercase().replace(" metal", ""),
-                            &custom_feed_genres,
-                        );
-                    }
-
-                    is_genre_in_want
-                })
-                .collect::<Vec<_>>()
-        } else {
-            releases
-                .into_iter()
-                .filter(|(_releases, artist)| {
-                    let mut is_genre_in_want = artist.genre.is_some();
-
-                    if let Some(ref genre) = artist.genre {
-                        is_genre_in_want = contains_any_keywords(
-                            &genre.to_lowercase().replace(" metal", ""),
-                            &custom_feed_genres,
-                        );
-                    }
-
-                    is_genre_in_want || custom_feed_bands.contains(&artist.name.to_lowercase())
-                })
-                .collect::<Vec<_>>()
-        }
-    } else {
-        releases
-    };
-
-    let content = releases_to_html(releases);
+    let releases = todays_releases(ctx.custom_feed_id, calendar_repo, feed_repo)?;
+    let changes = recent_date_changes(ctx.custom_feed_id, 1, calendar_repo, feed_repo)?;
+    let content = releases_to_html(releases) + &date_changes_to_html(&changes);
 
     let channel = if content.is_empty() {
-        build_channel(pub_date.clone(), link_feed.into(), image)
+        build_channel(ctx.pub_date.clone(), ctx.link_feed.clone(), image)
     } else {
         let mut guid = Guid::default();
-        guid.set_value(date.to_string());
+        guid.set_value(ctx.date.to_string());
 
         let item = ItemBuilder::default()
-            .title(date.clone())
-            .pub_date(pub_date.clone())
+            .title(ctx.date.clone())
+            .pub_date(ctx.pub_date.clone())
             .content(content)
             .guid(guid)
-            .link(Some(link_item.into()))
+            .link(Some(ctx.link_item.clone()))
             .build();
 
-        let channel = build_channel_with_items(&pub_date, link_feed.into(), image, vec![item]);
+        let channel = build_channel_with_items(&ctx.pub_date, &ctx.link_feed, image, vec![item]);
 
-        if let Err(err) = feed_repo.create(date_int, &channel.to_string(), custom_feed_id) {
+        if let Err(err) = feed_repo.create(
+            ctx.date_int,
+            &channel.to_string(),
+            ctx.custom_feed_id,
+            FeedFormat::Rss,
+        ) {
             error!("Error creating feed: {err}")
         }
 
@@ -367,16 +380,190 @@ fn create_new_feed(
     Ok(channel)
 }
 
-fn contains_any_keywords(genre: &str, keywords: &str) -> bool {
-    let normalized_genre = genre.to_lowercase();
-    let genre_words: Vec<&str> = normalized_genre
-        .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+/// Creates a channel where each release of the past week is its own item.
+fn create_items_channel(
+    mut ctx: FeedContext,
+    calendar_repo: &Arc<dyn CalendarRepository + Send + Sync>,
+    feed_repo: &Arc<dyn FeedRepository + Send + Sync>,
+) -> Result<Channel> {
+    let today = date_now().date();
+    let num_days = config().FEED_ITEMS_DAYS;
+    let releases = calendar_repo.get_between(today - Duration::days(num_days - 1), today)?;
+    let releases = filter_custom_feed(releases, ctx.custom_feed_id, feed_repo)?;
+
+    ctx.link_feed.push_str(if ctx.custom_feed_id > -1 {
+        "&mode=items"
+    } else {
+        "?mode=items"
+    });
+
+    let changes = recent_date_changes(ctx.custom_feed_id, num_days, calendar_repo, feed_repo)?;
+
+    let items = changes
+        .iter()
+        .map(|(change, release, artist)| date_change_to_item(change, release, artist))
+        .chain(
+            releases
+                .iter()
+                .rev()
+                .map(|(release, artist)| release_to_item(release, artist)),
+        )
         .collect();
 
-    keywords
-        .to_lowercase()
-        .split('@')
-        .any(|keyword| genre_words.iter().any(|&word| word.contains(keyword)))
+    let image = channel_image();
+
+    let mut channel = build_channel_with_items(&ctx.pub_date, &ctx.link_feed, image, items);
+    channel
+        .namespaces
+        .insert(String::from("media"), String::from(MEDIA_NAMESPACE));
+    Ok(channel)
+}
+
+/// The namespace of the Media RSS extension, which holds the thumbnails of the covers.
+const MEDIA_NAMESPACE: &str = "http://search.yahoo.com/mrss/";
+
+fn release_to_item(release: &Release, artist: &Artist) -> Item {
+    let mut guid = Guid::default();
+    guid.set_value(release.uid());
+    guid.set_permalink(false);
+
+    let mut categories = Vec::new();
+    if let Some(genre) = &artist.genre {
+        categories.push(CategoryBuilder::default().name(genre).build());
+    }
+    if let Some(release_type) = &release.release_type {
+        categories.push(
+            CategoryBuilder::default()
+                .name(release_type)
+                .domain(Some(String::from("release_type")))
+                .build(),
+        );
+    }
+
+    let pub_date = release
+        .date()
+        .with_time(Time::MIDNIGHT)
+        .assume_offset(UtcOffset::UTC)
+        .format(&Rfc2822)
+        .unwrap_or_default();
+
+    let mut item = ItemBuilder::default();
+    if let Some(url) = release.cover_url(&config().HOST_URL) {
+        let thumbnail = ExtensionBuilder::default()
+            .name("media:thumbnail")
+            .attrs(BTreeMap::from([(String::from("url"), url.clone())]))
+            .build();
+        item.extensions(BTreeMap::from([(
+            String::from("media"),
+            BTreeMap::from([(String::from("thumbnail"), vec![thumbnail])]),
+        )]));
+
+        if let Some(size) = release.cover_size {
+            item.enclosure(
+                EnclosureBuilder::default()
+                    .url(url)
+                    .length(size.to_string())
+                    .mime_type("image/jpeg")
+                    .build(),
+            );
+        }
+    }
+
+    item.title(format!("{} – {}", artist.name, release.album))
+        .link(Some(release.permalink(artist, &config().HOST_URL)))
+        .pub_date(pub_date)
+        .content(format!(
+            "<ul id=\"feeds__container\">{}</ul>",
+            release.to_html(artist)
+        ))
+        .categories(categories)
+        .guid(guid)
+        .build()
+}
+
+/// Creates an item announcing that the date of a release changed.
+fn date_change_to_item(change: &ReleaseChange, release: &Release, artist: &Artist) -> Item {
+    let mut guid = Guid::default();
+    guid.set_value(change.uid(release));
+    guid.set_permalink(false);
+
+    let pub_date = OffsetDateTime::from_unix_timestamp(change.changed_at)
+        .unwrap_or(OffsetDateTime::UNIX_EPOCH)
+        .format(&Rfc2822)
+        .unwrap_or_default();
+
+    ItemBuilder::default()
+        .title(change.title(release, artist))
+        .link(Some(release.permalink(artist, &config().HOST_URL)))
+        .pub_date(pub_date)
+        .content(format!(
+            "<ul id=\"feeds__container\">{}</ul>",
+            change.to_html(release, artist)
+        ))
+        .guid(guid)
+        .build()
+}
+
+/// Fetches the release date changes detected in the past days, filtered by the
+/// custom feed when there is one.
+fn recent_date_changes(
+    custom_feed_id: i32,
+    num_days: i64,
+    calendar_repo: &Arc<dyn CalendarRepository + Send + Sync>,
+    feed_repo: &Arc<dyn FeedRepository + Send + Sync>,
+) -> Result<Vec<(ReleaseChange, Release, Artist)>> {
+    let since = (date_now() - Duration::days(num_days)).unix_timestamp();
+    let changes = calendar_repo.date_changes(since)?;
+
+    if custom_feed_id > -1 {
+        let custom_feed = feed_repo.get_custom_feed(custom_feed_id)?;
+        Ok(changes
+            .into_iter()
+            .filter(|(_change, release, artist)| custom_feed.matches(release, artist))
+            .collect())
+    } else {
+        Ok(changes)
+    }
+}
+
+/// Fetches today's releases, filtered by the custom feed when there is one.
+fn todays_releases(
+    custom_feed_id: i32,
+    calendar_repo: &Arc<dyn CalendarRepository + Send + Sync>,
+    feed_repo: &Arc<dyn FeedRepository + Send + Sync>,
+) -> Result<Vec<(Release, Artist)>> {
+    let releases = calendar_repo.get().map_err(|err| {
+        error!("Error fetching calendar: {}", err);
+        err
+    })?;
+
+    filter_custom_feed(releases, custom_feed_id, feed_repo)
+}
+
+/// Keeps the releases of the custom feed, or all of them when there is none.
+fn filter_custom_feed(
+    releases: Vec<(Release, Artist)>,
+    custom_feed_id: i32,
+    feed_repo: &Arc<dyn FeedRepository + Send + Sync>,
+) -> Result<Vec<(Release, Artist)>> {
+    if custom_feed_id > -1 {
+        let custom_feed = feed_repo.get_custom_feed(custom_feed_id)?;
+        Ok(releases
+            .into_iter()
+            .filter(|(release, artist)| custom_feed.matches(release, artist))
+            .collect())
+    } else {
+        Ok(releases)
+    }
+}
+
+/// Returns the favicon shown as the image of the RSS channels.
+fn channel_image() -> Image {
+    let image_url = format!("{}/public/favicon.png", config().HOST_URL);
+    rss::ImageBuilder::default()
+        .link(&image_url)
+        .url(image_url)
+        .build()
 }
 
 fn build_channel(pub_date: String, link: String, image: Image) -> Channel {
@@ -422,27 +609,443 @@ fn build_channel_from_existing(channel: Channel, items: Vec<Item>) -> Channel {
         .build()
 }
 
+/// Generates today's feeds again in every format, for the main feed and every custom feed.
+///
+/// The feeds cached today are deleted first, so that the regenerated ones list the
+/// releases as they are now in the database, e.g. after the calendar was updated.
+///
+/// Returns the number of feeds that were regenerated, the main feed included.
+pub fn regenerate_todays_feeds(
+    calendar_repo: &Arc<dyn CalendarRepository + Send + Sync>,
+    feed_repo: &Arc<dyn FeedRepository + Send + Sync>,
+) -> Result<usize> {
+    let now = date_now();
+    let custom_feed_ids = std::iter::once(-1)
+        .chain(feed_repo.custom_feeds()?.iter().map(|feed| feed.id))
+        .collect::<Vec<_>>();
+
+    let date_int = FeedContext::new(now, "feed.xml", -1)
+        .ok_or(Error::ParseFail)?
+        .date_int;
+    feed_repo.delete_on(date_int)?;
+
+    for &custom_feed_id in &custom_feed_ids {
+        let (Some(ctx_rss), Some(ctx_atom), Some(ctx_json)) = (
+            FeedContext::new(now, "feed.xml", custom_feed_id),
+            FeedContext::new(now, "feed.atom", custom_feed_id),
+            FeedContext::new(now, "feed.json", custom_feed_id),
+        ) else {
+            return Err(Error::ParseFail);
+        };
+
+        create_new_feed(&ctx_rss, channel_image(), calendar_repo, feed_repo)?;
+        create_new_atom_entry(&ctx_atom, calendar_repo, feed_repo)?;
+        create_new_json_item(&ctx_json, calendar_repo, feed_repo)?;
+    }
+
+    Ok(custom_feed_ids.len())
+}
+
+async fn feed_atom_handler(
+    State(state): State<AppState>,
+    feed_query: Query<FeedQuery>,
+) -> impl IntoResponse {
+    let custom_feed_id = feed_query.id.unwrap_or(-1);
+    let Some(ctx) = FeedContext::new(date_now(), "feed.atom", custom_feed_id) else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Could not parse today's date.",
+        )
+            .into_response();
+    };
+
+    match state
+        .feed_repo
+        .get(config().FEED_MAX_ITEMS, custom_feed_id, FeedFormat::Atom)
+    {
+        Ok(feeds) => (
+            [(CONTENT_TYPE, "application/atom+xml;charset=UTF-8")],
+            create_atom_feed(feeds, &ctx, &state.calendar_repo, &state.feed_repo).to_string(),
+        )
+            .into_response(),
+        Err(err) => {
+            error!("getting releases today {}: {err}", date_now());
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Could not fetch today's releases.",
+            )
+                .into_response()
+        }
+    }
+}
+
+fn create_atom_feed(
+    feeds: Vec<Feed>,
+    ctx: &FeedContext,
+    calendar_repo: &Arc<dyn CalendarRepository + Send + Sync>,
+    feed_repo: &Arc<dyn FeedRepository + Send + Sync>,
+) -> AtomFeed {
+    let mut entries = feeds
+        .iter()
+        .filter_map(|feed| {
+            AtomFeed::read_from(feed.feed.as_bytes())
+                .ok()
+                .and_then(|feed| feed.entries.first().cloned())
+        })
+        .collect::<Vec<_>>();
+
+    let is_cached = feeds.first().is_some_and(|feed| feed.date == ctx.date_int);
+    if !is_cached {
+        match create_new_atom_entry(ctx, calendar_repo, feed_repo) {
+            Ok(Some(entry)) => entries.insert(0, entry),
+            Ok(None) => {}
+            Err(err) => error!("Error creating new Atom entry: {err}"),
+        }
+    }
+
+    build_atom_feed(ctx, entries)
+}
+
+/// Creates today's Atom entry and caches it in the database.
+///
+/// Returns `None` when there are no releases today.
+fn create_new_atom_entry(
+    ctx: &FeedContext,
+    calendar_repo: &Arc<dyn CalendarRepository + Send + Sync>,
+    feed_repo: &Arc<dyn FeedRepository + Send + Sync>,
+) -> Result<Option<Entry>> {
+    let releases = todays_releases(ctx.custom_feed_id, calendar_repo, feed_repo)?;
+    let changes = recent_date_changes(ctx.custom_feed_id, 1, calendar_repo, feed_repo)?;
+    if releases.is_empty() && changes.is_empty() {
+        return Ok(None);
+    }
+
+    let entry = EntryBuilder::default()
+        .id(format!(
+            "tag:{},{}:calendar/{}/{}",
+            host_name(&config().HOST_URL),
+            ctx.date_int / 10000,
+            ctx.date_int,
+            ctx.custom_feed_id
+        ))
+        .title(ctx.date.clone())
+        .updated(parse_fixed_date_time(&ctx.updated))
+        .published(Some(parse_fixed_date_time(&ctx.updated)))
+        .link(
+            LinkBuilder::default()
+                .href(ctx.link_item.clone())
+                .rel("alternate")
+                .mime_type(Some(String::from("text/html")))
+                .build(),
+        )
+        .content(Some(
+            ContentBuilder::default()
+                .content_type(Some(String::from("html")))
+                .value(Some(
+                    releases_to_html(releases) + &date_changes_to_html(&changes),
+                ))
+                .build(),
+        ))
+        .build();
+
+    let feed = build_atom_feed(ctx, vec![entry.clone()]);
+    if let Err(err) = feed_repo.create(
+        ctx.date_int,
+        &feed.to_string(),
+        ctx.custom_feed_id,
+        FeedFormat::Atom,
+    ) {
+        error!("Error creating feed: {err}")
+    }
+
+    Ok(Some(entry))
+}
+
+fn build_atom_feed(ctx: &FeedContext, entries: Vec<Entry>) -> AtomFeed {
+    let base_url = &config().HOST_URL;
+
+    let updated = entries
+        .first()
+        .map(|entry| entry.updated)
+        .unwrap_or_else(|| parse_fixed_date_time(&ctx.updated));
+
+    FeedBuilder::default()
+        .id(ctx.link_feed.clone())
+        .title("Heavy Metal Releases")
+        .subtitle(Some(
+            "A feed for the latest heavy metal album releases.".into(),
+        ))
+        .updated(updated)
+        .author(
+            PersonBuilder::default()
+                .name("Heavy Metal Releases")
+                .uri(Some(base_url.clone()))
+                .build(),
+        )
+        .links(vec![
+            LinkBuilder::default()
+                .href(ctx.link_feed.clone())
+                .rel("self")
+                .mime_type(Some(String::from("application/atom+xml")))
+                .build(),
+            LinkBuilder::default()
+                .href(format!("{base_url}/calendar"))
+                .rel("alternate")
+                .mime_type(Some(String::from("text/html")))
+                .build(),
+        ])
+        .icon(Some(format!("{base_url}/public/favicon.png")))
+        .lang(Some(String::from("en-US")))
+        .entries(entries)
+        .build()
+}
+
+async fn feed_json_handler(
+    State(state): State<AppState>,
+    feed_query: Query<FeedQuery>,
+) -> impl IntoResponse {
+    let custom_feed_id = feed_query.id.unwrap_or(-1);
+    let Some(ctx) = FeedContext::new(date_now(), "feed.json", custom_feed_id) else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Could not parse today's date.",
+        )
+            .into_response();
+    };
+
+    match state
+        .feed_repo
+        .get(config().FEED_MAX_ITEMS, custom_feed_id, FeedFormat::Json)
+    {
+        Ok(feeds) => {
+            let feed = create_json_feed(feeds, &ctx, &state.calendar_repo, &state.feed_repo);
+            (
+                [(CONTENT_TYPE, "application/feed+json;charset=UTF-8")],
+                serde_json::to_string(&feed).unwrap_or_default(),
+            )
+                .into_response()
+        }
+        Err(err) => {
+            error!("getting releases today {}: {err}", date_now());
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Could not fetch today's releases.",
+            )
+                .into_response()
+        }
+    }
+}
+
+fn create_json_feed(
+    feeds: Vec<Feed>,
+    ctx: &FeedContext,
+    calendar_repo: &Arc<dyn CalendarRepository + Send + Sync>,
+    feed_repo: &Arc<dyn FeedRepository + Send + Sync>,
+) -> JsonFeed {
+    let mut items = feeds
+        .iter()
+        .filter_map(|feed| {
+            serde_json::from_str::<JsonFeed>(&feed.feed)
+                .ok()
+                .and_then(|feed| feed.items.first().cloned())
+        })
+        .collect::<Vec<_>>();
+
+    let is_cached = feeds.first().is_some_and(|feed| feed.date == ctx.date_int);
+    if !is_cached {
+        match create_new_json_item(ctx, calendar_repo, feed_repo) {
+            Ok(Some(item)) => items.insert(0, item),
+            Ok(None) => {}
+            Err(err) => error!("Error creating new JSON Feed item: {err}"),
+        }
+    }
+
+    build_json_feed(ctx, items)
+}
+
+/// Creates today's JSON Feed item and caches it in the database.
+///
+/// Returns `None` when there are no releases today.
+fn create_new_json_item(
+    ctx: &FeedContext,
+    calendar_repo: &Arc<dyn CalendarRepository + Send + Sync>,
+    feed_repo: &Arc<dyn FeedRepository + Send + Sync>,
+) -> Result<Option<JsonFeedItem>> {
+    let releases = todays_releases(ctx.custom_feed_id, calendar_repo, feed_repo)?;
+    let changes = recent_date_changes(ctx.custom_feed_id, 1, calendar_repo, feed_repo)?;
+    if releases.is_empty() && changes.is_empty() {
+        return Ok(None);
+    }
+
+    let metal = MetalExtension {
+        releases: releases
+            .iter()
+            .map(|(release, artist)| MetalRelease::new(release, artist))
+            .collect(),
+    };
+
+    let item = JsonFeedItem {
+        id: format!("{}-{}", ctx.date_int, ctx.custom_feed_id),
+        url: ctx.link_item.clone(),
+        title: ctx.date.clone(),
+        content_html: releases_to_html(releases) + &date_changes_to_html(&changes),
+        date_published: ctx.updated.clone(),
+        tags: metal.genres(),
+        metal,
+    };
+
+    let feed = build_json_feed(ctx, vec![item.clone()]);
+    match serde_json::to_string(&feed) {
+        Ok(feed) => {
+            if let Err(err) =
+                feed_repo.create(ctx.date_int, &feed, ctx.custom_feed_id, FeedFormat::Json)
+            {
+                error!("Error creating feed: {err}")
+            }
+        }
+        Err(err) => error!("Error serializing JSON Feed: {err}"),
+    }
+
+    Ok(Some(item))
+}
+
+fn build_json_feed(ctx: &FeedContext, items: Vec<JsonFeedItem>) -> JsonFeed {
+    let base_url = &config().HOST_URL;
+
+    JsonFeed {
+        version: String::from(json_feed::VERSION),
+        title: String::from("Heavy Metal Releases"),
+        home_page_url: format!("{base_url}/calendar"),
+        feed_url: ctx.link_feed.clone(),
+        description: String::from("A feed for the latest heavy metal album releases."),
+        icon: format!("{base_url}/public/logo/android-chrome-512x512.png"),
+        favicon: format!("{base_url}/public/favicon.png"),
+        language: String::from("en-US"),
+        authors: vec![JsonFeedAuthor {
+            name: String::from("Heavy Metal Releases"),
+            url: base_url.clone(),
+        }],
+        items,
+    }
+}
+
+async fn ics_handler(
+    State(state): State<AppState>,
+    feed_query: Query<FeedQuery>,
+) -> impl IntoResponse {
+    let now = date_now();
+    let from = now.date() - Duration::days(30);
+    let to = now.date() + Duration::days(365);
+
+    let releases = match state.calendar_repo.get_between(from, to) {
+        Ok(releases) => releases,
+        Err(err) => {
+            error!("getting releases between {from} and {to}: {err}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Could not fetch the releases.",
+            )
+                .into_response();
+        }
+    };
+
+    let releases = match feed_query.id.filter(|&id| id > -1) {
+        Some(id) => match state.feed_repo.get_custom_feed(id) {
+            Ok(custom_feed) => releases
+                .into_iter()
+                .filter(|(release, artist)| custom_feed.matches(release, artist))
+                .collect(),
+            Err(_) => return (StatusCode::NOT_FOUND, "Custom feed not found.").into_response(),
+        },
+        None => releases,
+    };
+
+    let utc = now.to_offset(UtcOffset::UTC);
+    let dtstamp = format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        utc.year(),
+        utc.month() as u8,
+        utc.day(),
+        utc.hour(),
+        utc.minute(),
+        utc.second()
+    );
+    let host = host_name(&config().HOST_URL);
+
+    let mut ics = [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "PRODID:-//Heavy Metal Releases//Heavy Metal Notifier//EN",
+        "CALSCALE:GREGORIAN",
+        "METHOD:PUBLISH",
+        "X-WR-CALNAME:Heavy Metal Releases",
+        "X-WR-CALDESC:Upcoming heavy metal album releases.",
+        "REFRESH-INTERVAL;VALUE=DURATION:P1D",
+        "X-PUBLISHED-TTL:P1D",
+    ]
+    .iter()
+    .map(|line| ical::fold(line))
+    .collect::<String>();
+
+    for (release, artist) in &releases {
+        ics.push_str(&release.to_vevent(artist, &dtstamp, host));
+    }
+    ics.push_str(&ical::fold("END:VCALENDAR"));
+
+    (
+        [
+            (CONTENT_TYPE, "text/calendar;charset=UTF-8"),
+            (CONTENT_DISPOSITION, "inline; filename=\"releases.ics\""),
+        ],
+        ics,
+    )
+        .into_response()
+}
+
+fn parse_fixed_date_time(date: &str) -> FixedDateTime {
+    FixedDateTime::parse_from_rfc3339(date).unwrap_or_default()
+}
+
+/// Extracts the host name from a URL, e.g. `www.metal-releases.com`
+/// from `https://www.metal-releases.com:7125`.
+fn host_name(url: &str) -> &str {
+    let host = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+
+    host.split([':', '/']).next().unwrap_or(host)
+}
+
 #[derive(Deserialize)]
 struct GenerateFeedForm {
     #[serde(default)]
     bands: Vec<String>,
     #[serde(default)]
     genres: Vec<String>,
+    #[serde(default)]
+    countries: Vec<String>,
+    #[serde(default)]
+    labels: Vec<String>,
 }
 
 async fn feed_post_handler(
     State(state): State<AppState>,
     Form(form): Form<GenerateFeedForm>,
 ) -> impl IntoResponse {
-    match state
-        .feed_repo
-        .get_or_create_custom_feed(form.bands, form.genres)
-    {
+    match state.feed_repo.get_or_create_custom_feed(
+        form.bands,
+        form.genres,
+        form.countries,
+        form.labels,
+    ) {
         None => Redirect::to("/calendar/feed.xml").into_response(),
         Some(id) => {
             let url = &format!("{}/calendar/feed.xml?id={id}", config().HOST_URL);
             let input = format!(
-                "<input id=\"custom_link\" hx-swap-oob=\"true\" readonly type=\"text\" placeholder=\"Your custom link\" class=\"input input-bordered w-full mt-1\" value=\"{url}\">"
+                "<input id=\"custom_link\" hx-swap-oob=\"true\" readonly type=\"text\" placeholder=\"Your custom link\" class=\"input input-bordered w-full mt-1\" value=\"{url}\">\
+                 <input id=\"subscribe_feed_id\" hx-swap-oob=\"true\" type=\"hidden\" name=\"feed_id\" value=\"{id}\">\
+                 <input id=\"webhook_feed_id\" hx-swap-oob=\"true\" type=\"hidden\" name=\"feed_id\" value=\"{id}\">"
             );
             (StatusCode::OK, input).into_response()
         }
@@ -462,6 +1065,21 @@ async fn releases_handler(
     }
 }
 
+/// Lists the release date changes under a heading, or nothing when there are none.
+fn date_changes_to_html(changes: &[(ReleaseChange, Release, Artist)]) -> String {
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    changes.iter().fold(
+        String::from("<h3>Date changes</h3><ul>"),
+        |mut acc, (change, release, artist)| {
+            acc.push_str(&change.to_html(release, artist));
+            acc
+        },
+    ) + "</ul>"
+}
+
 fn releases_to_html(releases: Vec<(Release, Artist)>) -> String {
     releases.iter().fold(
         String::from("<ol id=\"feeds__container\" class=\"list-disc\">"),
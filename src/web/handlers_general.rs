@@ -1,14 +1,27 @@
-use axum::{Router, response::Redirect, routing::get};
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::HeaderMap,
+    response::Redirect,
+    routing::get,
+};
+use maud::Markup;
+use serde::Deserialize;
+use tracing::error;
 
-use super::templates::main::*;
+use super::templates::{main::*, search::search};
 use crate::web::AppState;
 
+/// The maximum number of results of the search page.
+const MAX_SEARCH_RESULTS: i64 = 100;
+
 /// Defines the routes for general endpoints of the web application.
 pub fn routes_general() -> Router<AppState> {
     Router::new()
         .route("/", get(index))
         .route("/about", get(about_handler))
         .route("/contact", get(contact_handler).post(contact_post_handler))
+        .route("/search", get(search_handler))
         .route("/sitemap", get(sitemap_handler))
         .route("/tos", get(tos))
 }
@@ -16,3 +29,26 @@ pub fn routes_general() -> Router<AppState> {
 async fn sitemap_handler() -> Redirect {
     Redirect::to("/public/sitemap.xml")
 }
+
+/// Represents the query of the search page.
+#[derive(Deserialize)]
+struct SearchQuery {
+    #[serde(default)]
+    q: String,
+}
+
+async fn search_handler(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+) -> Markup {
+    let results = state
+        .entities_repo
+        .search(&query.q, 0, MAX_SEARCH_RESULTS)
+        .unwrap_or_else(|err| {
+            error!("Error searching '{}': {err}", query.q);
+            Vec::new()
+        });
+
+    search(&query.q, &results, headers)
+}
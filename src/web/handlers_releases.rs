@@ -0,0 +1,110 @@
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::HeaderMap,
+    response::{IntoResponse, Redirect},
+    routing::get,
+};
+use reqwest::StatusCode;
+use tracing::error;
+
+use super::templates::release::release;
+use crate::{error::Error, web::AppState};
+
+/// Defines the routes for the release pages.
+pub fn routes_releases() -> Router<AppState> {
+    Router::new().route("/:permalink", get(release_handler))
+}
+
+/// Serves the page of a release at its permalink, e.g. `/releases/42-wintersun-time-ii`.
+///
+/// The release is found by the ID the permalink starts with. Any other slug, or none,
+/// redirects to the permalink so that a release is shared under a single URL. A release
+/// merged into another redirects to it, and a hidden release is not found.
+async fn release_handler(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(permalink): Path<String>,
+) -> impl IntoResponse {
+    let Some((id, slug)) = parse_permalink(&permalink) else {
+        return (StatusCode::NOT_FOUND, "404 Not Found").into_response();
+    };
+
+    let (release_row, artist) = match state.entities_repo.release(id) {
+        Ok(release) => release,
+        Err(Error::EntityNotFound { .. }) => {
+            return (StatusCode::NOT_FOUND, "404 Not Found").into_response();
+        }
+        Err(err) => {
+            error!("Error fetching release {id}: {err}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    if let Some(kept_id) = release_row.merged_into
+        && let Ok((kept, kept_artist)) = state.entities_repo.release(kept_id)
+    {
+        return Redirect::permanent(&kept.permalink(&kept_artist, "")).into_response();
+    }
+
+    if release_row.is_hidden {
+        return (StatusCode::NOT_FOUND, "404 Not Found").into_response();
+    }
+
+    if slug != release_row.slug(&artist) {
+        return Redirect::permanent(&release_row.permalink(&artist, "")).into_response();
+    }
+
+    let (details, tracks) = state
+        .entities_repo
+        .release_tracklist(id)
+        .unwrap_or_else(|err| {
+            error!("Error fetching the tracklist of release {id}: {err}");
+            (None, Vec::new())
+        });
+
+    let date = release_row.date();
+    let same_day = state
+        .calendar_repo
+        .get_by_date(date.year() as u32, date.month() as u8, date.day())
+        .unwrap_or_else(|err| {
+            error!("Error fetching the releases of {date}: {err}");
+            Vec::new()
+        })
+        .into_iter()
+        .filter(|(other, _artist)| other.id != release_row.id)
+        .collect::<Vec<_>>();
+
+    release(
+        &release_row,
+        &artist,
+        details.as_ref(),
+        &tracks,
+        &same_day,
+        headers,
+    )
+    .into_response()
+}
+
+/// Splits a permalink into the ID of the release and its slug, e.g. `42` and
+/// `wintersun-time-ii` for `42-wintersun-time-ii`.
+fn parse_permalink(permalink: &str) -> Option<(i32, &str)> {
+    let (id, slug) = permalink.split_once('-').unwrap_or((permalink, ""));
+    Some((id.parse().ok()?, slug))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_permalink_ok() {
+        pretty_assertions::assert_eq!(
+            parse_permalink("42-wintersun-time-ii"),
+            Some((42, "wintersun-time-ii"))
+        );
+        pretty_assertions::assert_eq!(parse_permalink("42"), Some((42, "")));
+        pretty_assertions::assert_eq!(parse_permalink("wintersun-time-ii"), None);
+        pretty_assertions::assert_eq!(parse_permalink(""), None);
+    }
+}
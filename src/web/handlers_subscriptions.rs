@@ -0,0 +1,244 @@
+use axum::{
+    Form, Router,
+    extract::{Path, Query, State},
+    response::{Html, IntoResponse},
+    routing::{get, post},
+};
+use maud::{Markup, html};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tracing::{error, warn};
+
+use super::templates::subscriptions::{confirmation_email, message_page};
+use crate::{
+    config::config,
+    date_now,
+    model::Frequency,
+    support::{
+        email::send_html_email,
+        token::{self, Purpose},
+    },
+    web::AppState,
+};
+
+/// Defines the routes for the email subscription endpoints.
+pub fn routes_subscriptions() -> Router<AppState> {
+    Router::new()
+        .route("/", post(subscribe_handler))
+        .route("/:id/confirm", get(confirm_handler))
+        .route(
+            "/:id/unsubscribe",
+            get(unsubscribe_handler).post(unsubscribe_handler),
+        )
+}
+
+/// Represents the subscription form of the home page.
+#[derive(Deserialize)]
+struct SubscribeForm {
+    email: String,
+    frequency: String,
+    #[serde(default = "default_feed_id")]
+    feed_id: i32,
+}
+
+fn default_feed_id() -> i32 {
+    -1
+}
+
+/// Represents the signed token of the links sent by email.
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: String,
+}
+
+/// Subscribes an email address and sends it the confirmation link.
+///
+/// The change of frequency of a confirmed subscription is confirmed by a new link as
+/// well. At most one confirmation email is sent to a subscriber per hour.
+async fn subscribe_handler(
+    State(state): State<AppState>,
+    Form(form): Form<SubscribeForm>,
+) -> impl IntoResponse {
+    let (Some(smtp), Some(key)) = (&config().smtp, &config().SECRET_KEY) else {
+        warn!("Email subscriptions are disabled. Email: {}", form.email);
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            message("Email subscriptions are disabled on this server."),
+        );
+    };
+
+    let Ok(frequency) = form.frequency.parse::<Frequency>() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            message("Please choose a daily or weekly digest."),
+        );
+    };
+
+    if form.email.trim().parse::<lettre::Address>().is_err() {
+        return (
+            StatusCode::BAD_REQUEST,
+            message("Please enter a valid email address."),
+        );
+    }
+
+    if form.feed_id > -1 && state.feed_repo.get_custom_feed(form.feed_id).is_err() {
+        return (
+            StatusCode::BAD_REQUEST,
+            message("The custom feed does not exist. Please generate it again."),
+        );
+    }
+
+    let subscriber = match state
+        .subscriber_repo
+        .create(&form.email, form.feed_id, frequency)
+    {
+        Ok(subscriber) => subscriber,
+        Err(err) => {
+            error!("Error creating subscriber: {err}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                message("Could not subscribe. Please try again later."),
+            );
+        }
+    };
+
+    if !subscriber.awaits_confirmation() {
+        return (
+            StatusCode::OK,
+            message("You are already subscribed to this digest."),
+        );
+    }
+
+    let done = if subscriber.is_confirmed {
+        message("Please click the link sent to your email address to confirm the new frequency.")
+    } else {
+        message("Almost there! Please click the link sent to your email address to confirm.")
+    };
+
+    let now = date_now().unix_timestamp();
+    if !subscriber.can_resend_confirmation(now) {
+        return (StatusCode::OK, done);
+    }
+    if let Err(err) = state
+        .subscriber_repo
+        .mark_confirmation_sent(subscriber.id, now)
+    {
+        error!(
+            "Error recording the confirmation of subscriber {}: {err}",
+            subscriber.id
+        );
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            message("Could not subscribe. Please try again later."),
+        );
+    }
+
+    let confirm_url = format!(
+        "{}/subscriptions/{}/confirm?token={}",
+        config().HOST_URL,
+        subscriber.id,
+        token::sign(key, Purpose::Confirm, subscriber.id, &subscriber.email)
+    );
+    tokio::task::spawn_blocking(move || {
+        send_html_email(
+            smtp,
+            &subscriber.email,
+            "Confirm your Heavy Metal Releases subscription",
+            confirmation_email(&confirm_url),
+            None,
+        )
+    });
+
+    (StatusCode::OK, done)
+}
+
+/// Confirms the subscription when the token of the link is valid.
+async fn confirm_handler(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(query): Query<TokenQuery>,
+) -> impl IntoResponse {
+    let Some(key) = &config().SECRET_KEY else {
+        return (StatusCode::SERVICE_UNAVAILABLE, invalid_link_page());
+    };
+
+    match state.subscriber_repo.get(id) {
+        Ok(subscriber)
+            if token::verify(key, Purpose::Confirm, id, &subscriber.email, &query.token) =>
+        {
+            if let Err(err) = state.subscriber_repo.confirm(id) {
+                error!("Error confirming subscriber {id}: {err}");
+                return (StatusCode::INTERNAL_SERVER_ERROR, invalid_link_page());
+            }
+
+            (
+                StatusCode::OK,
+                Html(
+                    message_page(
+                        "Subscription confirmed",
+                        "Your subscription is confirmed. The releases will be sent to your inbox. Horns up! \\m/",
+                    )
+                    .into_string(),
+                ),
+            )
+        }
+        _ => (StatusCode::BAD_REQUEST, invalid_link_page()),
+    }
+}
+
+/// Deletes the subscription when the token of the link is valid.
+///
+/// The route accepts both GET, for the link in the email, and POST, for the
+/// one-click unsubscription of email clients supporting `List-Unsubscribe-Post`.
+async fn unsubscribe_handler(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(query): Query<TokenQuery>,
+) -> impl IntoResponse {
+    let Some(key) = &config().SECRET_KEY else {
+        return (StatusCode::SERVICE_UNAVAILABLE, invalid_link_page());
+    };
+
+    match state.subscriber_repo.get(id) {
+        Ok(subscriber)
+            if token::verify(
+                key,
+                Purpose::Unsubscribe,
+                id,
+                &subscriber.email,
+                &query.token,
+            ) =>
+        {
+            if let Err(err) = state.subscriber_repo.delete(id) {
+                error!("Error deleting subscriber {id}: {err}");
+                return (StatusCode::INTERNAL_SERVER_ERROR, invalid_link_page());
+            }
+
+            (
+                StatusCode::OK,
+                Html(
+                    message_page(
+                        "Unsubscribed",
+                        "You will no longer receive emails from us. You can subscribe again anytime from the home page.",
+                    )
+                    .into_string(),
+                ),
+            )
+        }
+        _ => (StatusCode::BAD_REQUEST, invalid_link_page()),
+    }
+}
+
+fn message(text: &str) -> Markup {
+    html!(p #subscribe_message class="mt-1" { (text) })
+}
+
+fn invalid_link_page() -> Html<String> {
+    Html(
+        message_page(
+            "Invalid link",
+            "This link is invalid or the subscription no longer exists.",
+        )
+        .into_string(),
+    )
+}
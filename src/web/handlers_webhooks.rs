@@ -0,0 +1,163 @@
+use axum::{
+    Form, Router,
+    extract::{Path, Query, State},
+    response::{Html, IntoResponse},
+    routing::{get, post},
+};
+use maud::{Markup, html};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tracing::error;
+
+use super::templates::subscriptions::message_page;
+use crate::{
+    config::config,
+    model::WebhookKind,
+    notifier::{SIGNATURE_HEADER, is_valid_url, resolves_to_public, secret},
+    support::token::{self, Purpose},
+    web::AppState,
+};
+
+/// Defines the routes for the webhook endpoints.
+pub fn routes_webhooks() -> Router<AppState> {
+    Router::new()
+        .route("/", post(register_handler))
+        .route("/:id/remove", get(remove_handler).post(remove_handler))
+}
+
+/// Represents the webhook form of the home page.
+#[derive(Deserialize)]
+struct WebhookForm {
+    url: String,
+    kind: String,
+    #[serde(default = "default_feed_id")]
+    feed_id: i32,
+}
+
+fn default_feed_id() -> i32 {
+    -1
+}
+
+/// Represents the signed token of the removal link.
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: String,
+}
+
+/// Registers a webhook and shows its removal link and, for generic JSON
+/// webhooks, the secret with which the payloads are signed.
+async fn register_handler(
+    State(state): State<AppState>,
+    Form(form): Form<WebhookForm>,
+) -> impl IntoResponse {
+    let Some(key) = &config().SECRET_KEY else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            message(html!("Webhooks are disabled on this server.")),
+        );
+    };
+
+    let Ok(kind) = form.kind.parse::<WebhookKind>() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            message(html!("Please choose Discord, Slack or JSON.")),
+        );
+    };
+
+    if !is_valid_url(kind, &form.url) || !resolves_to_public(&form.url).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            message(html!("Please enter a valid " (kind.as_str()) " webhook URL.")),
+        );
+    }
+
+    if form.feed_id > -1 && state.feed_repo.get_custom_feed(form.feed_id).is_err() {
+        return (
+            StatusCode::BAD_REQUEST,
+            message(html!(
+                "The custom feed does not exist. Please generate it again."
+            )),
+        );
+    }
+
+    let webhook = match state.webhook_repo.create(&form.url, kind, form.feed_id) {
+        Ok(webhook) => webhook,
+        Err(err) => {
+            error!("Error creating webhook: {err}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                message(html!(
+                    "Could not register the webhook. Please try again later."
+                )),
+            );
+        }
+    };
+
+    let remove_url = format!(
+        "{}/webhooks/{}/remove?token={}",
+        config().HOST_URL,
+        webhook.id,
+        token::sign(key, Purpose::RemoveWebhook, webhook.id, &webhook.url)
+    );
+
+    (
+        StatusCode::OK,
+        message(html!(
+            "Webhook registered! Keep this link to remove it: "
+            a href=(remove_url) class="link link-primary visited:link-secondary focus:link-accent break-all" { (remove_url) }
+            @if kind == WebhookKind::Json {
+                br;
+                "The payloads are signed in the " code { (SIGNATURE_HEADER) } " header with the secret "
+                code class="break-all" { (secret(key, &webhook)) }
+            }
+        )),
+    )
+}
+
+/// Removes the webhook when the token of the link is valid.
+async fn remove_handler(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(query): Query<TokenQuery>,
+) -> impl IntoResponse {
+    let Some(key) = &config().SECRET_KEY else {
+        return (StatusCode::SERVICE_UNAVAILABLE, invalid_link_page());
+    };
+
+    match state.webhook_repo.get(id) {
+        Ok(webhook)
+            if token::verify(key, Purpose::RemoveWebhook, id, &webhook.url, &query.token) =>
+        {
+            if let Err(err) = state.webhook_repo.delete(id) {
+                error!("Error deleting webhook {id}: {err}");
+                return (StatusCode::INTERNAL_SERVER_ERROR, invalid_link_page());
+            }
+
+            (
+                StatusCode::OK,
+                Html(
+                    message_page(
+                        "Webhook removed",
+                        "The webhook will no longer be notified of the releases.",
+                    )
+                    .into_string(),
+                ),
+            )
+        }
+        _ => (StatusCode::BAD_REQUEST, invalid_link_page()),
+    }
+}
+
+fn message(content: Markup) -> Markup {
+    html!(p #webhook_message class="mt-1" { (content) })
+}
+
+fn invalid_link_page() -> Html<String> {
+    Html(
+        message_page(
+            "Invalid link",
+            "This link is invalid or the webhook no longer exists.",
+        )
+        .into_string(),
+    )
+}
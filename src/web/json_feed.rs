@@ -0,0 +1,150 @@
+//! The `json_feed` module defines the types serialized to produce a [JSON Feed 1.1](https://www.jsonfeed.org/version/1.1/).
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::config,
+    model::{Artist, Release},
+};
+
+/// The version URL every JSON Feed 1.1 document must declare.
+pub const VERSION: &str = "https://jsonfeed.org/version/1.1";
+
+/// Represents a JSON Feed document.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonFeed {
+    pub version: String,
+    pub title: String,
+    pub home_page_url: String,
+    pub feed_url: String,
+    pub description: String,
+    pub icon: String,
+    pub favicon: String,
+    pub language: String,
+    pub authors: Vec<JsonFeedAuthor>,
+    pub items: Vec<JsonFeedItem>,
+}
+
+/// Represents the author of a feed or an item.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonFeedAuthor {
+    pub name: String,
+    pub url: String,
+}
+
+/// Represents an item of the feed, i.e. the releases of a day.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonFeedItem {
+    pub id: String,
+    pub url: String,
+    pub title: String,
+    pub content_html: String,
+    pub date_published: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// The custom extension holding the structured releases of the item.
+    #[serde(rename = "_metal")]
+    pub metal: MetalExtension,
+}
+
+/// The `_metal` extension object of an item.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetalExtension {
+    pub releases: Vec<MetalRelease>,
+}
+
+/// The structured representation of a release in the `_metal` extension.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetalRelease {
+    pub artist: String,
+    pub album: String,
+    /// The permalink of the release page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub genre: Option<String>,
+    pub country: Option<String>,
+    pub release_type: Option<String>,
+    pub label: Option<String>,
+    pub url_youtube: String,
+    pub url_bandcamp: Option<String>,
+    pub url_metallum_artist: Option<String>,
+    pub url_metallum_album: Option<String>,
+    /// The URL of the thumbnail of the album cover.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_url: Option<String>,
+}
+
+impl MetalRelease {
+    /// Creates the structured representation of the release by the artist.
+    pub fn new(release: &Release, artist: &Artist) -> Self {
+        Self {
+            artist: artist.name.clone(),
+            album: release.album.clone(),
+            url: Some(release.permalink(artist, &config().HOST_URL)),
+            genre: artist.genre.clone(),
+            country: artist.country.clone(),
+            release_type: release.release_type.clone(),
+            label: release.label.clone(),
+            url_youtube: release.url_youtube.clone(),
+            url_bandcamp: artist.url_bandcamp.clone(),
+            url_metallum_artist: artist.url_metallum.clone(),
+            url_metallum_album: release.url_metallum.clone(),
+            cover_url: release.cover_url(&config().HOST_URL),
+        }
+    }
+}
+
+impl MetalExtension {
+    /// Collects the distinct genres of the releases, in order of appearance.
+    pub fn genres(&self) -> Vec<String> {
+        let mut genres: Vec<String> = Vec::new();
+        for genre in self.releases.iter().filter_map(|r| r.genre.as_ref()) {
+            if !genres.contains(genre) {
+                genres.push(genre.clone());
+            }
+        }
+        genres
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[test]
+    fn test_json_feed_item_serializes_extension_ok() -> Result<()> {
+        let item = JsonFeedItem {
+            id: String::from("20240831"),
+            url: String::from("http://localhost:7125/calendar/2024/8/31"),
+            title: String::from("August 31, 2024"),
+            content_html: String::from("<ol></ol>"),
+            date_published: String::from("2024-08-31T00:00:00Z"),
+            tags: Vec::new(),
+            metal: MetalExtension {
+                releases: vec![MetalRelease {
+                    artist: String::from("Wintersun"),
+                    album: String::from("Time II"),
+                    url: None,
+                    genre: Some(String::from("Symphonic Melodic Death Metal")),
+                    release_type: Some(String::from("Full-length")),
+                    url_youtube: String::from("https://www.youtube.com"),
+                    url_bandcamp: None,
+                    url_metallum_artist: None,
+                    url_metallum_album: None,
+                    country: None,
+                    label: None,
+                    cover_url: None,
+                }],
+            },
+        };
+
+        let got = serde_json::to_value(&item)?;
+
+        pretty_assertions::assert_eq!(got["_metal"]["releases"][0]["artist"], "Wintersun");
+        pretty_assertions::assert_eq!(got.get("tags"), None);
+        pretty_assertions::assert_eq!(serde_json::from_value::<JsonFeedItem>(got)?, item);
+        Ok(())
+    }
+}
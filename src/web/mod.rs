@@ -1,28 +1,54 @@
 //! The `web` module exposes the handlers for the web server.
 
+mod handlers_admin;
+mod handlers_api;
+mod handlers_artists;
 mod handlers_calendar;
 mod handlers_general;
-mod templates;
+mod handlers_releases;
+mod handlers_subscriptions;
+mod handlers_webhooks;
+pub(crate) mod json_feed;
+pub(crate) mod templates;
 
-use axum::{Router, http::Uri, response::IntoResponse, routing::get};
+use axum::{Router, extract::Path, http::Uri, response::IntoResponse, routing::get};
 use reqwest::{StatusCode, header};
 use rust_embed::Embed;
 use std::sync::Arc;
 
 use crate::{
+    config::config,
     error::Result,
-    model::{CalendarRepository, EntitiesRepository, FeedRepository},
+    model::{
+        CalendarRepository, EntitiesRepository, FeedRepository, JobRunRepository,
+        SourceHealthRepository, SubscriberRepository, WebhookRepository,
+    },
+    scraper::cover,
 };
+use handlers_admin::routes_admin;
+use handlers_api::routes_api;
+use handlers_artists::routes_artists;
+pub use handlers_calendar::regenerate_todays_feeds;
 use handlers_calendar::routes_calendar;
 use handlers_general::routes_general;
+use handlers_releases::routes_releases;
+use handlers_subscriptions::routes_subscriptions;
+use handlers_webhooks::routes_webhooks;
 
 /// Shared application state for the Axum web server.
 #[derive(Clone)]
 pub struct AppState {
     pub bands: Vec<String>,
     pub genres: [String; 46],
+    pub countries: Vec<String>,
+    pub labels: Vec<String>,
     pub calendar_repo: Arc<dyn CalendarRepository + Send + Sync>,
+    pub entities_repo: Arc<dyn EntitiesRepository + Send + Sync>,
     pub feed_repo: Arc<dyn FeedRepository + Send + Sync>,
+    pub job_run_repo: Arc<dyn JobRunRepository + Send + Sync>,
+    pub source_health_repo: Arc<dyn SourceHealthRepository + Send + Sync>,
+    pub subscriber_repo: Arc<dyn SubscriberRepository + Send + Sync>,
+    pub webhook_repo: Arc<dyn WebhookRepository + Send + Sync>,
 }
 
 impl AppState {
@@ -30,6 +56,10 @@ impl AppState {
         calendar_repo: Arc<dyn CalendarRepository + Send + Sync>,
         entities_repo: Arc<dyn EntitiesRepository + Send + Sync>,
         feed_repo: Arc<dyn FeedRepository + Send + Sync>,
+        job_run_repo: Arc<dyn JobRunRepository + Send + Sync>,
+        source_health_repo: Arc<dyn SourceHealthRepository + Send + Sync>,
+        subscriber_repo: Arc<dyn SubscriberRepository + Send + Sync>,
+        webhook_repo: Arc<dyn WebhookRepository + Send + Sync>,
     ) -> Self {
         Self {
             bands: entities_repo.bands(),
@@ -81,8 +111,15 @@ impl AppState {
                 String::from("True Norwegian Black Metal"),
                 String::from("Viking Metal"),
             ],
+            countries: entities_repo.countries(),
+            labels: entities_repo.labels(),
             calendar_repo,
+            entities_repo,
             feed_repo,
+            job_run_repo,
+            source_health_repo,
+            subscriber_repo,
+            webhook_repo,
         }
     }
 }
@@ -91,12 +128,30 @@ impl AppState {
 pub async fn routes() -> Result<Router<AppState>> {
     let router = Router::new()
         .merge(routes_general())
+        .nest("/admin", routes_admin())
         .nest("/calendar", routes_calendar())
-        .route("/public/*file", get(static_handler));
+        .nest("/api/v1", routes_api())
+        .nest("/artists", routes_artists())
+        .nest("/releases", routes_releases())
+        .nest("/subscriptions", routes_subscriptions())
+        .nest("/webhooks", routes_webhooks())
+        .route("/public/*file", get(static_handler))
+        .route("/covers/:file", get(cover_handler));
 
     Ok(router)
 }
 
+/// Returns the link trimmed of its trailing slash if it is an HTTP or HTTPS URL.
+fn valid_link(url: &str) -> Option<&str> {
+    let url = url.trim();
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {
+            Some(url.trim_end_matches('/'))
+        }
+        _ => None,
+    }
+}
+
 async fn static_handler(uri: Uri) -> impl IntoResponse {
     let path = uri
         .path()
@@ -107,6 +162,27 @@ async fn static_handler(uri: Uri) -> impl IntoResponse {
     StaticFile(path)
 }
 
+/// Serves the thumbnail of an album cover from the covers folder.
+///
+/// The name of a thumbnail is the hash of its content, so it is cached for a year.
+async fn cover_handler(Path(file): Path<String>) -> impl IntoResponse {
+    if !cover::is_cover_name(&file) {
+        return (StatusCode::NOT_FOUND, "404 Not Found").into_response();
+    }
+
+    match tokio::fs::read(std::path::Path::new(&config().COVERS_DIR).join(&file)).await {
+        Ok(content) => (
+            [
+                (header::CONTENT_TYPE, "image/jpeg"),
+                (header::CACHE_CONTROL, "public, max-age=31536000, immutable"),
+            ],
+            content,
+        )
+            .into_response(),
+        Err(_) => (StatusCode::NOT_FOUND, "404 Not Found").into_response(),
+    }
+}
+
 #[derive(Embed)]
 #[folder = "web/static/"]
 struct Asset;
@@ -0,0 +1,433 @@
+use axum::http::HeaderMap;
+use maud::{Markup, html};
+use time::OffsetDateTime;
+
+use super::{
+    Page,
+    core::{footer, layout},
+};
+use crate::{
+    jobs::ScheduledJob,
+    model::{Artist, JobRun, Release, SourceHealth},
+};
+
+/// The types of release offered when adding one by hand.
+const RELEASE_TYPES: [&str; 7] = [
+    "Full-length",
+    "EP",
+    "Single",
+    "Split",
+    "Compilation",
+    "Live album",
+    "Demo",
+];
+
+/// Generates the dashboard of the administrators with the jobs, the health of the
+/// sources, the search of the releases to correct and the corrections made.
+pub fn dashboard(
+    runs: &[JobRun],
+    health: &[SourceHealth],
+    corrections: &[(Release, Artist)],
+    headers: HeaderMap,
+) -> Markup {
+    let body = html!(
+        h4 class="text-3xl font-bold mb-3" { "Administration" }
+        h5 class="text-2xl font-bold mb-3" { "Jobs" }
+        (job_schedule(runs, true))
+        p class="mb-8" {
+            a href="/admin/jobs" class="link link-primary visited:link-secondary focus:link-accent" { "See the recent runs" }
+        }
+        h5 class="text-2xl font-bold mb-3" { "Sources" }
+        @if health.is_empty() {
+            p class="mb-8" { "No source has run yet." }
+        } @else {
+            table class="table table-sm mb-8" {
+                thead {
+                    tr {
+                        th { "Source" }
+                        th { "Last run (UTC)" }
+                        th { "Last success (UTC)" }
+                        th { "Releases" }
+                        th { "Failures in a row" }
+                        th { "Last error" }
+                    }
+                }
+                tbody {
+                    @for source in health {
+                        tr {
+                            td {
+                                (source.name) " "
+                                @if source.is_failing() {
+                                    span class="badge badge-error" { "failing" }
+                                } @else {
+                                    span class="badge badge-success" { "ok" }
+                                }
+                            }
+                            td class="whitespace-nowrap" { (timestamp(source.last_run_at)) }
+                            td class="whitespace-nowrap" { (source.last_success_at.map(timestamp).unwrap_or_default()) }
+                            td { (source.last_num_releases) }
+                            td { (source.consecutive_failures) }
+                            td class="font-mono text-sm" { (source.last_error.as_deref().unwrap_or_default()) }
+                        }
+                    }
+                }
+            }
+        }
+        h5 class="text-2xl font-bold mb-3" { "Releases and artists" }
+        div class="flex flex-wrap gap-4 items-center mb-2" {
+            input type="search" name="q" placeholder="Search artists and albums to correct"
+                class="input input-bordered w-full md:w-1/2"
+                hx-get="/admin/search" hx-trigger="input changed delay:300ms, search"
+                hx-target="#admin_results" hx-swap="outerHTML";
+            a href="/admin/releases/new" class="btn" { "Add a release" }
+        }
+        (search_results("", &[]))
+        h5 class="text-2xl font-bold mb-3 mt-8" { "Corrections" }
+        @if corrections.is_empty() {
+            p { "No release was hidden or added by hand." }
+        } @else {
+            table class="table table-sm" {
+                tbody {
+                    @for (release, artist) in corrections {
+                        tr {
+                            td class="whitespace-nowrap" { (release.date()) }
+                            td {
+                                a href=(format!("/admin/releases/{}", release.id)) class="link link-primary visited:link-secondary focus:link-accent" {
+                                    (artist.name) " - " (release.album)
+                                }
+                            }
+                            td { (status_text(release)) }
+                        }
+                    }
+                }
+            }
+        }
+    );
+
+    page("Administration", body, headers)
+}
+
+/// Generates the page listing the schedule of the jobs and their most recent runs.
+pub fn jobs(runs: &[JobRun], headers: HeaderMap) -> Markup {
+    let body = html!(
+        h4 class="text-3xl font-bold mb-3" { "Jobs" }
+        (job_schedule(runs, false))
+        h5 class="text-2xl font-bold mb-3 mt-8" { "Recent runs" }
+        @if runs.is_empty() {
+            p { "No job has run yet." }
+        } @else {
+            table class="table table-sm" {
+                thead {
+                    tr {
+                        th { "Job" }
+                        th { "Started (UTC)" }
+                        th { "Duration" }
+                        th { "Status" }
+                        th { "Items changed" }
+                        th { "Error" }
+                    }
+                }
+                tbody {
+                    @for run in runs {
+                        tr {
+                            td { (run.name) }
+                            td class="whitespace-nowrap" { (timestamp(run.started_at)) }
+                            td { (run.duration_text().unwrap_or_default()) }
+                            td { (status_badge(&run.status)) }
+                            td { (run.items_changed.map(|items| items.to_string()).unwrap_or_default()) }
+                            td class="font-mono text-sm" { (run.error.as_deref().unwrap_or_default()) }
+                        }
+                    }
+                }
+            }
+        }
+    );
+
+    page("Jobs", body, headers)
+}
+
+/// Generates the releases matching the search of the dashboard, with the links to
+/// correct them and their artist.
+pub fn search_results(query: &str, results: &[(Release, Artist)]) -> Markup {
+    html!(
+        div #admin_results {
+            @if !query.trim().is_empty() && results.is_empty() {
+                p { "No release matches your search." }
+            } @else if !results.is_empty() {
+                table class="table table-sm" {
+                    tbody {
+                        @for (release, artist) in results {
+                            tr {
+                                td class="whitespace-nowrap" { (release.date()) }
+                                td {
+                                    a href=(format!("/admin/artists/{}", artist.id)) class="link link-primary visited:link-secondary focus:link-accent" { (artist.name) }
+                                }
+                                td {
+                                    a href=(format!("/admin/releases/{}", release.id)) class="link link-primary visited:link-secondary focus:link-accent" { (release.album) }
+                                }
+                                td class="text-gray-500" { (artist.genre.as_deref().unwrap_or_default()) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
+
+/// Generates the form correcting the genre and the links of an artist.
+pub fn artist(artist: &Artist, genres: &[String], headers: HeaderMap) -> Markup {
+    let body = html!(
+        h4 class="text-3xl font-bold mb-1" { (artist.name) }
+        p class="mb-6" {
+            a href=(format!("/artists/{}", artist.id)) class="link link-primary visited:link-secondary focus:link-accent" { "See the public page" }
+        }
+        form class="md:w-1/2" hx-post=(format!("/admin/artists/{}", artist.id)) hx-target="#admin_message" hx-swap="outerHTML" {
+            label class="form-control mb-3" {
+                span class="label-text" { "Genre" }
+                input type="text" name="genre" value=(artist.genre.as_deref().unwrap_or_default()) list="genres" class="input input-bordered";
+            }
+            label class="form-control mb-3" {
+                span class="label-text" { "Bandcamp page" }
+                input type="url" name="url_bandcamp" value=(artist.url_bandcamp.as_deref().unwrap_or_default())
+                    placeholder="https://artist.bandcamp.com" class="input input-bordered";
+            }
+            label class="form-control mb-3" {
+                span class="label-text" { "Metallum page" }
+                input type="url" name="url_metallum" value=(artist.url_metallum.as_deref().unwrap_or_default())
+                    placeholder="https://www.metal-archives.com/bands/..." class="input input-bordered";
+            }
+            (datalist("genres", genres))
+            button type="submit" class="btn" { "Save" }
+        }
+        (message(html!(
+            @if artist.bandcamp_locked {
+                "The Bandcamp link was set by an administrator and is never searched again."
+            } @else {
+                "A Bandcamp link set here is never searched again."
+            }
+        )))
+    );
+
+    page(&artist.name, body, headers)
+}
+
+/// Generates the page hiding a release, or merging it into another release.
+///
+/// The other releases of the artist are offered as the release to keep.
+pub fn release(
+    release: &Release,
+    artist: &Artist,
+    others: &[Release],
+    headers: HeaderMap,
+) -> Markup {
+    let title = format!("{} - {}", artist.name, release.album);
+
+    let body = html!(
+        h4 class="text-3xl font-bold mb-1" { (release.album) }
+        p class="mb-3 text-xl" {
+            a href=(format!("/admin/artists/{}", artist.id)) class="link link-primary visited:link-secondary focus:link-accent" { (artist.name) }
+        }
+        ul class="mb-6 text-gray-500" {
+            li { (release.date()) }
+            @if let Some(release_type) = &release.release_type {
+                li { (release_type) }
+            }
+            li { (status_text(release)) }
+            @if !release.is_hidden {
+                li {
+                    a href=(release.permalink(artist, "")) class="link link-primary visited:link-secondary focus:link-accent" { "See the public page" }
+                }
+            }
+        }
+        div class="flex flex-wrap gap-4 mb-8" {
+            @if release.is_hidden {
+                button class="btn" hx-post=(format!("/admin/releases/{}/show", release.id)) hx-target="#admin_message" hx-swap="outerHTML" { "Show again" }
+            } @else {
+                button class="btn btn-error" hx-post=(format!("/admin/releases/{}/hide", release.id)) hx-target="#admin_message" hx-swap="outerHTML"
+                    hx-confirm="Hide this release from the calendar, the feeds and the API?" { "Hide" }
+            }
+        }
+        @if release.merged_into.is_none() {
+            h5 class="text-2xl font-bold mb-3" { "Merge this duplicate into another release" }
+            @if !others.is_empty() {
+                table class="table table-sm md:w-1/2 mb-3" {
+                    tbody {
+                        @for other in others {
+                            tr {
+                                td class="whitespace-nowrap" { (other.date()) }
+                                td { (other.album) }
+                                td {
+                                    button class="btn btn-sm" hx-post=(format!("/admin/releases/{}/merge", release.id))
+                                        hx-vals=(format!(r#"{{"into": {}}}"#, other.id))
+                                        hx-target="#admin_message" hx-swap="outerHTML" { "Keep this one" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            form class="flex flex-wrap gap-2 md:w-1/2" hx-post=(format!("/admin/releases/{}/merge", release.id)) hx-target="#admin_message" hx-swap="outerHTML" {
+                input type="number" name="into" min="1" required placeholder="ID of the release to keep" class="input input-bordered grow";
+                button type="submit" class="btn" { "Merge" }
+            }
+        }
+        (message(html!("The corrections are kept by the calendar updates.")))
+    );
+
+    page(&title, body, headers)
+}
+
+/// Generates the form adding a release that no source lists.
+pub fn new_release(bands: &[String], genres: &[String], headers: HeaderMap) -> Markup {
+    let body = html!(
+        h4 class="text-3xl font-bold mb-3" { "Add a release" }
+        form class="md:w-1/2" hx-post="/admin/releases/new" hx-target="#admin_message" hx-swap="outerHTML" {
+            label class="form-control mb-3" {
+                span class="label-text" { "Artist" }
+                input type="text" name="artist" required list="bands" class="input input-bordered";
+            }
+            label class="form-control mb-3" {
+                span class="label-text" { "Genre of a new artist" }
+                input type="text" name="genre" list="genres" class="input input-bordered";
+            }
+            label class="form-control mb-3" {
+                span class="label-text" { "Album" }
+                input type="text" name="album" required class="input input-bordered";
+            }
+            label class="form-control mb-3" {
+                span class="label-text" { "Release date" }
+                input type="date" name="date" required class="input input-bordered";
+            }
+            label class="form-control mb-3" {
+                span class="label-text" { "Type" }
+                select name="release_type" class="select select-bordered" {
+                    @for release_type in RELEASE_TYPES {
+                        option value=(release_type) { (release_type) }
+                    }
+                }
+            }
+            label class="form-control mb-3" {
+                span class="label-text" { "Label" }
+                input type="text" name="label" class="input input-bordered";
+            }
+            (datalist("bands", bands))
+            (datalist("genres", genres))
+            button type="submit" class="btn" { "Add" }
+        }
+        (message(html!("The release is kept even though no source lists it.")))
+    );
+
+    page("Add a release", body, headers)
+}
+
+/// Generates the outcome of an action of the administrators.
+pub fn message(content: Markup) -> Markup {
+    html!(p #admin_message class="mt-3" { (content) })
+}
+
+fn page(title: &str, content: Markup, headers: HeaderMap) -> Markup {
+    let body = html!(
+        section class="col-span-12" style="background: linear-gradient(90deg, #D73737 0%, #3D3D3D 100%)" {}
+        section class="col-span-12 container mx-auto px-6 p-10" {
+            ul class="flex flex-wrap gap-4 mb-6" {
+                li { a href="/admin" class="link link-hover" { "Dashboard" } }
+                li { a href="/admin/jobs" class="link link-hover" { "Jobs" } }
+                li { a href="/admin/releases/new" class="link link-hover" { "Add a release" } }
+            }
+            (content)
+        }
+    );
+
+    match headers.get("HX-Request") {
+        Some(_) => html!(
+            title hx-swap-oob="true" { (title) " | Heavy Metal Releases" }
+            (body)
+            (footer())
+        ),
+        None => layout(title, true, Page::Other, body),
+    }
+}
+
+/// Lists the jobs with their schedule and their last run, along with the buttons
+/// running them on demand if asked.
+fn job_schedule(runs: &[JobRun], with_buttons: bool) -> Markup {
+    html!(
+        table class="table table-sm w-full md:w-2/3 mb-3" {
+            thead {
+                tr {
+                    th { "Job" }
+                    th { "Schedule" }
+                    th { "Last run (UTC)" }
+                    @if with_buttons {
+                        th {}
+                    }
+                }
+            }
+            tbody {
+                @for job in ScheduledJob::ALL {
+                    tr {
+                        td { (job.name()) }
+                        td class="font-mono" { (job.cron()) }
+                        td {
+                            @match runs.iter().find(|run| run.name == job.name()) {
+                                Some(run) => (timestamp(run.started_at)) " " (status_badge(&run.status)),
+                                None => span class="text-gray-500" { "Never" },
+                            }
+                        }
+                        @if with_buttons {
+                            td {
+                                @if job.can_run_on_demand() {
+                                    button class="btn btn-sm" hx-post=(format!("/admin/jobs/{}/run", job.name()))
+                                        hx-target="#admin_message" hx-swap="outerHTML" { "Run now" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        @if with_buttons {
+            (message(html!()))
+        }
+    )
+}
+
+fn datalist(id: &str, values: &[String]) -> Markup {
+    html!(
+        datalist id=(id) {
+            @for value in values {
+                option value=(value) {}
+            }
+        }
+    )
+}
+
+fn status_badge(status: &str) -> Markup {
+    let class = match status {
+        "success" => "badge badge-success",
+        "failure" => "badge badge-error",
+        "running" => "badge badge-info",
+        _ => "badge badge-warning",
+    };
+
+    html!(span class=(class) { (status) })
+}
+
+/// Describes the corrections of the release, e.g. `Merged into release 42`.
+fn status_text(release: &Release) -> String {
+    match (release.merged_into, release.is_hidden, release.is_manual) {
+        (Some(id), _, _) => format!("Merged into release {id}"),
+        (None, true, _) => String::from("Hidden"),
+        (None, false, true) => String::from("Added by hand"),
+        _ if release.is_removed => String::from("No longer listed by the sources"),
+        _ => String::from("Listed by the sources"),
+    }
+}
+
+/// Formats the Unix timestamp as a UTC date and time, e.g. `2026-10-17 08:00`.
+fn timestamp(at: i64) -> String {
+    OffsetDateTime::from_unix_timestamp(at)
+        .map(|at| format!("{} {:02}:{:02}", at.date(), at.hour(), at.minute()))
+        .unwrap_or_default()
+}
@@ -0,0 +1,127 @@
+use axum::http::HeaderMap;
+use maud::{Markup, html};
+use time::Date;
+
+use super::{
+    Page,
+    core::{footer, layout},
+};
+use crate::model::{Artist, Release};
+
+/// Generates the page of an artist listing its upcoming releases and its discography.
+pub fn artist(artist: &Artist, releases: &[Release], today: Date, headers: HeaderMap) -> Markup {
+    let (upcoming, past): (Vec<&Release>, Vec<&Release>) =
+        releases.iter().partition(|release| release.date() >= today);
+
+    let body = html!(
+        section class="col-span-12" style="background: linear-gradient(90deg, #D73737 0%, #3D3D3D 100%)" {}
+        section class="col-span-12 container mx-auto px-6 p-10" {
+            h4 class="text-3xl font-bold mb-1" { (artist.name) }
+            @if let Some(genre) = &artist.genre {
+                p class="mb-3 text-gray-500" { (genre) }
+            }
+            @if let Some(country) = &artist.country {
+                p class="mb-3 text-gray-500" { (country) }
+            }
+            ul class="flex flex-wrap gap-4 mb-6" {
+                @if let Some(url) = &artist.url_bandcamp {
+                    li { a href=(url) target="_blank" class="link link-primary visited:link-secondary focus:link-accent" { "Bandcamp" } }
+                }
+                @if let Some(url) = &artist.url_metallum {
+                    li { a href=(url) target="_blank" class="link link-primary visited:link-secondary focus:link-accent" { "Metallum" } }
+                }
+                li {
+                    a href=(format!("https://www.youtube.com/results?search_query={}", url_escape::encode_component(&artist.name)))
+                      target="_blank" class="link link-primary visited:link-secondary focus:link-accent" { "YouTube" }
+                }
+            }
+            div #artist_subscribe class="mb-8" {
+                button class="btn" hx-post=(format!("/artists/{}/subscribe", artist.id)) hx-target="#artist_subscribe" {
+                    "Subscribe to this band"
+                }
+            }
+            h5 class="text-2xl font-bold mb-3" { "Upcoming releases" }
+            @if upcoming.is_empty() {
+                p class="mb-8" { "No upcoming release announced yet." }
+            } @else {
+                ul class="mb-8" {
+                    @for release in &upcoming {
+                        li class="mb-2 p-3 rounded bg-blue-100 dark:bg-blue-900" { (release_line(release, artist)) }
+                    }
+                }
+            }
+            h5 class="text-2xl font-bold mb-3" { "Discography" }
+            @if past.is_empty() {
+                p { "No past release." }
+            } @else {
+                ul {
+                    @for release in past.iter().rev() {
+                        li class="mb-2" { (release_line(release, artist)) }
+                    }
+                }
+            }
+        }
+    );
+
+    match headers.get("HX-Request") {
+        Some(_) => html!(
+            title hx-swap-oob="true" { (artist.name) " | Heavy Metal Releases" }
+            (body)
+            (footer())
+        ),
+        None => layout(&artist.name, true, Page::Other, body),
+    }
+}
+
+/// Generates the links to the custom feed of an artist, shown once subscribed.
+pub fn artist_feed_links(host_url: &str, custom_feed_id: i32) -> Markup {
+    let feed_url = |path: &str| format!("{host_url}/calendar/{path}?id={custom_feed_id}");
+
+    html!(
+        p class="mb-1" { "Add this feed to your RSS app:" }
+        input readonly type="text" class="input input-bordered w-full md:w-1/2" value=(feed_url("feed.xml"));
+        p class="mt-1" {
+            "Also available as "
+            a href=(feed_url("feed.atom")) class="link link-primary visited:link-secondary focus:link-accent" { "Atom" } ", "
+            a href=(feed_url("feed.json")) class="link link-primary visited:link-secondary focus:link-accent" { "JSON Feed" } " and "
+            a href=(feed_url("releases.ics")) class="link link-primary visited:link-secondary focus:link-accent" { "iCalendar" } "."
+        }
+        form class="mt-2 md:w-1/2" hx-post="/subscriptions" hx-target="#subscribe_message" hx-swap="outerHTML" {
+            input type="hidden" name="feed_id" value=(custom_feed_id);
+            input type="hidden" name="frequency" value="weekly";
+            div class="join w-full" {
+                input type="email" name="email" placeholder="Or get a weekly email: your@email.com" required class="input input-bordered join-item w-full";
+                button type="submit" class="btn join-item" { "Subscribe" }
+            }
+        }
+        p #subscribe_message class="mt-1" {}
+    )
+}
+
+fn release_line(release: &Release, artist: &Artist) -> Markup {
+    html!(
+        @if let Some(url) = release.cover_url("") {
+            img src=(url) alt=(format!("Cover of {}", release.album)) width="48" height="48" loading="lazy" class="inline-block align-middle object-cover rounded mr-2";
+        }
+        span class="font-mono mr-2" { (release.date()) }
+        a href=(release.permalink(artist, "")) class="font-bold link link-hover" { (release.album) }
+        @if let Some(release_type) = &release.release_type {
+            span class="text-gray-500" { " (" (release_type) ")" }
+        }
+        @if let Some(label) = &release.label {
+            span class="text-gray-500" { " · " (label) }
+        }
+        @if let Some(previous) = release.moved_from_date() {
+            " "
+            span class="badge badge-warning" title=(format!("Previously on {previous}")) {
+                @if previous < release.date() { "Postponed" } @else { "Moved" }
+            }
+        }
+        " "
+        a href=(release.url_youtube) target="_blank" class="link link-primary visited:link-secondary focus:link-accent" { "YouTube" }
+        @if let Some(url) = &release.url_metallum {
+            " · "
+            a href=(url) target="_blank" class="link link-primary visited:link-secondary focus:link-accent" { "Metallum" }
+        }
+    )
+}
@@ -7,7 +7,10 @@ use crate::{
     model::{Artist, Release},
     web::{
         handlers_calendar::CalendarDay,
-        templates::{Page, core::head},
+        templates::{
+            Page,
+            core::{ShareMeta, head},
+        },
     },
 };
 
@@ -15,10 +18,12 @@ use super::core::layout;
 
 /// Generates HTML for a list of releases for a specific date.
 pub fn feeds(date: &str, releases: Vec<(Release, Artist)>) -> Markup {
+    let title = format!("Releases {date}");
+
     html!(
         (DOCTYPE)
         html lang="en" {
-          (head(&format!("Releases {date}")))
+          (head(&title, &ShareMeta::site(&title)))
           body {
             (feeds_list(releases.as_ref()))
           }
@@ -31,9 +36,10 @@ pub fn calendar(
     date: OffsetDateTime,
     days: Vec<CalendarDay>,
     releases: Option<Vec<(Release, Artist)>>,
+    years: &[i32],
     headers: HeaderMap,
 ) -> Markup {
-    let body = html!((render_calendar(date, days, releases)));
+    let body = html!((render_calendar(date, days, releases, years)));
 
     match headers.get("HX-Request") {
         Some(_) => html!(
@@ -45,10 +51,14 @@ pub fn calendar(
 }
 
 /// Generates HTML for the calendar grid.
+///
+/// The `years` are the years with releases in the archive, which can be jumped to
+/// from the year selector.
 pub fn render_calendar(
     date: OffsetDateTime,
     days: Vec<CalendarDay>,
     releases: Option<Vec<(Release, Artist)>>,
+    years: &[i32],
 ) -> Markup {
     let weekdays = vec!["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
     let has_releases = releases.is_some();
@@ -68,6 +78,33 @@ pub fn render_calendar(
                 }
               }
               div class="flex items-center gap-2" {
+                @if !years.is_empty() {
+                  button class="text-gray-500 rounded px-2 py-1 text-xs font-medium hover:bg-gray-100 hover:text-gray-900 dark:bg-black"
+                         title="Previous year"
+                         hx-get=(format!("/calendar/{}/{}/{}/releases", date.year() - 1, date.month(), 10))
+                         hx-target="#calendar" {
+                    "«"
+                  }
+                  select name="year" aria-label="Year"
+                         class="py-1.5 px-2 rounded-md bg-gray-50 border border-gray-300 text-xs font-medium dark:bg-gray-800"
+                         hx-get="/calendar/archive"
+                         hx-trigger="change"
+                         hx-target="#calendar"
+                         hx-vals=(format!(r#"{{"month": {}}}"#, date.month() as u8)) {
+                    @if !years.contains(&date.year()) {
+                      option value=(date.year()) selected { (date.year()) }
+                    }
+                    @for year in years.iter().rev() {
+                      option value=(year) selected[*year == date.year()] { (year) }
+                    }
+                  }
+                  button class="text-gray-500 rounded px-2 py-1 text-xs font-medium hover:bg-gray-100 hover:text-gray-900 dark:bg-black"
+                         title="Next year"
+                         hx-get=(format!("/calendar/{}/{}/{}/releases", date.year() + 1, date.month(), 10))
+                         hx-target="#calendar" {
+                    "»"
+                  }
+                }
                 button class="hidden md:flex py-2 pl-1.5 pr-3 rounded-md bg-gray-50 border border-gray-300 items-center gap-1.5 text-xs font-medium hover:bg-gray-100 dark:bg-gray-800 dark:hover:bg-gray-600"
                        hx-get=(format!("/calendar/{}/{}/{}/releases", now.year(), now.month(), now.day()))
                        hx-target="#calendar" {
@@ -2,12 +2,50 @@ use maud::{DOCTYPE, Markup, html};
 
 use crate::{config::config, web::templates::Page};
 
+/// Describes a page to the apps unfurling its shared links, e.g. in a chat, through the
+/// OpenGraph and Twitter meta tags.
+pub struct ShareMeta {
+    pub title: String,
+    pub description: String,
+    /// The canonical URL of the page.
+    pub url: String,
+    /// The URL of the image shown along with the link, if any.
+    pub image: Option<String>,
+}
+
+impl ShareMeta {
+    /// Describes the site as a whole, for the pages without their own description.
+    pub fn site(title: &str) -> Self {
+        Self {
+            title: if title.is_empty() {
+                String::from("Heavy Metal Releases")
+            } else {
+                format!("{title} | Heavy Metal Releases")
+            },
+            description: String::from("Be notified of new heavy metal album releases."),
+            url: format!("{}/", config().HOST_URL),
+            image: Some(format!("{}/public/img/logo-64x64.png", config().HOST_URL)),
+        }
+    }
+}
+
 /// Generates the main layout for the application.
 pub fn layout(title: &str, is_show_nav: bool, page: Page, content: Markup) -> Markup {
+    layout_shared(title, is_show_nav, page, &ShareMeta::site(title), content)
+}
+
+/// Generates the main layout for a page described by its own share metadata.
+pub fn layout_shared(
+    title: &str,
+    is_show_nav: bool,
+    page: Page,
+    meta: &ShareMeta,
+    content: Markup,
+) -> Markup {
     html!(
         (DOCTYPE)
         html lang="en" {
-            (head(title))
+            (head(title, meta))
             @if is_show_nav {
                 (nav(page))
             }
@@ -25,7 +63,7 @@ pub fn layout(title: &str, is_show_nav: bool, page: Page, content: Markup) -> Ma
 }
 
 /// Generates the HTML for the document head.
-pub fn head(title: &str) -> Markup {
+pub fn head(title: &str, meta: &ShareMeta) -> Markup {
     html!(
         head {
             title {
@@ -38,12 +76,26 @@ pub fn head(title: &str) -> Markup {
             meta charset="UTF-8";
             meta http-equiv="X-UA-Compatible" content="IE=edge";
             meta name="viewport" content="width=device-width, initial-scale=1.0";
-            meta name="description" content="Be notified of new heavy metal album releases.";
+            meta name="description" content=(meta.description);
             meta name="keywords" content="heavy metal, album releases, automation";
-            link rel="canonical" href="https://metal.musicavis.ca/";
+            meta property="og:site_name" content="Heavy Metal Releases";
+            meta property="og:type" content="website";
+            meta property="og:title" content=(meta.title);
+            meta property="og:description" content=(meta.description);
+            meta property="og:url" content=(meta.url);
+            meta name="twitter:card" content="summary";
+            meta name="twitter:title" content=(meta.title);
+            meta name="twitter:description" content=(meta.description);
+            @if let Some(image) = &meta.image {
+                meta property="og:image" content=(image);
+                meta name="twitter:image" content=(image);
+            }
+            link rel="canonical" href=(meta.url);
             link rel="icon" href="/public/favicon.png" type="image/x-icon";
             link rel="stylesheet" href="/public/css/tailwind.css";
             link rel="alternate" type="application/rss+xml" title="Heavy Metal Releases Feed" href=(format!("{}/calendar/feed.xml", config().HOST_URL));
+            link rel="alternate" type="application/atom+xml" title="Heavy Metal Releases Feed" href=(format!("{}/calendar/feed.atom", config().HOST_URL));
+            link rel="alternate" type="application/feed+json" title="Heavy Metal Releases Feed" href=(format!("{}/calendar/feed.json", config().HOST_URL));
             script src="https://unpkg.com/htmx.org@2.0.3" integrity="sha384-0895/pl2MU10Hqc6jd4RvrthNlDiE9U1tWmX7WRESftEDRosgxNsQG/Ze9YMRzHq" crossorigin="anonymous" {}
             script src="https://unpkg.com/htmx-ext-multi-swap@2.0.0/multi-swap.js" {}
             script src="https://unpkg.com/hyperscript.org@0.9.13" {}
@@ -111,6 +163,14 @@ fn nav_items(page: Page) -> Markup {
             button hx-get="/calendar" hx-target="#content" hx-push-url="true"
              _="on click remove .font-bold from .nav__item then add .font-bold to closest <li/> then call document.querySelector('button').focus()" { "Calendar" }
         }
+        li class={
+                "nav__item"
+                @if page == Page::Search { " font-bold"}
+                @if page != Page::Search { " hover:text-gray-800 dark:hover:text-gray-300" }
+            } {
+            button hx-get="/search" hx-target="#content" hx-push-url="true"
+             _="on click remove .font-bold from .nav__item then add .font-bold to closest <li/> then call document.querySelector('button').focus()" { "Search" }
+        }
         li class={
                 "nav__item"
                 @if page == Page::Calendar { " font-bold"}
@@ -40,7 +40,7 @@ pub async fn index(headers: HeaderMap, State(state): State<AppState>) -> Markup
                     div class="md:hidden" {
                         div class="flex" {
                             div class="mb-8" {
-                                (rss_apps(&state.bands, &state.genres))
+                                (rss_apps(&state))
                             }
                             div {
                                 img src="/public/img/day-of-tentacle.png" alt="Monitoring" style="height: 10rem; width: 30rem;";
@@ -48,7 +48,7 @@ pub async fn index(headers: HeaderMap, State(state): State<AppState>) -> Markup
                         }
                     }
                     div class="hidden md:block" {
-                        (rss_apps(&state.bands, &state.genres))
+                        (rss_apps(&state))
                     }
                 }
                 div class="hidden md:block w-full md:w-1/2" {
@@ -127,12 +127,12 @@ pub async fn index(headers: HeaderMap, State(state): State<AppState>) -> Markup
     }
 }
 
-fn rss_apps(bands: &Vec<String>, genres: &[String; 46]) -> Markup {
+fn rss_apps(state: &AppState) -> Markup {
     html!(
         p {
             "The only thing you must do is install an RSS app and add the "
             a href=(format!("{}/calendar/feed.xml", config().HOST_URL)) class="link link-primary visited:link-secondary focus:link-accent" { (format!("{}/calendar/feed.xml", config().HOST_URL)) }
-            " feed. You may also customize your list according to the bands and genres you wish to track."
+            " feed. You may also customize your list according to the bands and genres you wish to track, and narrow it down to countries and record labels."
         }
         div class="my-4" {
             p class="font-bold text-center mb-1" { "Customize your feed" }
@@ -141,7 +141,7 @@ fn rss_apps(bands: &Vec<String>, genres: &[String; 46]) -> Markup {
                     option disabled selected class="truncate" { "Choose bands to follow (CRTL+Click)" }
                     option { "All" }
                     option { "None" }
-                    @for band in bands {
+                    @for band in &state.bands {
                         option { (band) }
                     }
                 }
@@ -149,16 +149,63 @@ fn rss_apps(bands: &Vec<String>, genres: &[String; 46]) -> Markup {
                     option disabled selected class="truncate" { "Choose genres to follow (CRTL+Click)" }
                     option { "All" }
                     option { "None" }
-                    @for genre in genres {
+                    @for genre in &state.genres {
                         option { (genre) }
                     }
                 }
+                select class="select select-bordered w-full min-h-48 md:w-1/2" name="countries" multiple {
+                    option disabled selected class="truncate" { "Only these countries (CRTL+Click)" }
+                    @for country in &state.countries {
+                        option { (country) }
+                    }
+                }
+                select class="select select-bordered w-full min-h-48 md:w-1/2" name="labels" multiple {
+                    option disabled selected class="truncate" { "Only these labels (CRTL+Click)" }
+                    @for label in &state.labels {
+                        option { (label) }
+                    }
+                }
                 button type="submit" class="btn btn-wide w-full mt-1" {
                     "Generate Feed"
                 }
             }
             input #custom_link readonly type="text" placeholder="Your custom link to copy" class="input input-bordered w-full mt-1";
         }
+        div class="my-4" {
+            p class="font-bold text-center mb-1" { "Or receive the releases by email" }
+            form hx-post="/subscriptions" hx-target="#subscribe_message" hx-swap="outerHTML" {
+                input #subscribe_feed_id type="hidden" name="feed_id" value="-1";
+                input type="email" name="email" placeholder="your@email.com" required class="input input-bordered w-full";
+                select class="select select-bordered w-full mt-1" name="frequency" {
+                    option value="daily" selected { "Daily digest" }
+                    option value="weekly" { "Weekly digest" }
+                }
+                button type="submit" class="btn btn-wide w-full mt-1" {
+                    "Subscribe"
+                }
+            }
+            p #subscribe_message class="mt-1" {
+                "The digest follows your custom feed when you generate one first."
+            }
+        }
+        div class="my-4" {
+            p class="font-bold text-center mb-1" { "Or post them to your team chat" }
+            form hx-post="/webhooks" hx-target="#webhook_message" hx-swap="outerHTML" {
+                input #webhook_feed_id type="hidden" name="feed_id" value="-1";
+                input type="url" name="url" placeholder="https://discord.com/api/webhooks/..." required class="input input-bordered w-full";
+                select class="select select-bordered w-full mt-1" name="kind" {
+                    option value="discord" selected { "Discord" }
+                    option value="slack" { "Slack" }
+                    option value="json" { "JSON (signed)" }
+                }
+                button type="submit" class="btn btn-wide w-full mt-1" {
+                    "Add Webhook"
+                }
+            }
+            p #webhook_message class="mt-1" {
+                "Today's releases are posted every day, along with the newly announced ones whenever the calendar is updated."
+            }
+        }
         p { "Example RSS apps:" }
         p {
             b {"Android:" }
@@ -1,7 +1,12 @@
 mod core;
 
+pub mod admin;
+pub mod artist;
 pub mod calendar;
 pub mod main;
+pub mod release;
+pub mod search;
+pub mod subscriptions;
 
 /// Represents a page within an application.
 #[derive(PartialEq)]
@@ -11,4 +16,5 @@ pub enum Page {
     Contact,
     Home,
     Other,
+    Search,
 }
@@ -0,0 +1,172 @@
+use axum::http::HeaderMap;
+use maud::{Markup, html};
+
+use super::{
+    Page,
+    core::{ShareMeta, footer, layout_shared},
+};
+use crate::{
+    config::config,
+    model::{Artist, Release, ReleaseDetails, Track},
+};
+
+/// Generates the page of a release with its details, its tracklist and the other
+/// releases of the same day.
+pub fn release(
+    release: &Release,
+    artist: &Artist,
+    details: Option<&ReleaseDetails>,
+    tracks: &[Track],
+    same_day: &[(Release, Artist)],
+    headers: HeaderMap,
+) -> Markup {
+    let title = format!("{} - {}", artist.name, release.album);
+    let num_discs = tracks.iter().map(|track| track.disc).max().unwrap_or(1);
+    let label = release
+        .label
+        .as_ref()
+        .or(details.and_then(|details| details.label.as_ref()));
+
+    let body = html!(
+        section class="col-span-12" style="background: linear-gradient(90deg, #D73737 0%, #3D3D3D 100%)" {}
+        section class="col-span-12 container mx-auto px-6 p-10" {
+            div class="flex flex-col md:flex-row gap-6 mb-8" {
+                @if let Some(url) = release.cover_url("") {
+                    img src=(url) alt=(format!("Cover of {}", release.album)) width="300" class="rounded shadow self-start";
+                }
+                div {
+                    h4 class="text-3xl font-bold mb-1" { (release.album) }
+                    p class="mb-3 text-xl" {
+                        a href=(format!("/artists/{}", artist.id)) class="link link-primary visited:link-secondary focus:link-accent" { (artist.name) }
+                    }
+                    ul class="mb-6 text-gray-500" {
+                        li {
+                            a href=(format!("/calendar/{}/{}/{}", release.year, release.month, release.day)) class="link link-hover" { (release.date()) }
+                            @if let Some(previous) = release.moved_from_date() {
+                                " "
+                                span class="badge badge-warning" title=(format!("Previously on {previous}")) {
+                                    @if previous < release.date() { "Postponed" } @else { "Moved" }
+                                }
+                            }
+                        }
+                        @if let Some(release_type) = &release.release_type {
+                            li { (release_type) }
+                        }
+                        @if let Some(genre) = &artist.genre {
+                            li { (genre) }
+                        }
+                        @if let Some(country) = &artist.country {
+                            li { (country) }
+                        }
+                        @if let Some(label) = label {
+                            li { (label) }
+                        }
+                        @if let Some(format) = details.and_then(|details| details.format.as_ref()) {
+                            li { (format) }
+                        }
+                        @if let Some(runtime) = details.and_then(ReleaseDetails::runtime_text) {
+                            li { "Running time: " (runtime) }
+                        }
+                    }
+                    ul class="flex flex-wrap gap-4" {
+                        li { a href=(release.url_youtube) target="_blank" class="link link-primary visited:link-secondary focus:link-accent" { "YouTube" } }
+                        @if let Some(url) = &artist.url_bandcamp {
+                            li { a href=(url) target="_blank" class="link link-primary visited:link-secondary focus:link-accent" { "Bandcamp" } }
+                        }
+                        @if let Some(url) = &artist.url_metallum {
+                            li { a href=(url) target="_blank" class="link link-primary visited:link-secondary focus:link-accent" { "Metallum (band)" } }
+                        }
+                        @if let Some(url) = &release.url_metallum {
+                            li { a href=(url) target="_blank" class="link link-primary visited:link-secondary focus:link-accent" { "Metallum (album)" } }
+                        }
+                        @if let Some(id) = &release.musicbrainz_id {
+                            li { a href=(format!("https://musicbrainz.org/release-group/{id}")) target="_blank" class="link link-primary visited:link-secondary focus:link-accent" { "MusicBrainz" } }
+                        }
+                    }
+                }
+            }
+            @if !tracks.is_empty() {
+                h5 class="text-2xl font-bold mb-3" { "Tracklist" }
+                table class="table table-sm w-full md:w-1/2 mb-8" {
+                    tbody {
+                        @for track in tracks {
+                            @if num_discs > 1 && track.position == 1 {
+                                tr { th colspan="3" { "Disc " (track.disc) } }
+                            }
+                            tr {
+                                td class="w-8" { (track.position) "." }
+                                td { (track.title) }
+                                td class="text-right font-mono" { (track.duration_text().unwrap_or_default()) }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !same_day.is_empty() {
+                h5 class="text-2xl font-bold mb-3" { "Other releases that day" }
+                ul {
+                    @for (other, other_artist) in same_day {
+                        li class="mb-2" {
+                            a href=(other.permalink(other_artist, "")) class="link link-primary visited:link-secondary focus:link-accent" {
+                                (other_artist.name) " - " (other.album)
+                            }
+                            @if let Some(genre) = &other_artist.genre {
+                                span class="text-gray-500" { " · " (genre) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    );
+
+    match headers.get("HX-Request") {
+        Some(_) => html!(
+            title hx-swap-oob="true" { (title) " | Heavy Metal Releases" }
+            (body)
+            (footer())
+        ),
+        None => layout_shared(
+            &title,
+            true,
+            Page::Other,
+            &share_meta(release, artist, label.map(String::as_str)),
+            body,
+        ),
+    }
+}
+
+/// Describes the release to the apps unfurling its permalink, e.g.
+/// `Full-length by Wintersun, out on August 30, 2024 on Nuclear Blast.`
+fn share_meta(release: &Release, artist: &Artist, label: Option<&str>) -> ShareMeta {
+    let host = &config().HOST_URL;
+    let date = release.date();
+
+    let mut description = format!(
+        "{} by {}",
+        release.release_type.as_deref().unwrap_or("Album"),
+        artist.name
+    );
+    if let Some(genre) = &artist.genre {
+        description.push_str(&format!(" ({genre})"));
+    }
+    description.push_str(&format!(
+        ", out on {} {}, {}",
+        date.month(),
+        date.day(),
+        date.year()
+    ));
+    if let Some(label) = label {
+        description.push_str(&format!(" on {label}"));
+    }
+    description.push('.');
+
+    ShareMeta {
+        title: format!("{} - {}", artist.name, release.album),
+        description,
+        url: release.permalink(artist, host),
+        image: release
+            .cover_url(host)
+            .or_else(|| Some(format!("{host}/public/img/logo-64x64.png"))),
+    }
+}
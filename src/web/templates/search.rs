@@ -0,0 +1,92 @@
+use axum::http::HeaderMap;
+use maud::{Markup, html};
+
+use super::{
+    Page,
+    core::{footer, layout},
+};
+use crate::model::{Artist, Release};
+
+/// Generates the search page with the results of the query, if any.
+///
+/// Only the results are returned when they are requested by the search input.
+pub fn search(query: &str, results: &[(Release, Artist)], headers: HeaderMap) -> Markup {
+    if headers
+        .get("HX-Target")
+        .is_some_and(|target| target == "search_results")
+    {
+        return search_results(query, results);
+    }
+
+    let body = html!(
+        section class="col-span-12" style="background: linear-gradient(90deg, #D73737 0%, #3D3D3D 100%)" {}
+        section class="col-span-12 container mx-auto px-6 p-10" {
+            h4 class="text-3xl font-bold mb-3" { "Search" }
+            form action="/search" method="get" {
+                input type="search" name="q" value=(query) autofocus
+                    placeholder="Search artists and albums, e.g. motorhead"
+                    class="input input-bordered w-full md:w-1/2"
+                    hx-get="/search" hx-trigger="input changed delay:300ms, search"
+                    hx-target="#search_results" hx-swap="outerHTML" hx-push-url="true";
+            }
+            (search_results(query, results))
+        }
+    );
+
+    match headers.get("HX-Request") {
+        Some(_) => html!(
+            title hx-swap-oob="true" { "Search | Heavy Metal Releases" }
+            (body)
+            (footer())
+        ),
+        None => layout("Search", true, Page::Search, body),
+    }
+}
+
+fn search_results(query: &str, results: &[(Release, Artist)]) -> Markup {
+    html!(
+        div #search_results class="mt-4" {
+            @if query.trim().is_empty() {
+                p { "Type the name of an artist or an album." }
+            } @else if results.is_empty() {
+                p { "No release matches your search." }
+            } @else {
+                table class="table" {
+                    thead {
+                        tr {
+                            th { "Date" }
+                            th { "Artist" }
+                            th { "Album" }
+                            th { "Genre" }
+                        }
+                    }
+                    tbody {
+                        @for (release, artist) in results {
+                            tr {
+                                td class="whitespace-nowrap" {
+                                    a href=(format!("/calendar/{}/{}/{}", release.year, release.month, release.day))
+                                      class="link link-primary visited:link-secondary focus:link-accent" {
+                                        (release.date())
+                                    }
+                                }
+                                td {
+                                    a href=(format!("/artists/{}", artist.id))
+                                      class="link link-primary visited:link-secondary focus:link-accent" {
+                                        (artist.name)
+                                    }
+                                }
+                                td {
+                                    a href=(release.permalink(artist, ""))
+                                      class="link link-primary visited:link-secondary focus:link-accent" {
+                                        (release.album)
+                                    }
+                                }
+                                td { (artist.genre.as_deref().unwrap_or_default()) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
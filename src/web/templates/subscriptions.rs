@@ -0,0 +1,73 @@
+use maud::{DOCTYPE, Markup, PreEscaped, html};
+use time::Date;
+
+use super::{Page, core::layout};
+use crate::model::{Artist, Release};
+
+/// Generates the page shown after following a link sent by email.
+pub fn message_page(title: &str, message: &str) -> Markup {
+    layout(
+        title,
+        true,
+        Page::Other,
+        html!(
+            section class="col-span-12" style="background: linear-gradient(90deg, #D73737 0%, #3D3D3D 100%)" {}
+            section class="col-span-12 container mx-auto px-6 p-10" {
+                h4 class="text-3xl font-bold mb-3" { (title) }
+                p class="mb-8" { (message) }
+                a href="/" class="link link-primary visited:link-secondary focus:link-accent" { "Back to the home page" }
+            }
+        ),
+    )
+}
+
+/// Generates the body of the email asking to confirm a subscription.
+pub fn confirmation_email(confirm_url: &str) -> String {
+    html!(
+        (DOCTYPE)
+        html lang="en" {
+            body {
+                h2 { "Confirm your subscription" }
+                p {
+                    "Someone, hopefully you, subscribed this address to the Heavy Metal Releases digest. "
+                    "Please confirm by clicking the link below. If it was not you, ignore this email."
+                }
+                p { a href=(confirm_url) { "Confirm my subscription" } }
+            }
+        }
+    )
+    .into_string()
+}
+
+/// Generates the body of the digest email listing the releases between two dates.
+pub fn digest_email(
+    from: Date,
+    to: Date,
+    releases: &[&(Release, Artist)],
+    unsubscribe_url: &str,
+) -> String {
+    html!(
+        (DOCTYPE)
+        html lang="en" {
+            body {
+                h2 {
+                    @if from == to {
+                        "Heavy metal releases of " (from)
+                    } @else {
+                        "Heavy metal releases from " (from) " to " (to)
+                    }
+                }
+                ol {
+                    @for (release, artist) in releases {
+                        (PreEscaped(release.to_html(artist)))
+                    }
+                }
+                p style="font-size: small" {
+                    "You receive this email because you subscribed to the Heavy Metal Releases digest. "
+                    a href=(unsubscribe_url) { "Unsubscribe" }
+                }
+            }
+        }
+    )
+    .into_string()
+}